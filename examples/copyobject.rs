@@ -0,0 +1,25 @@
+extern crate s3;
+
+use s3::{
+    Client,
+    CopyObject,
+    MetadataDirective,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    dotenv::dotenv()?;
+
+    let client = Client::load("http://s3.amazonaws.com/")?;
+
+    let resp = client
+        .send(
+            CopyObject::new("cadims", "vimrc.copy", "cadims", "vimrc")
+                .metadata_directive(MetadataDirective::Copy),
+        )
+        .await?;
+
+    println!("{:#?}", resp);
+
+    Ok(())
+}
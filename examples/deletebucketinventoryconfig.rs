@@ -2,7 +2,7 @@ extern crate s3;
 
 use s3::{
     client::Client,
-    DeleteBucketInventoryConfig,
+    DeleteBucketInventoryConfiguration,
 };
 
 static SECRET_ACCESS_KEY: &'static str = "NQMJwbNv0qjBBtAIPbV47JOnqrGCveuqVvO8XwuG";
@@ -21,7 +21,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let resp = client
         .send(
             // Method unimplemented for Min.io
-            DeleteBucketInventoryConfig::new("imageapi", "list1"),
+            DeleteBucketInventoryConfiguration::new("imageapi", "list1"),
         )
         .await?;
 
@@ -2,7 +2,7 @@ extern crate s3;
 
 use s3::{
     Client,
-    GetBucketAccelerateConfig,
+    GetBucketAccelerateConfiguration,
 };
 
 #[tokio::main]
@@ -12,7 +12,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let client = Client::load("http://s3.amazonaws.com/")?;
 
     let resp = client
-        .send(GetBucketAccelerateConfig::new("cadims"))
+        .send(GetBucketAccelerateConfiguration::new("cadims"))
         .await?;
 
     println!("{:#?}", resp);
@@ -2,7 +2,7 @@ extern crate s3;
 
 use s3::{
     client::Client,
-    GetBucketAnalyticsConfig,
+    GetBucketAnalyticsConfiguration,
 };
 
 static SECRET_ACCESS_KEY: &'static str = "NQMJwbNv0qjBBtAIPbV47JOnqrGCveuqVvO8XwuG";
@@ -20,7 +20,7 @@ async fn main() -> Result<(), anyhow::Error> {
 
     let resp = client
         // NOT SUPPORTED IN Min.io
-        .send(GetBucketAnalyticsConfig::new("test", "list"))
+        .send(GetBucketAnalyticsConfiguration::new("test", "list"))
         .await?;
 
     println!("{:#?}", resp);
@@ -0,0 +1,24 @@
+extern crate s3;
+
+use s3::{
+    Client,
+    HeadBucket,
+    HeadObject,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    dotenv::dotenv()?;
+
+    let client = Client::load("http://s3.amazonaws.com/")?;
+
+    let resp = client.send(HeadBucket::new("cadims")).await?;
+
+    println!("{:#?}", resp);
+
+    let resp = client.send(HeadObject::new("cadims", "vimrc")).await?;
+
+    println!("{:#?}", resp);
+
+    Ok(())
+}
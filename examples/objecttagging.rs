@@ -0,0 +1,47 @@
+extern crate s3;
+
+use s3::{
+    client::Client,
+    DeleteObjectTagging,
+    GetObjectTagging,
+    PutObjectTagging,
+    TagSet,
+};
+
+static SECRET_ACCESS_KEY: &'static str = "NQMJwbNv0qjBBtAIPbV47JOnqrGCveuqVvO8XwuG";
+static ACCESS_KEY: &'static str = "6KSUI28SEVTXB63GLSLU";
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    dotenv::dotenv()?;
+
+    let client = Client::builder()
+        .host("http://localhost:9000")
+        .access_key(&ACCESS_KEY)
+        .secret_key(&SECRET_ACCESS_KEY)
+        .build()?;
+
+    let mut tags = TagSet::new();
+    tags.insert("project", "s3")?;
+    tags.insert("stage", "example")?;
+
+    client
+        .send(PutObjectTagging::new(
+            "test",
+            "putobject_example_file",
+            tags,
+        ))
+        .await?;
+
+    let resp = client
+        .send(GetObjectTagging::new("test", "putobject_example_file"))
+        .await?;
+
+    println!("{:#?}", resp);
+
+    client
+        .send(DeleteObjectTagging::new("test", "putobject_example_file"))
+        .await?;
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use chrono::{
 };
 use s3::{
     client::Client,
+    Metadata,
     PutObject,
 };
 
@@ -22,6 +23,9 @@ async fn main() -> Result<(), anyhow::Error> {
         .secret_key(&SECRET_ACCESS_KEY)
         .build()?;
 
+    let mut metadata = Metadata::new();
+    metadata.insert("source", "putobject example")?;
+
     let resp = client
         .send(
             PutObject::new(
@@ -30,7 +34,8 @@ async fn main() -> Result<(), anyhow::Error> {
                 "random bytes".as_bytes().to_vec(),
             )
             .expires(Utc.ymd(2020, 1, 1).and_hms(0, 0, 0))
-            .grant_read_email("example@gmail.com"),
+            .grant_read_email("example@gmail.com")
+            .metadata(metadata),
         )
         .await?;
 
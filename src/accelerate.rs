@@ -0,0 +1,71 @@
+use crate::Error;
+
+/// The endpoints routing requests through the CloudFront edge locations of
+/// transfer acceleration. Requests are sent to `<bucket>.<endpoint>`, which
+/// is why bucket names containing dots can't be accelerated.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AccelerateEndpoint {
+    /// `s3-accelerate.amazonaws.com`
+    Accelerate,
+
+    /// `s3-accelerate.dualstack.amazonaws.com`, which is also reachable
+    /// over IPv6.
+    DualStack,
+}
+
+impl AccelerateEndpoint {
+    pub fn domain(self) -> &'static str {
+        match self {
+            AccelerateEndpoint::Accelerate => "s3-accelerate.amazonaws.com",
+            AccelerateEndpoint::DualStack => "s3-accelerate.dualstack.amazonaws.com",
+        }
+    }
+
+    /// The endpoint with the given domain, if it is an accelerate endpoint.
+    pub fn from_domain(domain: &str) -> Option<Self> {
+        [
+            AccelerateEndpoint::Accelerate,
+            AccelerateEndpoint::DualStack,
+        ]
+        .iter()
+        .copied()
+        .find(|endpoint| endpoint.domain().eq_ignore_ascii_case(domain))
+    }
+}
+
+/// Fails if the bucket can't be used with transfer acceleration.
+pub(crate) fn check_bucket(bucket: &str) -> Result<(), Error> {
+    if bucket.contains('.') {
+        Err(Error::AccelerateBucketName(bucket.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Error,
+        Request,
+    };
+    use hyper::Method;
+    use url::Url;
+
+    #[test]
+    fn dotted_buckets_are_rejected() {
+        let url = Url::parse("https://s3-accelerate.dualstack.amazonaws.com").unwrap();
+
+        assert!(Request::new(Method::GET)
+            .bucket("photos")
+            .host(url.clone())
+            .is_ok());
+
+        match Request::new(Method::GET)
+            .bucket("photos.example.com")
+            .host(url)
+        {
+            Err(Error::AccelerateBucketName(bucket)) => assert_eq!(bucket, "photos.example.com"),
+            _ => panic!("dotted bucket name was accepted"),
+        }
+    }
+}
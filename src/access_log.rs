@@ -0,0 +1,408 @@
+// Parses the server access logs S3 writes to the target of a bucket's
+// logging configuration. Every line is a record of space separated fields,
+// with fields containing spaces quoted or, for the time, in brackets, and
+// `-` written for fields that don't apply to the request.
+use crate::{
+    types::ListBucketResult,
+    Client,
+    Error,
+    GetObject,
+    ListObjectsV2,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use percent_encoding::percent_decode_str;
+use std::{
+    collections::VecDeque,
+    io::{
+        BufRead,
+        Cursor,
+    },
+    net::IpAddr,
+    str::FromStr,
+    time::Duration,
+};
+
+// Bucket owner to request id, which are present in every record
+const REQUIRED_FIELDS: usize = 6;
+
+/// A single request from a server access log. Fields added by S3 after
+/// the ones known here are kept in `other`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AccessLogRecord {
+    /// The canonical id of the owner of the source bucket.
+    pub bucket_owner: String,
+
+    pub bucket: String,
+    pub time: DateTime<Utc>,
+    pub remote_ip: Option<IpAddr>,
+
+    /// The canonical id or arn of the requester, `None` for anonymous
+    /// requests.
+    pub requester: Option<String>,
+
+    pub request_id: String,
+
+    /// The operation, e.g. `REST.GET.OBJECT` or `S3.EXPIRE.OBJECT`.
+    pub operation: Option<String>,
+
+    /// The decoded key of the object the request was made on.
+    pub key: Option<String>,
+
+    /// The request line, e.g. `GET /bucket/key HTTP/1.1`.
+    pub request_uri: Option<String>,
+
+    pub http_status: Option<u16>,
+    pub error_code: Option<String>,
+    pub bytes_sent: Option<u64>,
+    pub object_size: Option<u64>,
+
+    /// The time from receiving the request until the last byte of the
+    /// response was sent.
+    pub total_time: Option<Duration>,
+
+    /// The time S3 spent processing the request.
+    pub turn_around_time: Option<Duration>,
+
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+    pub version_id: Option<String>,
+    pub host_id: Option<String>,
+    pub signature_version: Option<String>,
+    pub cipher_suite: Option<String>,
+    pub authentication_type: Option<String>,
+    pub host_header: Option<String>,
+    pub tls_version: Option<String>,
+    pub access_point_arn: Option<String>,
+
+    /// Whether the request was only allowed because of an acl.
+    pub acl_required: bool,
+
+    pub other: Vec<String>,
+}
+
+impl FromStr for AccessLogRecord {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Error> {
+        let fields = split(line)?;
+
+        if fields.len() < REQUIRED_FIELDS {
+            return Err(Error::InvalidLogging(format!(
+                "expected at least {} fields, found {}",
+                REQUIRED_FIELDS,
+                fields.len()
+            )));
+        }
+
+        let mut fields = fields.into_iter().map(Field);
+        let mut next = || fields.next().unwrap_or(Field(None));
+
+        Ok(AccessLogRecord {
+            bucket_owner: next().required("bucket owner")?,
+            bucket: next().required("bucket")?,
+            time: next().time()?,
+            remote_ip: next().parse("remote ip")?,
+            requester: next().string(),
+            request_id: next().required("request id")?,
+            operation: next().string(),
+            key: next().key()?,
+            request_uri: next().string(),
+            http_status: next().parse("http status")?,
+            error_code: next().string(),
+            bytes_sent: next().parse("bytes sent")?,
+            object_size: next().parse("object size")?,
+            total_time: next().parse("total time")?.map(Duration::from_millis),
+            turn_around_time: next().parse("turn around time")?.map(Duration::from_millis),
+            referer: next().string(),
+            user_agent: next().string(),
+            version_id: next().string(),
+            host_id: next().string(),
+            signature_version: next().string(),
+            cipher_suite: next().string(),
+            authentication_type: next().string(),
+            host_header: next().string(),
+            tls_version: next().string(),
+            access_point_arn: next().string(),
+            acl_required: next().string().as_deref() == Some("Yes"),
+            other: fields
+                .map(|field| field.0.unwrap_or("-").to_owned())
+                .collect(),
+        })
+    }
+}
+
+/// Split a line into its fields, with `None` for the `-` placeholder.
+fn split(line: &str) -> Result<Vec<Option<&str>>, Error> {
+    let mut fields = Vec::new();
+    let mut rest = line.trim_end_matches(['\r', '\n']);
+
+    loop {
+        rest = rest.trim_start_matches(' ');
+        if rest.is_empty() {
+            return Ok(fields);
+        }
+
+        let (field, remaining) = if let Some(quoted) = rest.strip_prefix('"') {
+            // Quotes within a field aren't escaped, so a field only ends at
+            // a quote followed by a space or the end of the line
+            let end = quoted
+                .match_indices('"')
+                .map(|(index, _)| index)
+                .find(|&index| {
+                    quoted[index + 1..].is_empty() || quoted[index + 1..].starts_with(' ')
+                })
+                .ok_or_else(|| {
+                    Error::InvalidLogging(format!("unterminated quote in {:?}", line))
+                })?;
+            (&quoted[..end], &quoted[end + 1..])
+        } else if let Some(bracketed) = rest.strip_prefix('[') {
+            let end = bracketed.find(']').ok_or_else(|| {
+                Error::InvalidLogging(format!("unterminated bracket in {:?}", line))
+            })?;
+            (&bracketed[..end], &bracketed[end + 1..])
+        } else {
+            let end = rest.find(' ').unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+
+        fields.push(if field == "-" { None } else { Some(field) });
+        rest = remaining;
+    }
+}
+
+struct Field<'l>(Option<&'l str>);
+
+impl<'l> Field<'l> {
+    fn string(self) -> Option<String> {
+        self.0.map(str::to_owned)
+    }
+
+    fn required(self, name: &str) -> Result<String, Error> {
+        self.string()
+            .ok_or_else(|| Error::InvalidLogging(format!("missing {}", name)))
+    }
+
+    fn parse<T: FromStr>(self, name: &str) -> Result<Option<T>, Error> {
+        self.0
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| Error::InvalidLogging(format!("invalid {} {:?}", name, value)))
+            })
+            .transpose()
+    }
+
+    // e.g. `06/Feb/2019:00:00:38 +0000`
+    fn time(self) -> Result<DateTime<Utc>, Error> {
+        let value = self.0.unwrap_or_default();
+
+        DateTime::parse_from_str(value, "%d/%b/%Y:%H:%M:%S %z")
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|_| Error::InvalidLogging(format!("invalid time {:?}", value)))
+    }
+
+    // Keys are written url encoded
+    fn key(self) -> Result<Option<String>, Error> {
+        self.0
+            .map(|value| {
+                percent_decode_str(value)
+                    .decode_utf8()
+                    .map(|key| key.into_owned())
+                    .map_err(|_| Error::InvalidLogging(format!("invalid key {:?}", value)))
+            })
+            .transpose()
+    }
+}
+
+/// An iterator over the records of a single log object. Empty lines are
+/// skipped.
+pub struct AccessLogRecords<R: BufRead> {
+    lines: std::io::Lines<R>,
+    line: usize,
+}
+
+impl<R: BufRead> AccessLogRecords<R> {
+    pub fn new(reader: R) -> Self {
+        AccessLogRecords {
+            lines: reader.lines(),
+            line: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for AccessLogRecords<R> {
+    type Item = Result<AccessLogRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            self.line += 1;
+
+            let line = match line {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => line,
+                Err(error) => {
+                    return Some(Err(Error::InvalidLogging(format!(
+                        "line {}: {}",
+                        self.line, error
+                    ))))
+                }
+            };
+
+            return Some(line.parse().map_err(|error| match error {
+                Error::InvalidLogging(message) => {
+                    Error::InvalidLogging(format!("line {}: {}", self.line, message))
+                }
+                error => error,
+            }));
+        }
+    }
+}
+
+/// Reads the records of every log object under a prefix in key order,
+/// which is also the order the objects were written in. Objects are
+/// listed a page at a time and downloaded one at a time, so only a single
+/// log object is held in memory.
+pub struct AccessLogs<'c> {
+    client: &'c Client,
+    bucket: String,
+    prefix: String,
+    token: Option<String>,
+    listed: bool,
+    keys: VecDeque<String>,
+    key: Option<String>,
+    records: Option<AccessLogRecords<Cursor<Vec<u8>>>>,
+}
+
+impl<'c> AccessLogs<'c> {
+    pub(crate) fn new(client: &'c Client, bucket: &str, prefix: &str) -> Self {
+        AccessLogs {
+            client,
+            bucket: bucket.to_owned(),
+            prefix: prefix.to_owned(),
+            token: None,
+            listed: false,
+            keys: VecDeque::new(),
+            key: None,
+            records: None,
+        }
+    }
+
+    /// The key of the log object the last record was read from.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// The next record, or `None` once every log object has been read.
+    pub async fn next(&mut self) -> Result<Option<AccessLogRecord>, Error> {
+        loop {
+            if let Some(ref mut records) = self.records {
+                match records.next() {
+                    Some(record) => return record.map(Some),
+                    None => self.records = None,
+                }
+            }
+
+            if let Some(key) = self.keys.pop_front() {
+                let object = self.client.send(GetObject::new(&self.bucket, &key)).await?;
+                self.records = Some(AccessLogRecords::new(Cursor::new(object.body)));
+                self.key = Some(key);
+                continue;
+            }
+
+            if self.listed {
+                return Ok(None);
+            }
+
+            let page = self.list().await?;
+            self.keys
+                .extend(page.contents.into_iter().map(|object| object.key));
+
+            match page.next_continuation_token {
+                Some(next) if page.is_truncated => self.token = Some(next),
+                _ => self.listed = true,
+            }
+        }
+    }
+
+    async fn list(&self) -> Result<ListBucketResult, Error> {
+        let mut request = ListObjectsV2::new(&self.bucket).prefix(&self.prefix);
+        if let Some(ref token) = self.token {
+            request = request.continuation_token(token);
+        }
+
+        self.client.send(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parse_record() {
+        let line = concat!(
+            "79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be awsexamplebucket1 ",
+            "[06/Feb/2019:00:00:38 +0000] 192.0.2.3 ",
+            "79a59df900b949e55d96a1e698fbacedfd6e09d98eacf8f8d5218e7cd47ef2be 3E57427F3EXAMPLE ",
+            "REST.GET.VERSIONING photos/a%20b.jpg \"GET /awsexamplebucket1?versioning HTTP/1.1\" ",
+            "200 - 113 - 7 - \"-\" \"S3Console/0.4 \"quoted\"\" - ",
+            "s9lzHYrFp76ZVxRcpX9+5cjAnEH2ROuNkd2BHfIa6UkFVdtjf5mKR3/eTPFvsiP/XV/VLi31234= ",
+            "SigV4 ECDHE-RSA-AES128-GCM-SHA256 AuthHeader awsexamplebucket1.s3.us-west-1.amazonaws.com ",
+            "TLSV1.2 - Yes extra"
+        );
+
+        let record: AccessLogRecord = line.parse().unwrap();
+        assert_eq!(record.bucket, "awsexamplebucket1");
+        assert_eq!(record.time, Utc.ymd(2019, 2, 6).and_hms(0, 0, 38));
+        assert_eq!(record.remote_ip, Some("192.0.2.3".parse().unwrap()));
+        assert_eq!(record.operation.as_deref(), Some("REST.GET.VERSIONING"));
+        assert_eq!(record.key.as_deref(), Some("photos/a b.jpg"));
+        assert_eq!(
+            record.request_uri.as_deref(),
+            Some("GET /awsexamplebucket1?versioning HTTP/1.1")
+        );
+        assert_eq!(record.http_status, Some(200));
+        assert_eq!(record.error_code, None);
+        assert_eq!(record.bytes_sent, Some(113));
+        assert_eq!(record.object_size, None);
+        assert_eq!(record.total_time, Some(Duration::from_millis(7)));
+        assert_eq!(record.referer, None);
+        assert_eq!(
+            record.user_agent.as_deref(),
+            Some("S3Console/0.4 \"quoted\"")
+        );
+        assert_eq!(record.signature_version.as_deref(), Some("SigV4"));
+        assert_eq!(record.tls_version.as_deref(), Some("TLSV1.2"));
+        assert_eq!(record.access_point_arn, None);
+        assert!(record.acl_required);
+        assert_eq!(record.other, vec!["extra".to_owned()]);
+    }
+
+    #[test]
+    fn read_records() {
+        let log = concat!(
+            "owner bucket [06/Feb/2019:00:00:38 +0000] - - REQ1 S3.EXPIRE.OBJECT key - - - - - - - \"-\" \"-\" -\n",
+            "\n",
+            "owner bucket [not a time] - - REQ2\n",
+        );
+
+        let mut records = AccessLogRecords::new(Cursor::new(log));
+
+        let first = records.next().unwrap().unwrap();
+        assert_eq!(first.request_id, "REQ1");
+        assert_eq!(first.requester, None);
+        assert_eq!(first.host_id, None);
+        assert!(!first.acl_required);
+
+        match records.next() {
+            Some(Err(Error::InvalidLogging(message))) => assert!(message.starts_with("line 3:")),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(records.next().is_none());
+    }
+}
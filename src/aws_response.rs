@@ -3,10 +3,18 @@ use crate::{
         self,
         AwsResponseError,
     },
+    object_lock,
     storage_class::StorageClass,
     Error,
     Gmt,
     Headers,
+    LegalHoldStatus,
+    Metadata,
+    ObjectEncryption,
+    ObjectLock,
+    ObjectLockMode,
+    ReplicationStatus,
+    ServerSideEncryption,
 };
 use chrono::{
     DateTime,
@@ -18,6 +26,7 @@ use http_body::Body;
 use hyper::{
     Body as HttpBody,
     Response,
+    StatusCode,
 };
 use std::str::FromStr;
 
@@ -30,6 +39,29 @@ pub trait AwsResponse {
     fn parts_count(&self) -> Result<Option<u64>, Error>;
     fn error(&mut self) -> BoxFuture<Result<Vec<u8>, Error>>;
     fn delete_marker(&mut self) -> Result<Option<bool>, Error>;
+    fn copy_source_version_id(&self) -> Result<Option<String>, Error>;
+    fn content_length(&self) -> Result<Option<u64>, Error>;
+    fn content_type(&self) -> Result<Option<String>, Error>;
+    fn content_disposition(&self) -> Result<Option<String>, Error>;
+    fn content_encoding(&self) -> Result<Option<String>, Error>;
+    fn content_language(&self) -> Result<Option<String>, Error>;
+    fn cache_control(&self) -> Result<Option<String>, Error>;
+    fn bucket_region(&self) -> Result<Option<String>, Error>;
+    fn metadata(&self) -> Metadata;
+    fn encryption(&self) -> Result<ObjectEncryption, Error>;
+    fn object_lock(&self) -> Result<ObjectLock, Error>;
+    fn replication_status(&self) -> Result<Option<ReplicationStatus>, Error>;
+    fn request_charged(&self) -> Result<bool, Error>;
+}
+
+fn optional_header(response: &Response<HttpBody>, header: &str) -> Result<Option<String>, Error> {
+    Ok(response
+        .headers()
+        .get(header)
+        .map(HeaderValue::to_str)
+        .transpose()
+        .map_err(error::Internal::from)?
+        .map(str::to_owned))
 }
 
 impl AwsResponse for Response<HttpBody> {
@@ -141,4 +173,128 @@ impl AwsResponse for Response<HttpBody> {
             .transpose()
             .map_err(error::Internal::from)?)
     }
+
+    fn copy_source_version_id(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::X_AMZ_COPY_SOURCE_VERSION_ID)
+    }
+
+    fn content_length(&self) -> Result<Option<u64>, Error> {
+        Ok(optional_header(self, Headers::CONTENT_LENGTH)?
+            .as_deref()
+            .map(u64::from_str)
+            .transpose()
+            .map_err(error::Internal::from)?)
+    }
+
+    fn content_type(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::CONTENT_TYPE)
+    }
+
+    fn content_disposition(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::CONTENT_DISPOSITION)
+    }
+
+    fn content_encoding(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::CONTENT_ENCODING)
+    }
+
+    fn content_language(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::CONTENT_LANGUAGE)
+    }
+
+    fn cache_control(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::CACHE_CONTROL)
+    }
+
+    fn bucket_region(&self) -> Result<Option<String>, Error> {
+        optional_header(self, Headers::X_AMZ_BUCKET_REGION)
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata::from_headers(self.headers())
+    }
+
+    fn encryption(&self) -> Result<ObjectEncryption, Error> {
+        let server_side_encryption = optional_header(self, Headers::X_AMZ_SERVER_SIDE_ENCRYPTION)?
+            .as_deref()
+            .map(ServerSideEncryption::from_str)
+            .transpose()?;
+
+        let bucket_key_enabled = optional_header(
+            self,
+            Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_BUCKET_KEY_ENABLED,
+        )?
+        .as_deref()
+        .map(bool::from_str)
+        .transpose()
+        .map_err(error::Internal::from)?
+        .unwrap_or(false);
+
+        Ok(ObjectEncryption {
+            server_side_encryption,
+            kms_key_id: optional_header(
+                self,
+                Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+            )?,
+            bucket_key_enabled,
+            sse_customer_key_md5: optional_header(
+                self,
+                Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+            )?,
+        })
+    }
+
+    fn object_lock(&self) -> Result<ObjectLock, Error> {
+        Ok(ObjectLock {
+            mode: optional_header(self, Headers::X_AMZ_OBJECT_LOCK_MODE)?
+                .as_deref()
+                .map(ObjectLockMode::from_str)
+                .transpose()?,
+            retain_until_date: optional_header(self, Headers::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE)?
+                .as_deref()
+                .map(object_lock::from_iso8601)
+                .transpose()?,
+            legal_hold: optional_header(self, Headers::X_AMZ_OBJECT_LOCK_LEGAL_HOLD)?
+                .as_deref()
+                .map(LegalHoldStatus::from_str)
+                .transpose()?,
+        })
+    }
+
+    fn replication_status(&self) -> Result<Option<ReplicationStatus>, Error> {
+        optional_header(self, Headers::X_AMZ_REPLICATION_STATUS)?
+            .as_deref()
+            .map(ReplicationStatus::from_str)
+            .transpose()
+    }
+
+    // Only ever set to `requester`, when the requester was charged for a
+    // request on a requester pays bucket
+    fn request_charged(&self) -> Result<bool, Error> {
+        Ok(optional_header(self, Headers::X_AMZ_REQUEST_CHARGED)?.as_deref() == Some("requester"))
+    }
+}
+
+/// Some requests, such as `CopyObject`, can fail after S3 has already responded
+/// with `200 OK`. In that case the error is returned in the body instead.
+pub(crate) fn error_in_body(status: StatusCode, body: &str) -> Result<(), Error> {
+    let mut body = body.trim_start();
+
+    if body.starts_with("<?xml") {
+        body = body
+            .find("?>")
+            .map(|end| body[end + 2..].trim_start())
+            .unwrap_or(body);
+    }
+
+    if body.starts_with("<Error>") || body.starts_with("<Error ") {
+        let error: AwsResponseError =
+            quick_xml::de::from_str(body).map_err(error::Internal::from)?;
+        Err(error::ResponseError {
+            status,
+            error: Some(error),
+        })?
+    } else {
+        Ok(())
+    }
 }
@@ -0,0 +1,238 @@
+use crate::{
+    error,
+    AwsResponse,
+    Error,
+    Headers,
+    Region,
+};
+use http::{
+    header::HeaderValue,
+    uri::{
+        Authority,
+        Uri,
+    },
+    HeaderMap,
+    Method,
+};
+use hyper::{
+    body::{
+        self,
+        Bytes,
+    },
+    Body as HttpBody,
+    Request as HttpRequest,
+    Response,
+    StatusCode,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+};
+
+/// The regions of the buckets requests were redirected for, so that later
+/// requests are signed for the right region straight away.
+#[derive(Debug, Default)]
+pub(crate) struct BucketRegions {
+    regions: RwLock<HashMap<String, Region>>,
+}
+
+impl BucketRegions {
+    pub(crate) fn get(&self, bucket: &str) -> Option<Region> {
+        self.regions
+            .read()
+            .ok()
+            .and_then(|regions| regions.get(bucket).cloned())
+    }
+
+    pub(crate) fn insert(&self, bucket: &str, region: Region) {
+        if let Ok(mut regions) = self.regions.write() {
+            regions.insert(bucket.to_owned(), region);
+        }
+    }
+}
+
+/// A built request with its body read into memory, so it can be sent again
+/// once the region of its bucket is known.
+pub(crate) struct BufferedRequest {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl BufferedRequest {
+    pub(crate) async fn new(request: HttpRequest<HttpBody>) -> Result<Self, Error> {
+        let (parts, body) = request.into_parts();
+
+        Ok(BufferedRequest {
+            method: parts.method,
+            uri: parts.uri,
+            headers: parts.headers,
+            body: body::to_bytes(body).await.map_err(error::Internal::from)?,
+        })
+    }
+
+    /// The bucket the request was built for, as the bucket is the first
+    /// label of the host on top of the domain of `url_domain`.
+    pub(crate) fn bucket(&self, url_domain: &str) -> Option<String> {
+        let host = self.headers.get(Headers::HOST)?.to_str().ok()?;
+        let bucket = host.strip_suffix(url_domain)?.strip_suffix('.')?;
+
+        if bucket.is_empty() {
+            None
+        } else {
+            Some(bucket.to_owned())
+        }
+    }
+
+    /// Moves a request sent to a regional endpoint, such as
+    /// `s3.us-east-1.amazonaws.com`, to the endpoint of another region.
+    /// Requests to any other host, including the global endpoint which
+    /// routes requests to the bucket's region by itself, are left as is.
+    pub(crate) fn move_region(&mut self, from: &Region, to: &Region) -> Result<(), Error> {
+        let authority = match self.uri.authority() {
+            Some(authority) => replace_label(authority.as_str(), from.as_str(), to.as_str()),
+            None => None,
+        };
+
+        if let Some(authority) = authority {
+            let mut parts = self.uri.clone().into_parts();
+            parts.authority = Some(
+                authority
+                    .parse::<Authority>()
+                    .map_err(error::Internal::from)?,
+            );
+            self.uri = Uri::from_parts(parts).map_err(error::Internal::from)?;
+        }
+
+        let host = self
+            .headers
+            .get(Headers::HOST)
+            .and_then(|host| host.to_str().ok())
+            .and_then(|host| replace_label(host, from.as_str(), to.as_str()));
+
+        if let Some(host) = host {
+            self.headers.insert(
+                Headers::HOST,
+                HeaderValue::from_str(&host).map_err(error::Internal::from)?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// A copy of the request to send. It still has to be signed again if
+    /// anything changed since it was built.
+    pub(crate) fn request(&self) -> HttpRequest<HttpBody> {
+        let mut request = HttpRequest::new(HttpBody::from(self.body.clone()));
+        *request.method_mut() = self.method.clone();
+        *request.uri_mut() = self.uri.clone();
+        *request.headers_mut() = self.headers.clone();
+        request
+    }
+}
+
+// Replaces the dot separated label `from` of `host`, if there is one. The
+// first label is the bucket, or `s3` without one, and is never replaced.
+fn replace_label(host: &str, from: &str, to: &str) -> Option<String> {
+    if host.split('.').skip(1).any(|label| label == from) {
+        Some(
+            host.split('.')
+                .enumerate()
+                .map(|(i, label)| if i > 0 && label == from { to } else { label })
+                .collect::<Vec<_>>()
+                .join("."),
+        )
+    } else {
+        None
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RegionError {
+    region: Option<String>,
+}
+
+/// The region S3 says the bucket of a rejected request is in. Requests sent
+/// to the endpoint of the wrong region are answered with
+/// `301 PermanentRedirect`, and requests signed for the wrong region with
+/// `400 AuthorizationHeaderMalformed`. The region is taken from the
+/// `x-amz-bucket-region` header, or from the error in the body otherwise.
+///
+/// The body is read to do so, which is why the response is returned with
+/// the body put back.
+pub(crate) async fn redirected_region(
+    response: Response<HttpBody>,
+) -> Result<(Option<Region>, Response<HttpBody>), Error> {
+    let status = response.status();
+    if status != StatusCode::MOVED_PERMANENTLY && status != StatusCode::BAD_REQUEST {
+        return Ok((None, response));
+    }
+
+    if let Some(region) = response.bucket_region()? {
+        return Ok((Some(Region::from(region)), response));
+    }
+
+    let (parts, body) = response.into_parts();
+    let bytes = body::to_bytes(body).await.map_err(error::Internal::from)?;
+
+    let region = quick_xml::de::from_str::<RegionError>(&String::from_utf8_lossy(&bytes))
+        .ok()
+        .and_then(|error| error.region)
+        .map(Region::from);
+
+    Ok((region, Response::from_parts(parts, HttpBody::from(bytes))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regional_hosts_are_moved() {
+        let mut request = BufferedRequest {
+            method: Method::GET,
+            uri: Uri::from_static("https://bucket.s3.us-east-1.amazonaws.com/key"),
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        };
+        request.headers.insert(
+            Headers::HOST,
+            HeaderValue::from_static("bucket.s3.us-east-1.amazonaws.com"),
+        );
+
+        assert_eq!(
+            request.bucket("s3.us-east-1.amazonaws.com").as_deref(),
+            Some("bucket")
+        );
+
+        request
+            .move_region(&Region::UsEast1, &Region::EuNorth1)
+            .unwrap();
+        assert_eq!(
+            request.uri,
+            "https://bucket.s3.eu-north-1.amazonaws.com/key"
+        );
+        assert_eq!(
+            request.headers[Headers::HOST],
+            "bucket.s3.eu-north-1.amazonaws.com"
+        );
+
+        let request = request.request();
+        assert_eq!(
+            request.uri().host(),
+            Some("bucket.s3.eu-north-1.amazonaws.com")
+        );
+
+        assert_eq!(
+            replace_label("bucket.localhost", "us-east-1", "eu-north-1"),
+            None
+        );
+        assert_eq!(
+            replace_label("us-east-1.s3.amazonaws.com", "us-east-1", "eu-north-1"),
+            None
+        );
+    }
+}
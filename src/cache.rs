@@ -1,5 +1,6 @@
 /// The CacheControl header used to determine how an object is to be cached.
 /// For more information go to [rfc2616](https://www.w3.org/Protocols/rfc2616/rfc2616-sec14.html#sec14.9)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum CacheControl<'a> {
     NoCache,
     NoStore,
@@ -16,12 +17,12 @@ pub enum CacheControl<'a> {
     Extension(&'a str),
 }
 
-impl<'a> Into<String> for CacheControl<'a> {
-    fn into(self) -> String {
-        let cache = match self {
+impl<'a> From<CacheControl<'a>> for String {
+    fn from(cache: CacheControl<'a>) -> String {
+        match cache {
             CacheControl::NoCache => "no-cache".to_owned(),
             CacheControl::NoStore => "no-store".to_owned(),
-            CacheControl::MaxAge(value) => return format!("max-age={}", value),
+            CacheControl::MaxAge(value) => format!("max-age={}", value),
             CacheControl::MaxStale(value) => {
                 if let Some(value) = value {
                     format!("max-stale={}", value)
@@ -29,10 +30,10 @@ impl<'a> Into<String> for CacheControl<'a> {
                     "max-stale".to_owned()
                 }
             }
-            CacheControl::MinFresh(value) => return format!("min-fresh={}", value),
-            CacheControl::NoTransform => return "no-transform".to_owned(),
-            CacheControl::OnlyIfCached => return "only-if-cached".to_owned(),
-            CacheControl::Public => return "public".to_owned(),
+            CacheControl::MinFresh(value) => format!("min-fresh={}", value),
+            CacheControl::NoTransform => "no-transform".to_owned(),
+            CacheControl::OnlyIfCached => "only-if-cached".to_owned(),
+            CacheControl::Public => "public".to_owned(),
             CacheControl::Private(value) => {
                 if let Some(value) = value {
                     format!("private={}", value)
@@ -40,12 +41,39 @@ impl<'a> Into<String> for CacheControl<'a> {
                     "private".to_owned()
                 }
             }
-            CacheControl::MustRevalidate => return "must-revalidate".to_owned(),
-            CacheControl::ProxyRevalidate => return "proxy-revalidate".to_owned(),
-            CacheControl::SMaxAge(value) => return format!("s-maxage={}", value),
-            CacheControl::Extension(value) => return value.to_owned(),
-        };
+            CacheControl::MustRevalidate => "must-revalidate".to_owned(),
+            CacheControl::ProxyRevalidate => "proxy-revalidate".to_owned(),
+            CacheControl::SMaxAge(value) => format!("s-maxage={}", value),
+            CacheControl::Extension(value) => value.to_owned(),
+        }
+    }
+}
+
+/// Join a list of directives into a single `Cache-Control` header value
+pub(crate) fn header_value(cache: &[CacheControl]) -> Option<String> {
+    if cache.is_empty() {
+        None
+    } else {
+        Some(
+            cache
+                .iter()
+                .map(|&directive| String::from(directive))
+                .collect::<Vec<String>>()
+                .join(", "),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        format!("CacheControl:{}", cache)
+    #[test]
+    fn directives_are_joined() {
+        assert_eq!(header_value(&[]), None);
+        assert_eq!(
+            header_value(&[CacheControl::Public, CacheControl::MaxAge(3600)]),
+            Some("public, max-age=3600".to_owned())
+        );
     }
 }
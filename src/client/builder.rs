@@ -1,5 +1,6 @@
 use super::Client;
 use crate::{
+    AccelerateEndpoint,
     Error,
     Region,
 };
@@ -10,6 +11,8 @@ pub struct Builder<T: AsRef<str>> {
     host: Option<T>,
     access_key: Option<T>,
     secret_key: Option<T>,
+    accelerate: Option<AccelerateEndpoint>,
+    requester_pays: bool,
 }
 
 impl<T: AsRef<str>> Default for Builder<T> {
@@ -19,6 +22,8 @@ impl<T: AsRef<str>> Default for Builder<T> {
             host: None,
             access_key: None,
             secret_key: None,
+            accelerate: None,
+            requester_pays: false,
         }
     }
 }
@@ -56,12 +61,29 @@ impl<T: AsRef<str>> Builder<T> {
         self
     }
 
+    /// Route every request through a transfer acceleration endpoint, see
+    /// `Client::set_accelerate()`.
+    pub fn accelerate(mut self, endpoint: AccelerateEndpoint) -> Self {
+        self.accelerate = Some(endpoint);
+        self
+    }
+
+    /// Acknowledge on every request that the requester is charged for it,
+    /// see `Client::set_requester_pays()`.
+    pub fn requester_pays(mut self) -> Self {
+        self.requester_pays = true;
+        self
+    }
+
     /// Build a client with the give settings
     pub fn build(self) -> Result<Client, Error> {
         if let (Some(access_key), Some(secret_key), Some(host)) =
             (self.access_key, self.secret_key, self.host)
         {
-            Client::new(access_key, secret_key, self.region, host)
+            let mut client = Client::new(access_key, secret_key, self.region, host)?;
+            client.set_accelerate(self.accelerate);
+            client.set_requester_pays(self.requester_pays);
+            Ok(client)
         } else {
             Err(Error::ClientBuildError)
         }
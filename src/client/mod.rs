@@ -1,14 +1,40 @@
 use crate::{
+    bucket_region::{
+        self,
+        BucketRegions,
+        BufferedRequest,
+    },
     error,
     request::get::object::GetObjectResponse,
-    types::Bucket,
+    request_payer,
+    sign_request,
+    signing_key::DateKey,
+    types::{
+        AnalyticsConfiguration,
+        Bucket,
+        InventoryConfiguration,
+        MetricsConfiguration,
+        TagSetChanges,
+        TagSetMerge,
+    },
+    AccelerateEndpoint,
+    AccessLogs,
     AwsRequest,
     // CreateBucket,
-    // DeleteObject,
+    DeleteBucketTagging,
+    DeleteObject,
     Error,
+    GetBucketTagging,
     GetObject,
+    InventoryFile,
+    InventoryManifest,
+    InventoryRecords,
+    ListBucketAnalyticsConfigurations,
+    ListBucketInventoryConfigurations,
+    ListBucketMetricsConfigurations,
     // ListBuckets,
-    // PutObject,
+    PutBucketTagging,
+    PutObject,
     Region,
     SigningKey,
 };
@@ -17,8 +43,15 @@ use chrono::{
     DateTime,
     Utc,
 };
-use hyper::Body as HttpBody;
-use std::str::FromStr;
+use hyper::{
+    client::HttpConnector,
+    Body as HttpBody,
+};
+use hyper_tls::HttpsConnector;
+use std::{
+    io::Cursor,
+    str::FromStr,
+};
 use url::Url;
 
 mod builder;
@@ -33,12 +66,16 @@ static AWS_SHARED_CREDENTIALS_FILE: &str = "~/.aws/credentials";
 
 #[derive(Debug)]
 pub struct Client {
-    client: hyper::Client<hyper::client::HttpConnector, HttpBody>,
+    client: hyper::Client<HttpsConnector<HttpConnector>, HttpBody>,
     access_key: String,
     signing_key: SigningKey,
+    date_key: DateKey,
     region: Region,
+    bucket_regions: BucketRegions,
     date: DateTime<Utc>,
     host: Url,
+    accelerate: Option<AccelerateEndpoint>,
+    requester_pays: bool,
 }
 
 impl Client {
@@ -51,13 +88,18 @@ impl Client {
         host: T2,
     ) -> Result<Self, Error> {
         let date = Utc::now();
+        let date_key = DateKey::new(secret_key.as_ref(), &date);
         Ok(Self {
-            client: hyper::Client::builder().build_http(),
-            signing_key: SigningKey::from_date(&secret_key.as_ref(), &date.clone(), region),
+            client: hyper::Client::builder().build(HttpsConnector::new()),
+            signing_key: date_key.signing_key(&region),
+            date_key,
             region,
+            bucket_regions: BucketRegions::default(),
             date,
             host: Url::from_str(host.as_ref())?,
             access_key: access_key.as_ref().to_owned(),
+            accelerate: None,
+            requester_pays: false,
         })
     }
 
@@ -146,28 +188,28 @@ impl Client {
         self.send(request).await
     }
 
-    ///// A convience method for a `PutObject` request.
-    /////
-    ///// Note: If more control is needed over the request parameters use the
-    ///// `Client::send()` method directly
-    //pub async fn put<'a>(
-    //    &self,
-    //    bucket: &'a str,
-    //    key: &'a str,
-    //    contents: Vec<u8>,
-    //) -> Result<String, Error> {
-    //    let request = PutObject::new(bucket, key, contents);
-    //    self.send(request).await
-    //}
+    /// A convience method for a `PutObject` request.
+    ///
+    /// Note: If more control is needed over the request parameters use the
+    /// `Client::send()` method directly
+    pub async fn put<'a>(
+        &self,
+        bucket: &'a str,
+        key: &'a str,
+        contents: Vec<u8>,
+    ) -> Result<String, Error> {
+        let request = PutObject::new(bucket, key, contents);
+        self.send(request).await
+    }
 
-    ///// A convience method for a `DeleteObject` request.
-    /////
-    ///// Note: If more control is needed over the request parameters use the
-    ///// `Client::send()` method directly
-    //pub async fn delete<'a>(&self, bucket: &'a str, key: &'a str) -> Result<bool, Error> {
-    //    let request = DeleteObject::new(bucket, key);
-    //    self.send(request).await
-    //}
+    /// A convience method for a `DeleteObject` request.
+    ///
+    /// Note: If more control is needed over the request parameters use the
+    /// `Client::send()` method directly
+    pub async fn delete<'a>(&self, bucket: &'a str, key: &'a str) -> Result<bool, Error> {
+        let request = DeleteObject::new(bucket, key);
+        self.send(request).await
+    }
 
     ///// A convience method for a `CreateBucket` request.
     /////
@@ -186,25 +228,267 @@ impl Client {
     //    self.send(ListBuckets).await
     //}
 
+    /// Adds and removes individual tags of a bucket, keeping every other tag.
+    ///
+    /// The current tag-set is read, merged with `changes` and written back,
+    /// unless the changes conflict with the current tag-set. Conflicts are
+    /// returned in the merge, in which case nothing is written.
+    pub async fn merge_bucket_tagging(
+        &self,
+        bucket: &str,
+        changes: &TagSetChanges,
+    ) -> Result<TagSetMerge, Error> {
+        let current = self.send(GetBucketTagging::new(bucket)).await?;
+        let merge = changes.merge(&current);
+
+        if merge.changed && merge.conflicts.is_empty() {
+            // S3 doesn't accept an empty tag-set, it has to be deleted instead
+            if merge.tag_set.is_empty() {
+                self.send(DeleteBucketTagging::new(bucket)).await?;
+            } else {
+                self.send(PutBucketTagging::new(bucket, merge.tag_set.clone()))
+                    .await?;
+            }
+        }
+
+        Ok(merge)
+    }
+
+    /// Lists every analytics configuration of a bucket, requesting as many
+    /// pages as needed.
+    pub async fn list_bucket_analytics_configurations(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<AnalyticsConfiguration>, Error> {
+        let mut configurations = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut request = ListBucketAnalyticsConfigurations::new(bucket);
+            if let Some(ref token) = token {
+                request = request.continuation_token(token);
+            }
+
+            let page = self.send(request).await?;
+            configurations.extend(page.analytics_configurations);
+
+            match page.next_continuation_token {
+                Some(next) if page.is_truncated => token = Some(next),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+
+    /// Lists every inventory configuration of a bucket, requesting as many
+    /// pages as needed.
+    pub async fn list_bucket_inventory_configurations(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<InventoryConfiguration>, Error> {
+        let mut configurations = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut request = ListBucketInventoryConfigurations::new(bucket);
+            if let Some(ref token) = token {
+                request = request.continuation_token(token);
+            }
+
+            let page = self.send(request).await?;
+            configurations.extend(page.inventory_configurations);
+
+            match page.next_continuation_token {
+                Some(next) if page.is_truncated => token = Some(next),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+
+    /// Lists every metrics configuration of a bucket, requesting as many
+    /// pages as needed.
+    pub async fn list_bucket_metrics_configurations(
+        &self,
+        bucket: &str,
+    ) -> Result<Vec<MetricsConfiguration>, Error> {
+        let mut configurations = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut request = ListBucketMetricsConfigurations::new(bucket);
+            if let Some(ref token) = token {
+                request = request.continuation_token(token);
+            }
+
+            let page = self.send(request).await?;
+            configurations.extend(page.metrics_configurations);
+
+            match page.next_continuation_token {
+                Some(next) if page.is_truncated => token = Some(next),
+                _ => return Ok(configurations),
+            }
+        }
+    }
+
+    /// Reads the server access logs written under `prefix`, e.g. the
+    /// `target_prefix` of a bucket's logging configuration.
+    pub fn access_logs<'c>(&'c self, bucket: &str, prefix: &str) -> AccessLogs<'c> {
+        AccessLogs::new(self, bucket, prefix)
+    }
+
+    /// Loads the manifest of an inventory report, e.g.
+    /// `reports/source/audit/2020-01-01T00-00Z/manifest.json`, and verifies
+    /// it against the `manifest.checksum` next to it.
+    pub async fn inventory_manifest(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<InventoryManifest, Error> {
+        let checksum_key = format!("{}.checksum", key.trim_end_matches(".json"));
+
+        let manifest = self.send(GetObject::new(bucket, key)).await?;
+        let checksum = self.send(GetObject::new(bucket, &checksum_key)).await?;
+
+        InventoryManifest::from_slice(&manifest.body, Some(&checksum.as_str()))
+    }
+
+    /// Downloads a data file of an inventory report, verifies its checksum
+    /// and returns an iterator over its rows. Files are read one at a time,
+    /// so only a single file is held in memory.
+    pub async fn inventory_records(
+        &self,
+        manifest: &InventoryManifest,
+        file: &InventoryFile,
+    ) -> Result<InventoryRecords<Cursor<Vec<u8>>>, Error> {
+        let response = self
+            .send(GetObject::new(
+                manifest.destination_bucket_name(),
+                &file.key,
+            ))
+            .await?;
+
+        file.verify(&response.body)?;
+        manifest.records(Cursor::new(response.body))
+    }
+
+    /// Route every request through the given transfer acceleration
+    /// endpoint, or through the configured host again if `None`. Requests
+    /// on buckets with dots in their name fail, and creating, deleting or
+    /// listing buckets isn't supported by the accelerate endpoints.
+    pub fn set_accelerate(&mut self, endpoint: Option<AccelerateEndpoint>) {
+        self.accelerate = endpoint;
+    }
+
+    /// Acknowledge on every request that the requester is charged for it,
+    /// which is needed to access requester pays buckets of other accounts.
+    /// To do so for a single request wrap it in `RequesterPays` instead.
+    pub fn set_requester_pays(&mut self, requester_pays: bool) {
+        self.requester_pays = requester_pays;
+    }
+
+    /// The region requests on `bucket` are signed for, if it differs from
+    /// the region of the client. It is discovered when S3 rejects a request
+    /// for being sent to the wrong region.
+    pub fn bucket_region(&self, bucket: &str) -> Option<Region> {
+        self.bucket_regions.get(bucket)
+    }
+
+    /// Sign requests on `bucket` for `region` rather than for the region of
+    /// the client, which saves the retry needed to discover it.
+    pub fn set_bucket_region(&self, bucket: &str, region: Region) {
+        self.bucket_regions.insert(bucket, region);
+    }
+
     /// Sends any S3 request and returns the requests response type.
     pub async fn send<T: AwsRequest>(&self, request: T) -> Result<T::Response, Error> {
-        let request = request.into_request(
-            self.host.clone(),
+        self.send_to(self.accelerate, request).await
+    }
+
+    /// Same as `Client::send()`, but the request is routed through the given
+    /// transfer acceleration endpoint whatever the client is configured with.
+    pub async fn send_accelerated<T: AwsRequest>(
+        &self,
+        request: T,
+        endpoint: AccelerateEndpoint,
+    ) -> Result<T::Response, Error> {
+        self.send_to(Some(endpoint), request).await
+    }
+
+    // A request rejected for being sent to the wrong region is signed for
+    // the region S3 answers with and sent once more.
+    async fn send_to<T: AwsRequest>(
+        &self,
+        accelerate: Option<AccelerateEndpoint>,
+        request: T,
+    ) -> Result<T::Response, Error> {
+        let url = match accelerate {
+            Some(endpoint) => {
+                Url::parse(&format!("{}://{}", self.host.scheme(), endpoint.domain()))?
+            }
+            None => self.host.clone(),
+        };
+
+        let mut request = request.into_request(
+            url.clone(),
             &self.access_key,
             &self.signing_key,
             self.region.clone(),
         )?;
 
-        println!("{:#?}", request);
+        if self.requester_pays {
+            request_payer::set_requester_pays(&mut request);
+        }
 
-        let response = self
-            .client
-            .request(request)
-            .await
-            .map_err(error::Internal::from)?;
+        let mut request = BufferedRequest::new(request).await?;
+        let bucket = url.domain().and_then(|domain| request.bucket(domain));
 
-        println!("{:#?}", response);
+        let mut region = self.region.clone();
+        if let Some(cached) = bucket
+            .as_ref()
+            .and_then(|bucket| self.bucket_region(bucket))
+        {
+            request.move_region(&region, &cached)?;
+            region = cached;
+        }
+
+        let mut redirected = false;
+
+        loop {
+            let mut http_request = request.request();
+
+            if region != self.region {
+                let signing_key = self.date_key.signing_key(&region);
+                sign_request::sign(
+                    &mut http_request,
+                    &self.access_key,
+                    &signing_key,
+                    region.as_str(),
+                )?;
+            } else if self.requester_pays {
+                sign_request::sign(
+                    &mut http_request,
+                    &self.access_key,
+                    &self.signing_key,
+                    region.as_str(),
+                )?;
+            }
 
-        T::into_response(response).await
+            let response = self
+                .client
+                .request(http_request)
+                .await
+                .map_err(error::Internal::from)?;
+
+            let (discovered, response) = bucket_region::redirected_region(response).await?;
+
+            match (discovered, &bucket) {
+                (Some(discovered), Some(bucket)) if !redirected && discovered != region => {
+                    self.bucket_regions.insert(bucket, discovered.clone());
+                    request.move_region(&region, &discovered)?;
+                    region = discovered;
+                    redirected = true;
+                }
+                _ => return T::into_response(response).await,
+            }
+        }
     }
 }
@@ -0,0 +1,183 @@
+use crate::{
+    error,
+    Error,
+    Headers,
+    Request,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    str::FromStr,
+};
+
+/// The server-side encryption algorithm used when storing an object.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ServerSideEncryption {
+    /// Encrypt with keys managed by S3 (SSE-S3).
+    Aes256,
+
+    /// Encrypt with a key managed by AWS KMS (SSE-KMS).
+    AwsKms,
+
+    /// Dual-layer encryption with a key managed by AWS KMS (DSSE-KMS).
+    AwsKmsDsse,
+}
+
+impl From<ServerSideEncryption> for &'static str {
+    fn from(encryption: ServerSideEncryption) -> &'static str {
+        match encryption {
+            ServerSideEncryption::Aes256 => "AES256",
+            ServerSideEncryption::AwsKms => "aws:kms",
+            ServerSideEncryption::AwsKmsDsse => "aws:kms:dsse",
+        }
+    }
+}
+
+impl FromStr for ServerSideEncryption {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AES256" => Ok(ServerSideEncryption::Aes256),
+            "aws:kms" => Ok(ServerSideEncryption::AwsKms),
+            "aws:kms:dsse" => Ok(ServerSideEncryption::AwsKmsDsse),
+            _ => Err(Error::from(error::Internal::ParseServerSideEncryptionError)),
+        }
+    }
+}
+
+/// The server-side encryption options of a request that stores an object.
+/// Shared by `PutObject`, `CopyObject` and `CreateMultipartUpload`.
+#[derive(Default)]
+pub(crate) struct EncryptionHeaders<'a> {
+    pub(crate) server_side_encryption: Option<ServerSideEncryption>,
+    pub(crate) kms_key_id: Option<&'a str>,
+    pub(crate) context: BTreeMap<&'a str, &'a str>,
+    pub(crate) bucket_key_enabled: Option<bool>,
+}
+
+impl<'a> EncryptionHeaders<'a> {
+    pub(crate) fn headers<'r>(&self, request: Request<'r>) -> Result<Request<'r>, Error> {
+        // The encryption context is sent as base64 encoded json
+        let context = if self.context.is_empty() {
+            None
+        } else {
+            let json = serde_json::to_string(&self.context).map_err(error::Internal::from)?;
+            Some(base64::encode(&json))
+        };
+
+        Ok(request
+            .header(
+                Headers::X_AMZ_SERVER_SIDE_ENCRYPTION,
+                self.server_side_encryption.map(<&'static str>::from),
+            )
+            .header(
+                Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID,
+                self.kms_key_id.map(str::to_owned),
+            )
+            .header(Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CONTEXT, context)
+            .header(
+                Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_BUCKET_KEY_ENABLED,
+                self.bucket_key_enabled.map(|enabled| enabled.to_string()),
+            ))
+    }
+}
+
+/// The encryption S3 applied to an object, as returned in the headers of
+/// object responses.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ObjectEncryption {
+    /// The server-side encryption algorithm, `None` if the object is not
+    /// encrypted or is encrypted with a customer-provided key.
+    pub server_side_encryption: Option<ServerSideEncryption>,
+
+    /// The AWS KMS key used to encrypt the object.
+    pub kms_key_id: Option<String>,
+
+    /// Whether an S3 Bucket Key was used with SSE-KMS.
+    pub bucket_key_enabled: bool,
+
+    /// The base64 encoded MD5 digest of the customer-provided key (SSE-C).
+    pub sse_customer_key_md5: Option<String>,
+}
+
+/// A customer-provided 256-bit key used to encrypt an object (SSE-C).
+/// S3 does not store the key, so the same key must be provided to read the
+/// object again. Keys are only ever sent over https.
+#[derive(Clone, Eq, PartialEq)]
+pub struct SseCustomerKey {
+    key: String,
+    key_md5: String,
+}
+
+impl SseCustomerKey {
+    pub(crate) const ALGORITHM: &'static str = "AES256";
+
+    /// Create a new customer key from 256 bits of key material
+    pub fn new(key: [u8; 32]) -> Self {
+        SseCustomerKey {
+            key: base64::encode(&key),
+            key_md5: base64::encode(&*md5::compute(key)),
+        }
+    }
+
+    /// The base64 encoded MD5 digest of the key, which S3 uses to verify
+    /// the key was received without error.
+    pub fn key_md5(&self) -> &str {
+        &self.key_md5
+    }
+
+    pub(crate) fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl From<[u8; 32]> for SseCustomerKey {
+    fn from(key: [u8; 32]) -> Self {
+        SseCustomerKey::new(key)
+    }
+}
+
+impl fmt::Debug for SseCustomerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SseCustomerKey")
+            .field("key", &"<redacted>")
+            .field("key_md5", &self.key_md5)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn customer_key_is_redacted() {
+        let mut bytes = [0u8; 32];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = index as u8;
+        }
+        let key = SseCustomerKey::new(bytes);
+
+        assert_eq!(key.key(), "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=");
+        assert_eq!(key.key_md5(), "tP/LI3N87DFaSk0aoqYgzg==");
+        assert!(!format!("{:?}", key).contains(key.key()));
+    }
+
+    #[test]
+    fn encryption_context_is_encoded() {
+        let mut encryption = EncryptionHeaders::default();
+        encryption.context.insert("project", "s3");
+
+        let request = encryption
+            .headers(Request::new(hyper::Method::PUT))
+            .unwrap();
+        let context = request
+            .headers
+            .iter()
+            .find(|(name, _)| name == Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CONTEXT)
+            .map(|(_, value)| base64::decode(value.as_ref()).unwrap());
+
+        assert_eq!(context, Some(br#"{"project":"s3"}"#.to_vec()));
+    }
+}
@@ -60,6 +60,15 @@ pub enum Internal {
     #[error("Storage class header provided, but failed to parse it")]
     ParseStorageClassError,
 
+    #[error("Server-side encryption header provided, but failed to parse it")]
+    ParseServerSideEncryptionError,
+
+    #[error("Object lock header provided, but failed to parse it")]
+    ParseObjectLockError,
+
+    #[error("Failed to serialize or deserialize json")]
+    JsonError(#[from] serde_json::Error),
+
     #[error("Method was not set on request during signing, but is required")]
     MethodNotSet,
 
@@ -109,4 +118,52 @@ pub enum Error {
 
     #[error("Failed to build client because not all fields were provided")]
     ClientBuildError,
+
+    #[error("Invalid user-defined metadata: {0}")]
+    InvalidMetadata(String),
+
+    #[error("Invalid tagging: {0}")]
+    InvalidTagging(String),
+
+    #[error("Invalid cors configuration: {0}")]
+    InvalidCors(String),
+
+    #[error("Invalid lifecycle configuration: {0}")]
+    InvalidLifecycle(String),
+
+    #[error("Invalid or unsupported policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("Invalid analytics configuration: {0}")]
+    InvalidAnalytics(String),
+
+    #[error("Invalid logging configuration or access log: {0}")]
+    InvalidLogging(String),
+
+    #[error("Invalid metrics configuration: {0}")]
+    InvalidMetrics(String),
+
+    #[error("Invalid inventory configuration or report: {0}")]
+    InvalidInventory(String),
+
+    #[error("Invalid notification configuration: {0}")]
+    InvalidNotification(String),
+
+    #[error("Invalid replication configuration: {0}")]
+    InvalidReplication(String),
+
+    #[error("Invalid website configuration: {0}")]
+    InvalidWebsite(String),
+
+    #[error("Invalid delete request: {0}")]
+    InvalidDelete(String),
+
+    #[error("Transfer acceleration doesn't support bucket names containing dots: {0:?}")]
+    AccelerateBucketName(String),
+
+    #[error("A customer-provided encryption key can only be sent over https")]
+    SseCustomerKeyOverHttp,
+
+    #[error("The object was not modified according to the request's conditions")]
+    NotModified,
 }
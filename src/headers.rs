@@ -2,9 +2,20 @@ pub struct Headers;
 
 #[allow(dead_code)]
 impl Headers {
+    pub const ACCESS_CONTROL_ALLOW_CREDENTIALS: &'static str = "access-control-allow-credentials";
+    pub const ACCESS_CONTROL_ALLOW_HEADERS: &'static str = "access-control-allow-headers";
+    pub const ACCESS_CONTROL_ALLOW_METHODS: &'static str = "access-control-allow-methods";
+    pub const ACCESS_CONTROL_ALLOW_ORIGIN: &'static str = "access-control-allow-origin";
+    pub const ACCESS_CONTROL_EXPOSE_HEADERS: &'static str = "access-control-expose-headers";
+    pub const ACCESS_CONTROL_MAX_AGE: &'static str = "access-control-max-age";
     pub const AUTHORIZATION: &'static str = "authorization";
     pub const CACHE_CONTROL: &'static str = "cache-control";
+    pub const CONTENT_DISPOSITION: &'static str = "content-disposition";
+    pub const CONTENT_ENCODING: &'static str = "content-encoding";
+    pub const CONTENT_LANGUAGE: &'static str = "content-language";
+    pub const CONTENT_LENGTH: &'static str = "content-length";
     pub const CONTENT_MD5: &'static str = "content-md5";
+    pub const CONTENT_TYPE: &'static str = "content-type";
     pub const DELETE_MARKER: &'static str = "x-amz-delete-marker";
     pub const ETAG: &'static str = "etag";
     pub const EXPIRES: &'static str = "expires";
@@ -17,21 +28,57 @@ impl Headers {
     pub const PARTS_COUNT: &'static str = "x-amz-mp-parts-count";
     pub const PART_NUMBER: &'static str = "part-number";
     pub const RANGE: &'static str = "range";
-    pub const REQUEST_PAYER: &'static str = "request-payer";
-    pub const RESPONSE_CACHE_CONTROL: &'static str = "response-cache-control";
-    pub const RESPONSE_CONTENT_DISPOSITION: &'static str = "response-content-disposition";
-    pub const RESPONSE_CONTENT_ENCODING: &'static str = "response-content-encoding";
-    pub const RESPONSE_CONTENT_LANGUAGE: &'static str = "responsee-content-language";
-    pub const RESPONSE_CONTENT_TYPE: &'static str = "response-content-type";
-    pub const RESPONSE_EXPIRES: &'static str = "response-expires";
-    pub const SSE_CUSTOMER_ALGORITHM: &'static str = "sse-customer-algorithm";
-    pub const SSE_CUSTOMER_KEY: &'static str = "sse-customer-key";
-    pub const SSE_CUSTOMER_KEY_MD5: &'static str = "sse-customer-key-md5";
+    pub const VARY: &'static str = "vary";
     pub const VERSION_ID: &'static str = "vesrion-id";
     pub const X_AMZ_ACL: &'static str = "x-amz-acl";
+    pub const X_AMZ_BUCKET_OBJECT_LOCK_TOKEN: &'static str = "x-amz-bucket-object-lock-token";
+    pub const X_AMZ_BUCKET_REGION: &'static str = "x-amz-bucket-region";
+    pub const X_AMZ_BYPASS_GOVERNANCE_RETENTION: &'static str = "x-amz-bypass-governance-retention";
+    pub const X_AMZ_CONFIRM_REMOVE_SELF_BUCKET_ACCESS: &'static str =
+        "x-amz-confirm-remove-self-bucket-access";
     pub const X_AMZ_CONTENT_SHA256: &'static str = "x-amz-content-sha256";
+    pub const X_AMZ_COPY_SOURCE: &'static str = "x-amz-copy-source";
+    pub const X_AMZ_COPY_SOURCE_IF_MATCH: &'static str = "x-amz-copy-source-if-match";
+    pub const X_AMZ_COPY_SOURCE_IF_MODIFIED_SINCE: &'static str =
+        "x-amz-copy-source-if-modified-since";
+    pub const X_AMZ_COPY_SOURCE_IF_NONE_MATCH: &'static str = "x-amz-copy-source-if-none-match";
+    pub const X_AMZ_COPY_SOURCE_IF_UNMODIFIED_SINCE: &'static str =
+        "x-amz-copy-source-if-unmodified-since";
+    pub const X_AMZ_COPY_SOURCE_RANGE: &'static str = "x-amz-copy-source-range";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &'static str =
+        "x-amz-copy-source-server-side-encryption-customer-algorithm";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY: &'static str =
+        "x-amz-copy-source-server-side-encryption-customer-key";
+    pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5: &'static str =
+        "x-amz-copy-source-server-side-encryption-customer-key-md5";
+    pub const X_AMZ_COPY_SOURCE_VERSION_ID: &'static str = "x-amz-copy-source-version-id";
     pub const X_AMZ_DATE: &'static str = "x-amz-date";
+    pub const X_AMZ_MFA: &'static str = "x-amz-mfa";
+    pub const X_AMZ_METADATA_DIRECTIVE: &'static str = "x-amz-metadata-directive";
+    pub const X_AMZ_OBJECT_LOCK_LEGAL_HOLD: &'static str = "x-amz-object-lock-legal-hold";
+    pub const X_AMZ_OBJECT_LOCK_MODE: &'static str = "x-amz-object-lock-mode";
+    pub const X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE: &'static str =
+        "x-amz-object-lock-retain-until-date";
+    pub const X_AMZ_REPLICATION_STATUS: &'static str = "x-amz-replication-status";
+    pub const X_AMZ_REQUEST_CHARGED: &'static str = "x-amz-request-charged";
+    pub const X_AMZ_REQUEST_PAYER: &'static str = "x-amz-request-payer";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &'static str = "x-amz-server-side-encryption";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &'static str =
+        "x-amz-server-side-encryption-aws-kms-key-id";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_BUCKET_KEY_ENABLED: &'static str =
+        "x-amz-server-side-encryption-bucket-key-enabled";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CONTEXT: &'static str =
+        "x-amz-server-side-encryption-context";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &'static str =
+        "x-amz-server-side-encryption-customer-algorithm";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY: &'static str =
+        "x-amz-server-side-encryption-customer-key";
+    pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5: &'static str =
+        "x-amz-server-side-encryption-customer-key-md5";
+    pub const X_AMZ_SKIP_DESTINATION_VALIDATION: &'static str = "x-amz-skip-destination-validation";
     pub const X_AMZ_STORAGE_CLASS: &'static str = "x-amz-storage-class";
+    pub const X_AMZ_TAGGING: &'static str = "x-amz-tagging";
+    pub const X_AMZ_TAGGING_DIRECTIVE: &'static str = "x-amz-tagging-directive";
     pub const X_AMZ_VERSION_ID: &'static str = "x-amz-version-id";
     pub const X_AMZ_GRANT_WRITE: &'static str = "x-amz-grant-write";
     pub const X_AMZ_GRANT_READ: &'static str = "x-amz-grant-read";
@@ -0,0 +1,311 @@
+// Reads the reports written by an inventory configuration. A report is a
+// `manifest.json` listing gzip compressed csv data files, next to a
+// `manifest.checksum` holding the md5 of the manifest.
+use crate::{
+    error,
+    types::{
+        InventoryFormat,
+        ReplicationStatus,
+    },
+    uri_encode,
+    Error,
+    LegalHoldStatus,
+    ObjectLockMode,
+    StorageClass,
+};
+use chrono::{
+    DateTime,
+    TimeZone,
+    Utc,
+};
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::Read,
+    str::FromStr,
+};
+
+/// The manifest of an inventory report.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryManifest {
+    pub source_bucket: String,
+
+    /// The arn of the bucket the report was written to.
+    pub destination_bucket: String,
+
+    pub version: String,
+
+    /// When the report was started, in milliseconds since the epoch.
+    pub creation_timestamp: String,
+
+    pub file_format: InventoryFormat,
+
+    /// The comma separated columns of the data files.
+    pub file_schema: String,
+
+    pub files: Vec<InventoryFile>,
+}
+
+impl InventoryManifest {
+    /// Parse a `manifest.json`, verifying it against the contents of
+    /// `manifest.checksum` when given.
+    pub fn from_slice(manifest: &[u8], checksum: Option<&str>) -> Result<Self, Error> {
+        if let Some(checksum) = checksum {
+            verify_md5("manifest.json", manifest, checksum)?;
+        }
+
+        Ok(serde_json::from_slice(manifest).map_err(error::Internal::from)?)
+    }
+
+    /// The name of the bucket the data files were written to.
+    pub fn destination_bucket_name(&self) -> &str {
+        self.destination_bucket
+            .rsplit(':')
+            .next()
+            .unwrap_or(&self.destination_bucket)
+    }
+
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.creation_timestamp
+            .parse::<i64>()
+            .ok()
+            .map(|millis| Utc.timestamp_millis(millis))
+    }
+
+    pub fn columns(&self) -> Vec<&str> {
+        self.file_schema.split(',').map(str::trim).collect()
+    }
+
+    /// Read the rows of a data file of this report. `file` is the gzip
+    /// compressed file as stored in the bucket. Only csv reports can be read.
+    pub fn records<R: Read>(&self, file: R) -> Result<InventoryRecords<R>, Error> {
+        if self.file_format != InventoryFormat::Csv {
+            return Err(Error::InvalidInventory(format!(
+                "reading {} reports is not supported",
+                <&'static str>::from(self.file_format)
+            )));
+        }
+
+        Ok(InventoryRecords {
+            columns: self.columns().into_iter().map(str::to_owned).collect(),
+            rows: csv::ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(GzDecoder::new(file))
+                .into_records(),
+            row: 0,
+        })
+    }
+}
+
+/// A data file of an inventory report.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InventoryFile {
+    pub key: String,
+
+    /// The compressed size of the file.
+    pub size: u64,
+
+    #[serde(rename = "MD5checksum")]
+    pub md5_checksum: String,
+}
+
+impl InventoryFile {
+    /// Check the downloaded file against the checksum in the manifest.
+    pub fn verify(&self, file: &[u8]) -> Result<(), Error> {
+        verify_md5(&self.key, file, &self.md5_checksum)
+    }
+}
+
+fn verify_md5(name: &str, data: &[u8], checksum: &str) -> Result<(), Error> {
+    let actual = hex::encode(md5::compute(data).0);
+
+    if actual.eq_ignore_ascii_case(checksum.trim()) {
+        Ok(())
+    } else {
+        Err(Error::InvalidInventory(format!(
+            "checksum of {} is {} but the manifest expects {}",
+            name,
+            actual,
+            checksum.trim()
+        )))
+    }
+}
+
+/// A row of an inventory report. Columns that weren't included in the
+/// report, or are empty for the object, are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryRecord {
+    pub bucket: String,
+
+    /// The object key, already url-decoded.
+    pub key: String,
+
+    pub version_id: Option<String>,
+    pub is_latest: Option<bool>,
+    pub is_delete_marker: Option<bool>,
+    pub size: Option<u64>,
+    pub last_modified_date: Option<DateTime<Utc>>,
+    pub etag: Option<String>,
+    pub storage_class: Option<StorageClass>,
+    pub is_multipart_uploaded: Option<bool>,
+    pub replication_status: Option<ReplicationStatus>,
+    pub encryption_status: Option<String>,
+    pub object_lock_retain_until_date: Option<DateTime<Utc>>,
+    pub object_lock_mode: Option<ObjectLockMode>,
+    pub object_lock_legal_hold_status: Option<LegalHoldStatus>,
+    pub intelligent_tiering_access_tier: Option<String>,
+    pub bucket_key_status: Option<String>,
+    pub checksum_algorithm: Option<String>,
+
+    /// Columns this crate doesn't know, by name.
+    pub other: HashMap<String, String>,
+}
+
+impl InventoryRecord {
+    fn from_row(columns: &[String], row: &csv::StringRecord) -> Result<Self, String> {
+        let mut record = InventoryRecord::default();
+
+        for (column, value) in columns.iter().zip(row.iter()) {
+            if value.is_empty() {
+                continue;
+            }
+
+            match column.as_str() {
+                "Bucket" => record.bucket = value.to_owned(),
+                "Key" => {
+                    record.key = uri_encode::decode_form(value)
+                        .map_err(|_| format!("key {:?} is not valid utf-8", value))?
+                }
+                "VersionId" => record.version_id = Some(value.to_owned()),
+                "IsLatest" => record.is_latest = Some(parse(column, value)?),
+                "IsDeleteMarker" => record.is_delete_marker = Some(parse(column, value)?),
+                "Size" => record.size = Some(parse(column, value)?),
+                "LastModifiedDate" => record.last_modified_date = Some(parse_date(column, value)?),
+                "ETag" => record.etag = Some(value.to_owned()),
+                "StorageClass" => record.storage_class = Some(parse(column, value)?),
+                "IsMultipartUploaded" => record.is_multipart_uploaded = Some(parse(column, value)?),
+                "ReplicationStatus" => record.replication_status = Some(parse(column, value)?),
+                "EncryptionStatus" => record.encryption_status = Some(value.to_owned()),
+                "ObjectLockRetainUntilDate" => {
+                    record.object_lock_retain_until_date = Some(parse_date(column, value)?)
+                }
+                "ObjectLockMode" => record.object_lock_mode = Some(parse(column, value)?),
+                "ObjectLockLegalHoldStatus" => {
+                    record.object_lock_legal_hold_status = Some(parse(column, value)?)
+                }
+                "IntelligentTieringAccessTier" => {
+                    record.intelligent_tiering_access_tier = Some(value.to_owned())
+                }
+                "BucketKeyStatus" => record.bucket_key_status = Some(value.to_owned()),
+                "ChecksumAlgorithm" => record.checksum_algorithm = Some(value.to_owned()),
+                _ => {
+                    record.other.insert(column.clone(), value.to_owned());
+                }
+            }
+        }
+
+        Ok(record)
+    }
+}
+
+fn parse<T: FromStr>(column: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} has unexpected value {:?}", column, value))
+}
+
+fn parse_date(column: &str, value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|_| format!("{} has unexpected date {:?}", column, value))
+}
+
+/// Iterates over the rows of a data file, see `InventoryManifest::records()`.
+pub struct InventoryRecords<R: Read> {
+    columns: Vec<String>,
+    rows: csv::StringRecordsIntoIter<GzDecoder<R>>,
+    row: usize,
+}
+
+impl<R: Read> Iterator for InventoryRecords<R> {
+    type Item = Result<InventoryRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.rows.next()?;
+        self.row += 1;
+
+        Some(
+            row.map_err(|error| error.to_string())
+                .and_then(|row| InventoryRecord::from_row(&self.columns, &row))
+                .map_err(|error| Error::InvalidInventory(format!("row {}: {}", self.row, error))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{
+        write::GzEncoder,
+        Compression,
+    };
+    use std::io::Write;
+
+    #[test]
+    fn read_csv_report() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(
+                concat!(
+                    "\"source\",\"photos/a+b%2Bc.jpg\",\"1024\",\"2020-01-01T12:00:00.000Z\",\"STANDARD_IA\",\"COMPLETED\"\n",
+                    "\"source\",\"empty\",\"0\",\"2020-01-02T12:00:00.000Z\",\"STANDARD\",\"\"\n",
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        let file = encoder.finish().unwrap();
+
+        let manifest = format!(
+            r#"{{
+                "sourceBucket": "source",
+                "destinationBucket": "arn:aws:s3:::reports",
+                "version": "2016-11-30",
+                "creationTimestamp": "1577880000000",
+                "fileFormat": "CSV",
+                "fileSchema": "Bucket, Key, Size, LastModifiedDate, StorageClass, ReplicationStatus",
+                "files": [{{"key": "data/file.csv.gz", "size": {}, "MD5checksum": "{}"}}]
+            }}"#,
+            file.len(),
+            hex::encode(md5::compute(&file).0)
+        );
+        let checksum = hex::encode(md5::compute(manifest.as_bytes()).0);
+
+        assert!(InventoryManifest::from_slice(manifest.as_bytes(), Some("0000")).is_err());
+        let manifest = InventoryManifest::from_slice(manifest.as_bytes(), Some(&checksum)).unwrap();
+        assert_eq!(manifest.destination_bucket_name(), "reports");
+        assert_eq!(
+            manifest.created_at(),
+            Some(Utc.ymd(2020, 1, 1).and_hms(12, 0, 0))
+        );
+
+        manifest.files[0].verify(&file).unwrap();
+        assert!(manifest.files[0].verify(b"corrupt").is_err());
+
+        let records = manifest
+            .records(&file[..])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].key, "photos/a b+c.jpg");
+        assert_eq!(records[0].size, Some(1024));
+        assert_eq!(records[0].storage_class, Some(StorageClass::StandardIa));
+        assert_eq!(
+            records[0].replication_status,
+            Some(ReplicationStatus::Completed)
+        );
+        assert_eq!(records[1].replication_status, None);
+    }
+}
@@ -7,50 +7,222 @@ pub(crate) use crate::{
         Permission,
     },
     headers::Headers,
-    host::Host,
     optional_header::OptionalHeader,
-    payload_hash::PayloadHash,
-    query::{
-        QueryParam,
-        QueryParameter,
-    },
-    request::sub_resource::SubResource,
-    sign_request::SignRequest,
+    query::QueryParameter,
     signing_key::SigningKey,
+    uri_encode::UriEncode,
 };
 
 pub use crate::{
+    accelerate::AccelerateEndpoint,
+    access_log::{
+        AccessLogRecord,
+        AccessLogRecords,
+        AccessLogs,
+    },
     acl::Acl,
     aws_request::AwsRequest,
     cache::CacheControl,
     client::Client,
+    encryption::{
+        ObjectEncryption,
+        ServerSideEncryption,
+        SseCustomerKey,
+    },
     error::{
         Error,
         Result,
     },
-    region::Region,
+    inventory::{
+        InventoryFile,
+        InventoryManifest,
+        InventoryRecord,
+        InventoryRecords,
+    },
+    lifecycle::{
+        simulate_lifecycle,
+        LifecycleAction,
+        LifecycleActionKind,
+        LifecycleObject,
+        LifecycleReport,
+        LifecycleSimulation,
+        RuleReport,
+        Totals,
+    },
+    metadata::Metadata,
+    mfa::Mfa,
+    object_lock::{
+        LegalHoldStatus,
+        ObjectLock,
+        ObjectLockMode,
+    },
+    policy::{
+        Decision,
+        PolicyDecision,
+        PolicyRequest,
+    },
+    region::{
+        Partition,
+        Region,
+    },
     request::*,
+    request_payer::RequesterPays,
     storage_class::StorageClass,
+    types::{
+        AbortIncompleteMultipartUpload,
+        AccelerateConfiguration,
+        AccessControlTranslation,
+        AnalyticsConfiguration,
+        AnalyticsExportDestination,
+        AnalyticsFilter,
+        AnalyticsFormat,
+        AnalyticsS3BucketDestination,
+        BucketAccelerateStatus,
+        BucketLoggingStatus,
+        BucketLogsPermission,
+        BucketVersioning,
+        BucketVersioningStatus,
+        BucketWebsite,
+        CommonPrefix,
+        Condition,
+        ConditionValue,
+        CorsConfiguration,
+        CorsMethod,
+        CorsResponse,
+        CorsRule,
+        DefaultRetention,
+        DeleteError,
+        DeleteMarkerReplication,
+        DeleteResult,
+        DeletedObject,
+        Effect,
+        EncryptionConfiguration,
+        EventBucket,
+        EventEntity,
+        EventMessage,
+        EventObject,
+        EventRecord,
+        ExistingObjectReplication,
+        FilterRule,
+        FilterRuleName,
+        GlacierEventData,
+        GranteeType,
+        IncludedObjectVersions,
+        InventoryConfiguration,
+        InventoryDestination,
+        InventoryEncryption,
+        InventoryField,
+        InventoryFilter,
+        InventoryFormat,
+        InventoryFrequency,
+        InventoryS3BucketDestination,
+        InventorySchedule,
+        LambdaFunctionConfiguration,
+        LegalHold,
+        LifecycleConfiguration,
+        LifecycleExpiration,
+        LifecycleFilter,
+        LifecycleRule,
+        LifecycleStatus,
+        ListBucketAnalyticsConfigurationResult,
+        ListBucketResult,
+        ListInventoryConfigurationsResult,
+        ListMetricsConfigurationsResult,
+        ListVersionsResult,
+        LoggingEnabled,
+        MetricsConfiguration,
+        MetricsFilter,
+        MfaDelete,
+        NoncurrentVersionExpiration,
+        NoncurrentVersionTransition,
+        NotificationConfiguration,
+        NotificationEvent,
+        NotificationFilter,
+        Object,
+        ObjectIdentifier,
+        ObjectLockConfiguration,
+        ObjectLockEnabled,
+        ObjectLockRule,
+        ObjectVersion,
+        OneOrMany,
+        OptionalFields,
+        Payer,
+        Policy,
+        PolicyMap,
+        PolicyStatus,
+        Principal,
+        Protocol,
+        PublicAccessBlockConfiguration,
+        QueueConfiguration,
+        Redirect,
+        ReplicationConfiguration,
+        ReplicationDestination,
+        ReplicationFilter,
+        ReplicationMetrics,
+        ReplicationRule,
+        ReplicationRuleStatus,
+        ReplicationStatus,
+        ReplicationTime,
+        ReplicationTimeValue,
+        RequestParameters,
+        RequestPaymentConfiguration,
+        RestoreEventData,
+        Retention,
+        RoutingRule,
+        S3KeyFilter,
+        SourceSelectionCriteria,
+        SseKms,
+        SseKmsEncryptedObjects,
+        SseS3,
+        Statement,
+        StorageClassAnalysis,
+        StorageClassAnalysisDataExport,
+        Tag,
+        TagConflict,
+        TagSet,
+        TagSetChanges,
+        TagSetMerge,
+        TargetGrant,
+        TargetGrantee,
+        TargetGrants,
+        TopicConfiguration,
+        Transition,
+        UserIdentity,
+        WebsiteRedirect,
+    },
 };
 
+mod accelerate;
+mod access_log;
 mod acl;
 mod aws_request;
 mod aws_response;
+mod bucket_region;
 mod cache;
+mod encryption;
 mod error;
 mod gmt;
 mod grant;
 mod headers;
 mod host;
+mod inventory;
+mod lifecycle;
+mod metadata;
+mod mfa;
+mod object_lock;
 mod optional_header;
 mod payload_hash;
+mod policy;
 mod query;
 mod region;
 mod request;
+mod request_payer;
 mod sign_request;
 mod signing_key;
 mod storage_class;
 mod types;
+mod uri_encode;
+mod xml;
 
 #[cfg(feature = "credential_file")]
 mod parser;
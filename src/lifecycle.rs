@@ -0,0 +1,671 @@
+// Simulates what a lifecycle configuration would do to the objects of a
+// bucket at a given date, without sending any requests. The objects are
+// read from a listing, so buckets of any size can be simulated in a single
+// pass while only keeping the versions of one key in memory.
+use crate::{
+    types::{
+        LifecycleConfiguration,
+        LifecycleRule,
+        LifecycleStatus,
+        Object,
+        ObjectVersion,
+    },
+    StorageClass,
+    TagSet,
+};
+use chrono::{
+    DateTime,
+    Duration,
+    Timelike,
+    Utc,
+};
+use std::{
+    collections::HashMap,
+    iter::Peekable,
+};
+
+/// An object or object version as listed by `ListObjectsV2` or
+/// `ListObjectVersions`, the input of the lifecycle simulator. Listings
+/// don't include tags, so they have to be added for rules with a tag filter.
+#[derive(Debug, Clone)]
+pub struct LifecycleObject {
+    pub key: String,
+    pub version_id: Option<String>,
+    pub size: u64,
+    pub last_modified: DateTime<Utc>,
+    pub storage_class: StorageClass,
+
+    /// The tags of the object, only needed for rules with a tag filter.
+    pub tags: TagSet,
+
+    /// Whether this is the current version of the object.
+    pub is_latest: bool,
+    pub delete_marker: bool,
+}
+
+impl LifecycleObject {
+    /// Create the current version of an object stored as `STANDARD`
+    pub fn new<T: Into<String>>(key: T, size: u64, last_modified: DateTime<Utc>) -> Self {
+        LifecycleObject {
+            key: key.into(),
+            version_id: None,
+            size,
+            last_modified,
+            storage_class: StorageClass::Standard,
+            tags: TagSet::new(),
+            is_latest: true,
+            delete_marker: false,
+        }
+    }
+
+    /// Create a delete marker
+    pub fn delete_marker<T: Into<String>>(key: T, last_modified: DateTime<Utc>) -> Self {
+        LifecycleObject {
+            delete_marker: true,
+            ..LifecycleObject::new(key, 0, last_modified)
+        }
+    }
+
+    /// The version of the object, and whether it is the current version.
+    pub fn version<T: Into<String>>(mut self, version_id: T, is_latest: bool) -> Self {
+        self.version_id = Some(version_id.into());
+        self.is_latest = is_latest;
+        self
+    }
+
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = storage_class;
+        self
+    }
+
+    pub fn tags(mut self, tags: TagSet) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+impl From<Object> for LifecycleObject {
+    fn from(object: Object) -> Self {
+        LifecycleObject::new(object.key, object.size, object.last_modified)
+            .storage_class(object.storage_class)
+    }
+}
+
+impl From<ObjectVersion> for LifecycleObject {
+    fn from(version: ObjectVersion) -> Self {
+        LifecycleObject {
+            key: version.key,
+            version_id: Some(version.version_id),
+            size: version.size,
+            last_modified: version.last_modified,
+            storage_class: version.storage_class,
+            tags: TagSet::new(),
+            is_latest: version.is_latest,
+            delete_marker: version.delete_marker,
+        }
+    }
+}
+
+/// What a lifecycle rule would do to an object.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LifecycleActionKind {
+    /// The current version moves to another storage class.
+    Transition(StorageClass),
+
+    /// The current version expires. In a versioned bucket it becomes
+    /// noncurrent behind a new delete marker.
+    Expire,
+
+    /// A noncurrent version moves to another storage class.
+    NoncurrentTransition(StorageClass),
+
+    /// A noncurrent version is permanently deleted.
+    NoncurrentExpire,
+
+    /// A delete marker without any versions behind it is removed.
+    RemoveDeleteMarker,
+}
+
+/// A single action of the simulation.
+#[derive(Debug, Clone)]
+pub struct LifecycleAction {
+    /// The index of the rule in the configuration.
+    pub rule: usize,
+    pub key: String,
+    pub version_id: Option<String>,
+    pub size: u64,
+    pub kind: LifecycleActionKind,
+}
+
+/// A number of objects and their combined size.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Totals {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+impl Totals {
+    fn add(&mut self, size: u64) {
+        self.count += 1;
+        self.bytes += size;
+    }
+}
+
+/// Everything a single rule would do.
+#[derive(Debug, Clone, Default)]
+pub struct RuleReport {
+    pub id: Option<String>,
+    pub transitioned: HashMap<StorageClass, Totals>,
+    pub expired: Totals,
+    pub noncurrent_transitioned: HashMap<StorageClass, Totals>,
+    pub noncurrent_expired: Totals,
+    pub delete_markers_removed: u64,
+}
+
+/// The per-rule totals of a simulation, in the order of the rules of the
+/// configuration.
+#[derive(Debug, Clone, Default)]
+pub struct LifecycleReport {
+    pub rules: Vec<RuleReport>,
+
+    /// The objects and versions no rule would act on.
+    pub unaffected: Totals,
+}
+
+/// Simulate `config` at the date `at` over a listing of objects. Versions of
+/// the same key must be listed next to each other, as `ListObjectVersions`
+/// does. The simulation is an iterator over the actions that would be taken,
+/// the totals are available from `LifecycleSimulation::report()` once it is
+/// exhausted.
+///
+/// Each object is only evaluated once, actions that would only become
+/// possible after another action (such as a delete marker whose versions
+/// expire on the same date) are included, later runs of the lifecycle are not.
+pub fn simulate_lifecycle<I>(
+    config: &LifecycleConfiguration,
+    at: DateTime<Utc>,
+    objects: I,
+) -> LifecycleSimulation<'_, I::IntoIter>
+where
+    I: IntoIterator<Item = LifecycleObject>,
+{
+    LifecycleSimulation {
+        config,
+        at,
+        objects: objects.into_iter().peekable(),
+        pending: Vec::new(),
+        report: LifecycleReport {
+            rules: config
+                .rules
+                .iter()
+                .map(|rule| RuleReport {
+                    id: rule.id.clone(),
+                    ..RuleReport::default()
+                })
+                .collect(),
+            unaffected: Totals::default(),
+        },
+    }
+}
+
+pub struct LifecycleSimulation<'c, I: Iterator<Item = LifecycleObject>> {
+    config: &'c LifecycleConfiguration,
+    at: DateTime<Utc>,
+    objects: Peekable<I>,
+    pending: Vec<LifecycleAction>,
+    report: LifecycleReport,
+}
+
+impl<'c, I: Iterator<Item = LifecycleObject>> LifecycleSimulation<'c, I> {
+    /// The totals of the actions returned so far.
+    pub fn report(&self) -> &LifecycleReport {
+        &self.report
+    }
+
+    /// Run the remaining simulation, only keeping the totals.
+    pub fn into_report(mut self) -> LifecycleReport {
+        while self.next().is_some() {}
+        self.report
+    }
+
+    fn rules_for<'o>(
+        &'o self,
+        object: &'o LifecycleObject,
+    ) -> impl Iterator<Item = (usize, &'c LifecycleRule)> + 'o {
+        self.config
+            .rules
+            .iter()
+            .enumerate()
+            .filter(move |(_, rule)| applies(rule, object))
+    }
+
+    fn simulate_key(&mut self, mut versions: Vec<LifecycleObject>) {
+        // Newest first, so every version was replaced by the one before it
+        versions.sort_by(|a, b| {
+            b.is_latest
+                .cmp(&a.is_latest)
+                .then(b.last_modified.cmp(&a.last_modified))
+        });
+
+        let mut actions = Vec::new();
+        let mut unaffected = Vec::new();
+        let mut remaining = 0;
+        let mut noncurrent_index = 0;
+
+        for (index, version) in versions.iter().enumerate() {
+            let action = if version.is_latest {
+                if version.delete_marker {
+                    // Decided once the noncurrent versions are known
+                    None
+                } else {
+                    self.current_action(version)
+                }
+            } else {
+                // A listing may hold no newer version, e.g. a page of only
+                // noncurrent versions, in which case the version is taken to
+                // have become noncurrent when it was stored
+                let since = match index.checked_sub(1) {
+                    Some(newer) => versions[newer].last_modified,
+                    None => version.last_modified,
+                };
+                let action = self.noncurrent_action(version, since, noncurrent_index);
+                noncurrent_index += 1;
+
+                if action.map(|(_, kind)| kind) != Some(LifecycleActionKind::NoncurrentExpire) {
+                    remaining += 1;
+                }
+                action
+            };
+
+            if let Some((rule, kind)) = action {
+                actions.push(LifecycleAction {
+                    rule,
+                    key: version.key.clone(),
+                    version_id: version.version_id.clone(),
+                    size: version.size,
+                    kind,
+                });
+            } else if !(version.is_latest && version.delete_marker) {
+                unaffected.push(version.size);
+            }
+        }
+
+        if let Some(marker) = versions.first().filter(|v| v.is_latest && v.delete_marker) {
+            let rule = self.rules_for(marker).find(|(_, rule)| {
+                rule.expiration
+                    .as_ref()
+                    .and_then(|expiration| expiration.expired_object_delete_marker)
+                    == Some(true)
+            });
+
+            if let (Some((rule, _)), 0) = (rule, remaining) {
+                actions.push(LifecycleAction {
+                    rule,
+                    key: marker.key.clone(),
+                    version_id: marker.version_id.clone(),
+                    size: 0,
+                    kind: LifecycleActionKind::RemoveDeleteMarker,
+                });
+            } else {
+                unaffected.push(0);
+            }
+        }
+
+        for action in actions.iter() {
+            let report = &mut self.report.rules[action.rule];
+            match action.kind {
+                LifecycleActionKind::Transition(class) => report
+                    .transitioned
+                    .entry(class)
+                    .or_default()
+                    .add(action.size),
+                LifecycleActionKind::Expire => report.expired.add(action.size),
+                LifecycleActionKind::NoncurrentTransition(class) => report
+                    .noncurrent_transitioned
+                    .entry(class)
+                    .or_default()
+                    .add(action.size),
+                LifecycleActionKind::NoncurrentExpire => report.noncurrent_expired.add(action.size),
+                LifecycleActionKind::RemoveDeleteMarker => report.delete_markers_removed += 1,
+            }
+        }
+
+        for size in unaffected {
+            self.report.unaffected.add(size);
+        }
+
+        // Returned in listing order by `next()`
+        actions.reverse();
+        self.pending = actions;
+    }
+
+    // Expiration takes precedence over transitions, and of the transitions
+    // due the coldest storage class wins.
+    fn current_action(&self, object: &LifecycleObject) -> Option<(usize, LifecycleActionKind)> {
+        let expired = self.rules_for(object).find(|(_, rule)| {
+            let due = rule.expiration.as_ref().and_then(|expiration| {
+                match (expiration.days, expiration.date) {
+                    (Some(days), _) => Some(after_days(object.last_modified, days)),
+                    (None, date) => date,
+                }
+            });
+            matches!(due, Some(due) if due <= self.at)
+        });
+
+        if let Some((rule, _)) = expired {
+            return Some((rule, LifecycleActionKind::Expire));
+        }
+
+        self.rules_for(object)
+            .flat_map(|(index, rule)| {
+                rule.transitions
+                    .iter()
+                    .filter(|transition| {
+                        let due = match (transition.days, transition.date) {
+                            (Some(days), _) => Some(after_days(object.last_modified, days)),
+                            (None, Some(date)) => Some(date),
+                            _ => None,
+                        };
+                        matches!(due, Some(due) if due <= self.at)
+                    })
+                    .map(move |transition| (index, transition.storage_class))
+            })
+            .filter(|(_, class)| coldness(*class) > coldness(object.storage_class))
+            .fold(None, coldest)
+            .map(|(rule, class)| (rule, LifecycleActionKind::Transition(class)))
+    }
+
+    // `index` is the position of the version among the noncurrent versions,
+    // counting from the newest.
+    fn noncurrent_action(
+        &self,
+        version: &LifecycleObject,
+        since: DateTime<Utc>,
+        index: u32,
+    ) -> Option<(usize, LifecycleActionKind)> {
+        let expired = self.rules_for(version).find(|(_, rule)| {
+            rule.noncurrent_version_expiration.iter().any(|expiration| {
+                index >= expiration.newer_noncurrent_versions.unwrap_or(0)
+                    && after_days(since, expiration.noncurrent_days) <= self.at
+            })
+        });
+
+        if let Some((rule, _)) = expired {
+            return Some((rule, LifecycleActionKind::NoncurrentExpire));
+        }
+
+        if version.delete_marker {
+            return None;
+        }
+
+        self.rules_for(version)
+            .flat_map(|(rule_index, rule)| {
+                rule.noncurrent_version_transitions
+                    .iter()
+                    .filter(|transition| {
+                        index >= transition.newer_noncurrent_versions.unwrap_or(0)
+                            && after_days(since, transition.noncurrent_days) <= self.at
+                    })
+                    .map(move |transition| (rule_index, transition.storage_class))
+            })
+            .filter(|(_, class)| coldness(*class) > coldness(version.storage_class))
+            .fold(None, coldest)
+            .map(|(rule, class)| (rule, LifecycleActionKind::NoncurrentTransition(class)))
+    }
+}
+
+impl<'c, I: Iterator<Item = LifecycleObject>> Iterator for LifecycleSimulation<'c, I> {
+    type Item = LifecycleAction;
+
+    fn next(&mut self) -> Option<LifecycleAction> {
+        loop {
+            if let Some(action) = self.pending.pop() {
+                return Some(action);
+            }
+
+            let first = self.objects.next()?;
+            let mut versions = vec![first];
+            while let Some(next) = self.objects.peek() {
+                if next.key != versions[0].key {
+                    break;
+                }
+                versions.extend(self.objects.next());
+            }
+
+            self.simulate_key(versions);
+        }
+    }
+}
+
+fn applies(rule: &LifecycleRule, object: &LifecycleObject) -> bool {
+    if rule.status != LifecycleStatus::Enabled {
+        return false;
+    }
+
+    match (&rule.filter, &rule.prefix) {
+        (Some(filter), _) => filter.matches(&object.key, object.size, &object.tags),
+        (None, Some(prefix)) => object.key.starts_with(prefix.as_str()),
+        (None, None) => true,
+    }
+}
+
+// S3 adds the days to the time the object was created, or became
+// noncurrent, and rounds up to the next midnight UTC.
+fn after_days(start: DateTime<Utc>, days: u32) -> DateTime<Utc> {
+    let due = start + Duration::days(i64::from(days));
+    let midnight = due.date().and_hms(0, 0, 0);
+
+    if due.num_seconds_from_midnight() == 0 && due.nanosecond() == 0 {
+        midnight
+    } else {
+        midnight + Duration::days(1)
+    }
+}
+
+fn coldness(storage_class: StorageClass) -> u8 {
+    match storage_class {
+        StorageClass::Standard | StorageClass::ReducedRedundancy => 0,
+        StorageClass::IntelligentTiering => 1,
+        StorageClass::StandardIa => 2,
+        StorageClass::OnezoneIa => 3,
+        StorageClass::Glacier => 4,
+        StorageClass::DeepArchive => 5,
+    }
+}
+
+fn coldest(
+    best: Option<(usize, StorageClass)>,
+    next: (usize, StorageClass),
+) -> Option<(usize, StorageClass)> {
+    match best {
+        Some(best) if coldness(best.1) >= coldness(next.1) => Some(best),
+        _ => Some(next),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LifecycleFilter;
+    use chrono::TimeZone;
+
+    #[test]
+    fn simulation_reports_per_rule_totals() {
+        let config = LifecycleConfiguration::new()
+            .rule(
+                LifecycleRule::new("logs")
+                    .filter(LifecycleFilter::new().prefix("logs/"))
+                    .transition_after_days(30, StorageClass::StandardIa)
+                    .transition_after_days(90, StorageClass::Glacier)
+                    .expire_after_days(365),
+            )
+            .rule(
+                LifecycleRule::new("versions")
+                    .noncurrent_version_expiration(10)
+                    .expire_object_delete_markers(),
+            );
+
+        let day = |day| Utc.ymd(2020, 1, 1).and_hms(12, 0, 0) + Duration::days(day);
+        let at = day(100);
+
+        let objects = vec![
+            LifecycleObject::new("logs/a", 10, day(0)),
+            LifecycleObject::new("logs/b", 20, day(50)),
+            LifecycleObject::new("logs/c", 40, day(80)),
+            LifecycleObject::new("logs/d", 80, day(99)).storage_class(StorageClass::Glacier),
+            LifecycleObject::new("data", 100, day(0)).version("2", true),
+            LifecycleObject::new("data", 200, day(0) - Duration::days(1)).version("1", false),
+            LifecycleObject::delete_marker("gone", day(95)).version("3", true),
+            LifecycleObject::new("gone", 5, day(50)).version("2", false),
+            LifecycleObject::new("gone", 5, day(40)).version("1", false),
+        ];
+
+        let mut simulation = simulate_lifecycle(&config, at, objects);
+        let actions = simulation.by_ref().collect::<Vec<_>>();
+        let report = simulation.into_report();
+
+        assert_eq!(
+            actions
+                .iter()
+                .map(|action| (action.key.as_str(), action.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "logs/a",
+                    LifecycleActionKind::Transition(StorageClass::Glacier)
+                ),
+                (
+                    "logs/b",
+                    LifecycleActionKind::Transition(StorageClass::StandardIa)
+                ),
+                ("data", LifecycleActionKind::NoncurrentExpire),
+                ("gone", LifecycleActionKind::NoncurrentExpire),
+            ]
+        );
+
+        let logs = &report.rules[0];
+        assert_eq!(
+            logs.transitioned[&StorageClass::Glacier],
+            Totals {
+                count: 1,
+                bytes: 10
+            }
+        );
+        assert_eq!(
+            logs.transitioned[&StorageClass::StandardIa],
+            Totals {
+                count: 1,
+                bytes: 20
+            }
+        );
+
+        let versions = &report.rules[1];
+        assert_eq!(
+            versions.noncurrent_expired,
+            Totals {
+                count: 2,
+                bytes: 205
+            }
+        );
+        assert_eq!(versions.delete_markers_removed, 0);
+        assert_eq!(
+            report.unaffected,
+            Totals {
+                count: 5,
+                bytes: 225
+            }
+        );
+
+        // Once every version behind it expired the delete marker is removed
+        let report = simulate_lifecycle(
+            &config,
+            at,
+            vec![
+                LifecycleObject::delete_marker("gone", day(80)).version("2", true),
+                LifecycleObject::new("gone", 5, day(40)).version("1", false),
+            ],
+        )
+        .into_report();
+        assert_eq!(report.rules[1].noncurrent_expired.count, 1);
+        assert_eq!(report.rules[1].delete_markers_removed, 1);
+    }
+
+    #[test]
+    fn listings_feed_the_simulation() {
+        let config = LifecycleConfiguration::new().rule(
+            LifecycleRule::new("versions")
+                .noncurrent_version_expiration(10)
+                .expire_object_delete_markers(),
+        );
+
+        let listing: crate::types::ListVersionsResult = quick_xml::de::from_str(concat!(
+            "<ListVersionsResult><Name>bucket</Name><IsTruncated>false</IsTruncated>",
+            "<DeleteMarker><Key>gone</Key><VersionId>2</VersionId><IsLatest>true</IsLatest>",
+            "<LastModified>2020-03-21T12:00:00.000Z</LastModified></DeleteMarker>",
+            "<Version><Key>gone</Key><VersionId>1</VersionId><IsLatest>false</IsLatest>",
+            "<LastModified>2020-02-10T12:00:00.000Z</LastModified><Size>5</Size>",
+            "<StorageClass>STANDARD_IA</StorageClass></Version>",
+            "</ListVersionsResult>"
+        ))
+        .unwrap();
+
+        let objects = listing
+            .versions
+            .into_iter()
+            .map(LifecycleObject::from)
+            .collect::<Vec<_>>();
+        assert!(objects[0].delete_marker);
+        assert_eq!(objects[1].storage_class, StorageClass::StandardIa);
+
+        let at = Utc.ymd(2020, 4, 10).and_hms(12, 0, 0);
+        let report = simulate_lifecycle(&config, at, objects).into_report();
+        assert_eq!(report.rules[0].noncurrent_expired.count, 1);
+        assert_eq!(report.rules[0].delete_markers_removed, 1);
+
+        let listing: crate::types::ListBucketResult = quick_xml::de::from_str(concat!(
+            "<ListBucketResult><Name>bucket</Name><KeyCount>1</KeyCount>",
+            "<MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated>",
+            "<Contents><Key>a</Key><LastModified>2020-01-01T00:00:00.000Z</LastModified>",
+            "<Size>3</Size><StorageClass>GLACIER</StorageClass></Contents>",
+            "</ListBucketResult>"
+        ))
+        .unwrap();
+
+        let object = listing
+            .contents
+            .into_iter()
+            .map(LifecycleObject::from)
+            .next()
+            .unwrap();
+        assert_eq!(object.storage_class, StorageClass::Glacier);
+        assert!(object.is_latest);
+    }
+
+    #[test]
+    fn keys_without_a_current_version() {
+        let config = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("versions").noncurrent_version_expiration(10));
+
+        let day = |day| Utc.ymd(2020, 1, 1).and_hms(12, 0, 0) + Duration::days(day);
+
+        let report = simulate_lifecycle(
+            &config,
+            day(30),
+            vec![
+                LifecycleObject::new("old", 5, day(18)).version("3", false),
+                LifecycleObject::new("old", 7, day(10)).version("2", false),
+                LifecycleObject::new("recent", 3, day(25)).version("1", false),
+            ],
+        )
+        .into_report();
+
+        assert_eq!(
+            report.rules[0].noncurrent_expired,
+            Totals {
+                count: 2,
+                bytes: 12
+            }
+        );
+        assert_eq!(report.unaffected, Totals { count: 1, bytes: 3 });
+    }
+}
@@ -0,0 +1,314 @@
+use crate::Error;
+use http::HeaderMap;
+use std::collections::{
+    btree_map,
+    BTreeMap,
+};
+
+const PREFIX: &str = "x-amz-meta-";
+
+// User-defined metadata is limited to 2 KB, measured as the sum of the
+// number of bytes in the UTF-8 encoding of each name and value.
+const MAX_SIZE: usize = 2048;
+
+// An encoded-word may not be more than 75 characters long. `=?UTF-8?B?` and
+// `?=` take up 12 of those, leaving 63 for the base64 text, which is at most
+// 45 bytes of input when keeping the text free of padding.
+const MAX_WORD_BYTES: usize = 45;
+
+/// User-defined object metadata sent and received as `x-amz-meta-*` headers.
+/// Names are case-insensitive and are stored in lowercase, the same way S3
+/// stores them. Values containing non-ASCII characters are RFC 2047 encoded
+/// when sent and decoded again when received.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Metadata {
+    entries: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    /// Create a new empty metadata map
+    pub fn new() -> Self {
+        Metadata::default()
+    }
+
+    /// Insert a metadata entry, returning the previous value for `name` if any.
+    /// The name must be a valid HTTP header token without the `x-amz-meta-`
+    /// prefix, and the value may not contain control characters.
+    pub fn insert<N: AsRef<str>, V: Into<String>>(
+        &mut self,
+        name: N,
+        value: V,
+    ) -> Result<Option<String>, Error> {
+        let name = name.as_ref().to_ascii_lowercase();
+        let value = value.into();
+
+        if name.is_empty() || !name.bytes().all(is_token) {
+            return Err(Error::InvalidMetadata(format!(
+                "{:?} is not a valid metadata name",
+                name
+            )));
+        }
+
+        if value.chars().any(|c| c.is_control() && c != '\t') {
+            return Err(Error::InvalidMetadata(format!(
+                "value of {:?} contains control characters",
+                name
+            )));
+        }
+
+        let size = self.size() + name.len() + value.len()
+            - self
+                .get(&name)
+                .map(|old| name.len() + old.len())
+                .unwrap_or(0);
+        if size > MAX_SIZE {
+            return Err(Error::InvalidMetadata(format!(
+                "metadata may not be larger than {} bytes",
+                MAX_SIZE
+            )));
+        }
+
+        Ok(self.entries.insert(name, value))
+    }
+
+    /// Get the value for `name`, ignoring case.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Remove the value for `name`, ignoring case.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        self.entries.remove(&name.to_ascii_lowercase())
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.entries.contains_key(&name.to_ascii_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the entries ordered by name
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.entries.iter())
+    }
+
+    fn size(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|(name, value)| name.len() + value.len())
+            .sum()
+    }
+
+    /// The `x-amz-meta-*` headers to send with a request
+    pub(crate) fn headers(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.entries
+            .iter()
+            .map(|(name, value)| (format!("{}{}", PREFIX, name), encode(value)))
+    }
+
+    /// Collect the `x-amz-meta-*` headers of a response. S3 already validated
+    /// these, so values are taken as is apart from decoding.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let entries = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                name.as_str().strip_prefix(PREFIX).map(|name| {
+                    (
+                        name.to_owned(),
+                        decode(&String::from_utf8_lossy(value.as_bytes())),
+                    )
+                })
+            })
+            .collect();
+
+        Metadata { entries }
+    }
+}
+
+pub struct Iter<'a>(btree_map::Iter<'a, String, String>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = (&'a str, &'a str);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+// tchar from rfc7230
+fn is_token(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// Encode a value as a list of RFC 2047 encoded-words if it is not ASCII.
+/// Words never split a character, as required by the rfc.
+fn encode(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_owned();
+    }
+
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+
+    for (index, c) in value.char_indices() {
+        if index + c.len_utf8() - start > MAX_WORD_BYTES {
+            words.push(format!(
+                "=?UTF-8?B?{}?=",
+                base64::encode(&value[start..end])
+            ));
+            start = end;
+        }
+        end = index + c.len_utf8();
+    }
+    words.push(format!(
+        "=?UTF-8?B?{}?=",
+        base64::encode(&value[start..end])
+    ));
+
+    words.join(" ")
+}
+
+/// Decode any RFC 2047 encoded-words in a value. Whitespace between two
+/// adjacent encoded-words is dropped, anything that isn't a valid word is
+/// kept unchanged.
+fn decode(value: &str) -> String {
+    let mut output = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+
+        if let Some((decoded, length)) = decode_word(candidate) {
+            if !(after_word && before.trim().is_empty()) {
+                output.push_str(before);
+            }
+            output.push_str(&decoded);
+            rest = &candidate[length..];
+            after_word = true;
+        } else {
+            output.push_str(before);
+            output.push_str("=?");
+            rest = &candidate[2..];
+            after_word = false;
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Decode a single `=?charset?encoding?text?=` word at the start of `word`,
+/// returning the decoded text and the length of the word.
+fn decode_word(word: &str) -> Option<(String, usize)> {
+    let mut parts = word[2..].splitn(3, '?');
+    let charset = parts.next()?;
+    let encoding = parts.next()?;
+    let rest = parts.next()?;
+    let text = &rest[..rest.find("?=")?];
+
+    if text.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let length = 2 + charset.len() + 1 + encoding.len() + 1 + text.len() + 2;
+
+    // The charset may be followed by a language, e.g. `UTF-8*en`
+    let charset = charset.split('*').next()?;
+    if !charset.eq_ignore_ascii_case("utf-8") && !charset.eq_ignore_ascii_case("us-ascii") {
+        return None;
+    }
+
+    let bytes = match encoding {
+        "B" | "b" => base64::decode(text).ok()?,
+        "Q" | "q" => decode_q(text)?,
+        _ => return None,
+    };
+
+    Some((String::from_utf8(bytes).ok()?, length))
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut iter = text.bytes();
+
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [iter.next()?, iter.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_case_insensitive() {
+        let mut metadata = Metadata::new();
+        metadata.insert("Source-Commit", "abc123").unwrap();
+
+        assert_eq!(metadata.get("source-commit"), Some("abc123"));
+        assert_eq!(metadata.get("SOURCE-COMMIT"), Some("abc123"));
+        assert_eq!(
+            metadata.headers().collect::<Vec<_>>(),
+            vec![("x-amz-meta-source-commit".to_owned(), "abc123".to_owned())]
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_rejected() {
+        let mut metadata = Metadata::new();
+
+        assert!(metadata.insert("", "value").is_err());
+        assert!(metadata.insert("has space", "value").is_err());
+        assert!(metadata.insert("name", "line\nbreak").is_err());
+        assert!(metadata.insert("name", "x".repeat(MAX_SIZE)).is_err());
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn non_ascii_round_trip() {
+        let value = "Ünïcödé ".repeat(10);
+        let encoded = encode(&value);
+
+        assert!(encoded.is_ascii());
+        assert!(encoded.split(' ').all(|word| word.len() <= 75));
+        assert_eq!(decode(&encoded), value);
+    }
+
+    #[test]
+    fn decode_q_words() {
+        assert_eq!(decode("=?utf-8?Q?caf=C3=A9_au_lait?="), "café au lait");
+        assert_eq!(decode("a =?UTF-8?B?w6k=?= b"), "a é b");
+        assert_eq!(decode("=?not a word"), "=?not a word");
+    }
+}
@@ -0,0 +1,25 @@
+/// A multi-factor authentication device and the code it currently shows,
+/// sent as `x-amz-mfa`. It is needed to change the MFA delete setting of a
+/// bucket, and to delete object versions once MFA delete is enabled.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mfa {
+    /// The serial number of a hardware device, or the arn of a virtual one.
+    pub serial: String,
+
+    /// The code currently shown by the device.
+    pub token: String,
+}
+
+impl Mfa {
+    pub fn new<S: Into<String>, T: Into<String>>(serial: S, token: T) -> Self {
+        Mfa {
+            serial: serial.into(),
+            token: token.into(),
+        }
+    }
+
+    // The serial and the token separated by a space
+    pub(crate) fn header_value(&self) -> String {
+        format!("{} {}", self.serial, self.token)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    error,
+    xml,
+    Error,
+    Headers,
+    Request,
+};
+use chrono::{
+    DateTime,
+    SecondsFormat,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+};
+use std::str::FromStr;
+
+/// The retention mode protecting a locked object version.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ObjectLockMode {
+    /// The object version can't be overwritten or deleted, nor can its
+    /// retention be shortened, unless the user has the
+    /// `s3:BypassGovernanceRetention` permission and bypasses the retention.
+    Governance,
+
+    /// The object version can't be overwritten or deleted by any user,
+    /// including the root user, until the retention period expires.
+    Compliance,
+}
+
+impl From<ObjectLockMode> for &'static str {
+    fn from(mode: ObjectLockMode) -> &'static str {
+        match mode {
+            ObjectLockMode::Governance => "GOVERNANCE",
+            ObjectLockMode::Compliance => "COMPLIANCE",
+        }
+    }
+}
+
+impl FromStr for ObjectLockMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GOVERNANCE" => Ok(ObjectLockMode::Governance),
+            "COMPLIANCE" => Ok(ObjectLockMode::Compliance),
+            _ => Err(Error::from(error::Internal::ParseObjectLockError)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectLockMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        xml::from_text(deserializer)
+    }
+}
+
+/// Whether a legal hold is placed on an object version. A legal hold
+/// prevents the object version from being overwritten or deleted until it
+/// is removed, independent of any retention period.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LegalHoldStatus {
+    On,
+    Off,
+}
+
+impl From<LegalHoldStatus> for &'static str {
+    fn from(status: LegalHoldStatus) -> &'static str {
+        match status {
+            LegalHoldStatus::On => "ON",
+            LegalHoldStatus::Off => "OFF",
+        }
+    }
+}
+
+impl FromStr for LegalHoldStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ON" => Ok(LegalHoldStatus::On),
+            "OFF" => Ok(LegalHoldStatus::Off),
+            _ => Err(Error::from(error::Internal::ParseObjectLockError)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for LegalHoldStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        xml::from_text(deserializer)
+    }
+}
+
+/// The object lock settings of an object version, as returned in the
+/// headers of object responses.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ObjectLock {
+    pub mode: Option<ObjectLockMode>,
+    pub retain_until_date: Option<DateTime<Utc>>,
+    pub legal_hold: Option<LegalHoldStatus>,
+}
+
+/// The object lock options of a request that stores an object.
+#[derive(Default)]
+pub(crate) struct ObjectLockHeaders {
+    pub(crate) mode: Option<ObjectLockMode>,
+    pub(crate) retain_until_date: Option<DateTime<Utc>>,
+    pub(crate) legal_hold: Option<LegalHoldStatus>,
+}
+
+impl ObjectLockHeaders {
+    pub(crate) fn headers<'r>(&self, request: Request<'r>) -> Request<'r> {
+        request
+            .header(
+                Headers::X_AMZ_OBJECT_LOCK_MODE,
+                self.mode.map(<&'static str>::from),
+            )
+            .header(
+                Headers::X_AMZ_OBJECT_LOCK_RETAIN_UNTIL_DATE,
+                self.retain_until_date.map(to_iso8601),
+            )
+            .header(
+                Headers::X_AMZ_OBJECT_LOCK_LEGAL_HOLD,
+                self.legal_hold.map(<&'static str>::from),
+            )
+    }
+}
+
+/// Dates of the object lock api are ISO 8601 formatted, e.g. `2020-01-31T00:00:00.000Z`.
+pub(crate) fn to_iso8601(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+pub(crate) fn from_iso8601(date: &str) -> Result<DateTime<Utc>, Error> {
+    Ok(DateTime::parse_from_rfc3339(date)
+        .map_err(error::Internal::from)?
+        .with_timezone(&Utc))
+}
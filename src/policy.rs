@@ -0,0 +1,643 @@
+// Evaluates a bucket policy locally, following the IAM evaluation logic for
+// a single policy: a matching `Deny` statement always wins, otherwise a
+// matching `Allow` statement allows the request, and anything else is
+// implicitly denied.
+use crate::{
+    types::{
+        ConditionValue,
+        Effect,
+        OneOrMany,
+        Policy,
+        PolicyMap,
+        Principal,
+        Statement,
+    },
+    Error,
+};
+use chrono::{
+    DateTime,
+    SecondsFormat,
+    TimeZone,
+    Utc,
+};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+};
+
+/// A request to evaluate a policy against, e.g. "can this principal call
+/// `s3:GetObject` on `arn:aws:s3:::bucket/key` from this address".
+#[derive(Debug, Clone)]
+pub struct PolicyRequest {
+    principal: Option<(String, String)>,
+    action: String,
+    resource: String,
+    context: HashMap<String, Vec<String>>,
+}
+
+impl PolicyRequest {
+    /// Create a new anonymous request for an action on a resource
+    pub fn new<A: Into<String>, R: Into<String>>(action: A, resource: R) -> Self {
+        PolicyRequest {
+            principal: None,
+            action: action.into(),
+            resource: resource.into(),
+            context: HashMap::new(),
+        }
+    }
+
+    /// The principal making the request, e.g. `("AWS", "arn:aws:iam::123456789012:user/alice")`.
+    pub fn principal<K: Into<String>, V: Into<String>>(mut self, kind: K, principal: V) -> Self {
+        self.principal = Some((kind.into(), principal.into()));
+        self
+    }
+
+    /// The address the request is sent from, the `aws:SourceIp` condition key.
+    pub fn source_ip(self, ip: IpAddr) -> Self {
+        self.set("aws:SourceIp", ip.to_string())
+    }
+
+    /// Whether the request is sent over https, the `aws:SecureTransport` condition key.
+    pub fn secure_transport(self, secure: bool) -> Self {
+        self.set("aws:SecureTransport", secure.to_string())
+    }
+
+    /// The time of the request, the `aws:CurrentTime` condition key.
+    pub fn current_time(self, time: DateTime<Utc>) -> Self {
+        self.set(
+            "aws:CurrentTime",
+            time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        )
+    }
+
+    /// Add a value for any other condition key, e.g. `aws:Referer` or `s3:prefix`.
+    /// Can be called multiple times for multivalued keys.
+    pub fn context<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        // Condition keys are case-insensitive
+        self.context
+            .entry(key.into().to_ascii_lowercase())
+            .or_default()
+            .push(value.into());
+        self
+    }
+
+    // Replaces the value of a single valued key
+    fn set(mut self, key: &str, value: String) -> Self {
+        self.context.insert(key.to_ascii_lowercase(), vec![value]);
+        self
+    }
+
+    fn values(&self, key: &str) -> Option<&Vec<String>> {
+        self.context.get(&key.to_ascii_lowercase())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Decision {
+    /// A statement allows the request and none denies it.
+    Allow,
+
+    /// A statement denies the request.
+    ExplicitDeny,
+
+    /// No statement applies to the request.
+    ImplicitDeny,
+}
+
+/// The outcome of `Policy::evaluate()`.
+#[derive(Debug, Clone)]
+pub struct PolicyDecision<'p> {
+    pub decision: Decision,
+
+    /// The statement that decided the outcome and its position in the
+    /// policy, `None` for an implicit deny.
+    pub statement: Option<(usize, &'p Statement)>,
+}
+
+impl<'p> PolicyDecision<'p> {
+    pub fn is_allowed(&self) -> bool {
+        self.decision == Decision::Allow
+    }
+}
+
+impl Policy {
+    /// Decide whether the policy allows a request. Supports the string,
+    /// `IpAddress`, `Bool`, date and `Null` condition operators, including
+    /// their `IfExists` variants. Any other operator is reported as an error
+    /// rather than guessed at.
+    pub fn evaluate(&self, request: &PolicyRequest) -> Result<PolicyDecision<'_>, Error> {
+        let mut allowed = None;
+
+        for (index, statement) in self.statement.iter().enumerate() {
+            if !applies(statement, request)? {
+                continue;
+            }
+
+            match statement.effect {
+                Effect::Deny => {
+                    return Ok(PolicyDecision {
+                        decision: Decision::ExplicitDeny,
+                        statement: Some((index, statement)),
+                    })
+                }
+                Effect::Allow => {
+                    allowed = allowed.or(Some((index, statement)));
+                }
+            }
+        }
+
+        Ok(PolicyDecision {
+            decision: if allowed.is_some() {
+                Decision::Allow
+            } else {
+                Decision::ImplicitDeny
+            },
+            statement: allowed,
+        })
+    }
+}
+
+fn applies(statement: &Statement, request: &PolicyRequest) -> Result<bool, Error> {
+    let principal = match (&statement.principal, &statement.not_principal) {
+        (Some(principal), _) => principal_matches(principal, request),
+        (None, Some(principal)) => !principal_matches(principal, request),
+        (None, None) => false,
+    };
+
+    // Action names are case-insensitive, resources are not
+    let action = any_matches(
+        &statement.action,
+        &statement.not_action,
+        &request.action,
+        true,
+    );
+    let resource = any_matches(
+        &statement.resource,
+        &statement.not_resource,
+        &request.resource,
+        false,
+    );
+
+    if !(principal && action && resource) {
+        return Ok(false);
+    }
+
+    match statement.condition {
+        Some(ref condition) => conditions_match(condition, request),
+        None => Ok(true),
+    }
+}
+
+fn any_matches(
+    patterns: &Option<OneOrMany<String>>,
+    not_patterns: &Option<OneOrMany<String>>,
+    value: &str,
+    ignore_case: bool,
+) -> bool {
+    match (patterns, not_patterns) {
+        (Some(patterns), _) => patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern, value, ignore_case)),
+        (None, Some(patterns)) => !patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern, value, ignore_case)),
+        (None, None) => false,
+    }
+}
+
+fn principal_matches(principal: &Principal, request: &PolicyRequest) -> bool {
+    let principals = match principal {
+        Principal::Any => return true,
+        Principal::Map(principals) => principals,
+    };
+
+    principals.iter().any(|(kind, values)| {
+        values.iter().any(|value| {
+            if kind == "AWS" && value == "*" {
+                return true;
+            }
+
+            match request.principal {
+                Some((ref request_kind, ref id)) if request_kind == kind => {
+                    value == id || (kind == "AWS" && account_matches(value, id))
+                }
+                _ => false,
+            }
+        })
+    })
+}
+
+// An account id, or the root user of an account, stands for every
+// principal of that account.
+fn account_matches(value: &str, id: &str) -> bool {
+    let account = value
+        .strip_prefix("arn:aws:iam::")
+        .and_then(|rest| rest.strip_suffix(":root"))
+        .unwrap_or(value);
+
+    account.len() == 12
+        && account.bytes().all(|b| b.is_ascii_digit())
+        && id.split(':').nth(4) == Some(account)
+}
+
+fn conditions_match(
+    condition: &PolicyMap<PolicyMap<OneOrMany<ConditionValue>>>,
+    request: &PolicyRequest,
+) -> Result<bool, Error> {
+    for (operator, keys) in condition.iter() {
+        let (operator, if_exists) = match operator.strip_suffix("IfExists") {
+            Some(operator) => (operator, true),
+            None => (operator, false),
+        };
+
+        for (key, expected) in keys.iter() {
+            let expected = expected
+                .iter()
+                .map(ConditionValue::to_string_value)
+                .collect::<Vec<_>>();
+
+            // Checked up front, so unsupported operators are reported even
+            // when the request doesn't have the key
+            let negated = match OPERATORS.iter().find(|(name, _)| *name == operator) {
+                Some(&(_, negated)) => negated,
+                None => return Err(unsupported(operator)),
+            };
+
+            let matched = match (operator, request.values(key)) {
+                ("Null", actual) => null_matches(&expected, actual.is_none())?,
+                (_, Some(actual)) => operator_matches(operator, &expected, actual)?,
+                // A missing key only satisfies negated operators
+                (_, None) => if_exists || negated,
+            };
+
+            if !matched {
+                return Ok(false);
+            }
+        }
+    }
+
+    Ok(true)
+}
+
+// The supported condition operators, and whether they are negated
+const OPERATORS: &[(&str, bool)] = &[
+    ("StringEquals", false),
+    ("StringNotEquals", true),
+    ("StringEqualsIgnoreCase", false),
+    ("StringNotEqualsIgnoreCase", true),
+    ("StringLike", false),
+    ("StringNotLike", true),
+    ("IpAddress", false),
+    ("NotIpAddress", true),
+    ("Bool", false),
+    ("DateEquals", false),
+    ("DateNotEquals", true),
+    ("DateLessThan", false),
+    ("DateLessThanEquals", false),
+    ("DateGreaterThan", false),
+    ("DateGreaterThanEquals", false),
+    ("Null", false),
+];
+
+fn unsupported(operator: &str) -> Error {
+    Error::InvalidPolicy(format!(
+        "condition operator {:?} is not supported",
+        operator
+    ))
+}
+
+fn operator_matches(operator: &str, expected: &[String], actual: &[String]) -> Result<bool, Error> {
+    // Any value of the request matching any of the expected values is a
+    // match, and negated operators match when none of them does
+    let any = |test: &dyn Fn(&str, &str) -> Result<bool, Error>| -> Result<bool, Error> {
+        for value in actual {
+            let mut matched = false;
+            for pattern in expected {
+                if test(pattern, value)? {
+                    matched = true;
+                    break;
+                }
+            }
+            if matched {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    };
+
+    match operator {
+        "StringEquals" => any(&|pattern, value| Ok(pattern == value)),
+        "StringNotEquals" => any(&|pattern, value| Ok(pattern == value)).map(|m| !m),
+        "StringEqualsIgnoreCase" => any(&|pattern, value| Ok(pattern.eq_ignore_ascii_case(value))),
+        "StringNotEqualsIgnoreCase" => {
+            any(&|pattern, value| Ok(pattern.eq_ignore_ascii_case(value))).map(|m| !m)
+        }
+        "StringLike" => any(&|pattern, value| Ok(wildcard_match(pattern, value, false))),
+        "StringNotLike" => {
+            any(&|pattern, value| Ok(wildcard_match(pattern, value, false))).map(|m| !m)
+        }
+        "IpAddress" => any(&ip_matches),
+        "NotIpAddress" => any(&ip_matches).map(|m| !m),
+        "Bool" => any(&|pattern, value| Ok(pattern.eq_ignore_ascii_case(value))),
+        "DateEquals" => any(&|pattern, value| Ok(parse_date(value)? == parse_date(pattern)?)),
+        "DateNotEquals" => {
+            any(&|pattern, value| Ok(parse_date(value)? == parse_date(pattern)?)).map(|m| !m)
+        }
+        "DateLessThan" => any(&|pattern, value| Ok(parse_date(value)? < parse_date(pattern)?)),
+        "DateLessThanEquals" => {
+            any(&|pattern, value| Ok(parse_date(value)? <= parse_date(pattern)?))
+        }
+        "DateGreaterThan" => any(&|pattern, value| Ok(parse_date(value)? > parse_date(pattern)?)),
+        "DateGreaterThanEquals" => {
+            any(&|pattern, value| Ok(parse_date(value)? >= parse_date(pattern)?))
+        }
+        _ => Err(unsupported(operator)),
+    }
+}
+
+// `Null` checks whether a key is missing from the request rather than its
+// values, `true` requires it to be missing and `false` to be present.
+fn null_matches(expected: &[String], missing: bool) -> Result<bool, Error> {
+    for value in expected {
+        let null = match value.to_ascii_lowercase().as_str() {
+            "true" => true,
+            "false" => false,
+            _ => {
+                return Err(Error::InvalidPolicy(format!(
+                    "{:?} is not a valid value for Null, expected true or false",
+                    value
+                )))
+            }
+        };
+
+        if null == missing {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+fn ip_matches(cidr: &str, ip: &str) -> Result<bool, Error> {
+    let invalid = || Error::InvalidPolicy(format!("{:?} is not a valid ip address or range", cidr));
+
+    let (network, length) = match cidr.find('/') {
+        Some(index) => (&cidr[..index], Some(&cidr[index + 1..])),
+        None => (cidr, None),
+    };
+
+    let network: IpAddr = network.parse().map_err(|_| invalid())?;
+    let ip: IpAddr = ip
+        .parse()
+        .map_err(|_| Error::InvalidPolicy(format!("{:?} is not a valid ip address", ip)))?;
+
+    let (network, ip, bits) = match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => (
+            u128::from(u32::from(network)),
+            u128::from(u32::from(ip)),
+            32,
+        ),
+        (IpAddr::V6(network), IpAddr::V6(ip)) => (u128::from(network), u128::from(ip), 128),
+        _ => return Ok(false),
+    };
+
+    let length = match length {
+        Some(length) => length.parse::<u32>().map_err(|_| invalid())?,
+        None => bits,
+    };
+    if length > bits {
+        return Err(invalid());
+    }
+
+    let shift = bits - length;
+    Ok(length == 0 || (network >> shift) == (ip >> shift))
+}
+
+// Dates are given in ISO 8601 or as seconds since the epoch
+fn parse_date(date: &str) -> Result<DateTime<Utc>, Error> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Ok(date.with_timezone(&Utc));
+    }
+
+    date.parse::<i64>()
+        .map(|seconds| Utc.timestamp(seconds, 0))
+        .map_err(|_| Error::InvalidPolicy(format!("{:?} is not a valid date", date)))
+}
+
+/// Matches `value` against a pattern where `*` matches any run of
+/// characters and `?` any single character.
+fn wildcard_match(pattern: &str, value: &str, ignore_case: bool) -> bool {
+    let (pattern, value) = if ignore_case {
+        (pattern.to_ascii_lowercase(), value.to_ascii_lowercase())
+    } else {
+        (pattern.to_owned(), value.to_owned())
+    };
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+
+    // Backtracks to the last `*` on a mismatch
+    let (mut p, mut v) = (0, 0);
+    let mut star = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, v));
+            p += 1;
+        } else if let Some((star_p, star_v)) = star {
+            p = star_p + 1;
+            v = star_v + 1;
+            star = Some((star_p, star_v + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> Policy {
+        serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [
+                    {
+                        "Sid": "OfficeRead",
+                        "Effect": "Allow",
+                        "Principal": {"AWS": "123456789012"},
+                        "Action": "s3:Get*",
+                        "Resource": "arn:aws:s3:::bucket/*",
+                        "Condition": {"IpAddress": {"aws:SourceIp": ["10.0.0.0/8", "2001:db8::/32"]}}
+                    },
+                    {
+                        "Sid": "PublicAssets",
+                        "Effect": "Allow",
+                        "Principal": "*",
+                        "Action": "s3:GetObject",
+                        "Resource": "arn:aws:s3:::bucket/assets/*",
+                        "Condition": {
+                            "StringLike": {"aws:Referer": "https://*.example.com/*"},
+                            "DateLessThan": {"aws:CurrentTime": "2030-01-01T00:00:00Z"}
+                        }
+                    },
+                    {
+                        "Sid": "TlsOnly",
+                        "Effect": "Deny",
+                        "Principal": "*",
+                        "Action": "s3:*",
+                        "Resource": ["arn:aws:s3:::bucket", "arn:aws:s3:::bucket/*"],
+                        "Condition": {"Bool": {"aws:SecureTransport": "false"}}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn explicit_deny_wins() {
+        let policy = policy();
+        let alice = PolicyRequest::new("s3:GetObject", "arn:aws:s3:::bucket/report.csv")
+            .principal("AWS", "arn:aws:iam::123456789012:user/alice")
+            .source_ip("10.1.2.3".parse().unwrap());
+
+        let decision = policy
+            .evaluate(&alice.clone().secure_transport(true))
+            .unwrap();
+        assert_eq!(decision.decision, Decision::Allow);
+        assert_eq!(
+            decision.statement.unwrap().1.sid.as_deref(),
+            Some("OfficeRead")
+        );
+
+        let decision = policy
+            .evaluate(&alice.clone().secure_transport(false))
+            .unwrap();
+        assert_eq!(decision.decision, Decision::ExplicitDeny);
+        assert_eq!(decision.statement.unwrap().0, 2);
+
+        let outside = alice
+            .source_ip("192.168.0.1".parse().unwrap())
+            .secure_transport(true);
+        assert_eq!(
+            policy.evaluate(&outside).unwrap().decision,
+            Decision::ImplicitDeny
+        );
+    }
+
+    #[test]
+    fn conditions_and_wildcards() {
+        let policy = policy();
+        let request = |referer: &str, time: DateTime<Utc>| {
+            PolicyRequest::new("S3:GetObject", "arn:aws:s3:::bucket/assets/logo.png")
+                .context("aws:Referer", referer)
+                .current_time(time)
+                .secure_transport(true)
+        };
+
+        let now = Utc.ymd(2025, 6, 1).and_hms(0, 0, 0);
+        let later = Utc.ymd(2031, 1, 1).and_hms(0, 0, 0);
+
+        assert!(policy
+            .evaluate(&request("https://www.example.com/page", now))
+            .unwrap()
+            .is_allowed());
+        assert!(!policy
+            .evaluate(&request("https://www.example.org/page", now))
+            .unwrap()
+            .is_allowed());
+        assert!(!policy
+            .evaluate(&request("https://www.example.com/page", later))
+            .unwrap()
+            .is_allowed());
+
+        assert!(wildcard_match(
+            "arn:aws:s3:::bucket/*/2020-??-*",
+            "arn:aws:s3:::bucket/logs/2020-01-31.gz",
+            false
+        ));
+        assert!(!wildcard_match(
+            "arn:aws:s3:::bucket/*",
+            "arn:aws:s3:::other/key",
+            false
+        ));
+        assert!(ip_matches("2001:db8::/32", "2001:db8:1::1").unwrap());
+        assert!(ip_matches("10.0.0.0/8", "11.0.0.1").map(|m| !m).unwrap());
+    }
+
+    #[test]
+    fn null_and_missing_keys() {
+        let policy: Policy = serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [
+                    {
+                        "Effect": "Allow",
+                        "Principal": "*",
+                        "Action": "s3:GetObject",
+                        "Resource": "arn:aws:s3:::bucket/*",
+                        "Condition": {"Null": {"aws:Referer": "false"}}
+                    },
+                    {
+                        "Effect": "Deny",
+                        "Principal": "*",
+                        "Action": "s3:GetObject",
+                        "Resource": "arn:aws:s3:::bucket/*",
+                        "Condition": {"DateNotEquals": {"aws:EpochTime": "2020-01-01T00:00:00Z"}}
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let request = PolicyRequest::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+
+        // The deny applies to requests without the key, as for `StringNot*`
+        assert_eq!(
+            policy.evaluate(&request).unwrap().decision,
+            Decision::ExplicitDeny
+        );
+
+        let request = request.context("aws:EpochTime", "2020-01-01T00:00:00Z");
+        assert_eq!(
+            policy.evaluate(&request).unwrap().decision,
+            Decision::ImplicitDeny
+        );
+
+        let request = request.context("aws:Referer", "https://example.com");
+        assert!(policy.evaluate(&request).unwrap().is_allowed());
+
+        assert!(null_matches(&["TRUE".to_owned()], true).unwrap());
+        assert!(!null_matches(&["true".to_owned()], false).unwrap());
+        assert!(null_matches(&["maybe".to_owned()], false).is_err());
+    }
+
+    #[test]
+    fn unsupported_operators_fail_without_the_key() {
+        let policy: Policy = serde_json::from_str(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [{
+                    "Effect": "Deny",
+                    "Principal": "*",
+                    "Action": "s3:GetObject",
+                    "Resource": "arn:aws:s3:::bucket/*",
+                    "Condition": {"ArnNotEquals": {"aws:SourceArn": "arn:aws:sns:*:*:topic"}}
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let request = PolicyRequest::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+        assert!(policy.evaluate(&request).is_err());
+        assert!(policy
+            .evaluate(&request.context("aws:SourceArn", "arn:aws:sns:us-east-1:1:topic"))
+            .is_err());
+    }
+}
@@ -22,20 +22,42 @@ impl QueryParameter {
     pub const REPLICATION: &'static str = "replication";
     pub const TAGGING: &'static str = "tagging";
     pub const WEBSITE: &'static str = "website";
-    pub const VERSION_ID: &'static str = "VersionId";
+    pub const VERSION_ID: &'static str = "versionId";
     pub const PUBLIC_ACCESS_BLOCK: &'static str = "publicAccessBlock";
     pub const ACCELERATE: &'static str = "accelerate";
     pub const ACL: &'static str = "acl";
     pub const ANALYTICS: &'static str = "analytics";
+    pub const CONTINUATION_TOKEN: &'static str = "continuation-token";
     pub const CORS: &'static str = "cors";
+    pub const DELETE: &'static str = "delete";
+    pub const DELIMITER: &'static str = "delimiter";
+    pub const LIST_TYPE: &'static str = "list-type";
+    pub const LEGAL_HOLD: &'static str = "legal-hold";
     pub const LIFECYCLE: &'static str = "lifecycle";
     pub const LOCATION: &'static str = "location";
     pub const LOGGING: &'static str = "logging";
     pub const ID: &'static str = "id";
+    pub const MAX_KEYS: &'static str = "max-keys";
+    pub const OBJECT_LOCK: &'static str = "object-lock";
     pub const NOTIFICATION: &'static str = "notification";
     pub const POLICY_STATUS: &'static str = "policyStatus";
+    pub const RETENTION: &'static str = "retention";
     pub const REQUEST_PAYMENT: &'static str = "requestPayment";
     pub const VERSIONING: &'static str = "versioning";
+    pub const VERSIONS: &'static str = "versions";
+    pub const KEY_MARKER: &'static str = "key-marker";
+    pub const VERSION_ID_MARKER: &'static str = "version-id-marker";
+    pub const PREFIX: &'static str = "prefix";
+    pub const START_AFTER: &'static str = "start-after";
+    pub const PART_NUMBER: &'static str = "partNumber";
+    pub const UPLOAD_ID: &'static str = "uploadId";
+    pub const UPLOADS: &'static str = "uploads";
+    pub const RESPONSE_CACHE_CONTROL: &'static str = "response-cache-control";
+    pub const RESPONSE_CONTENT_DISPOSITION: &'static str = "response-content-disposition";
+    pub const RESPONSE_CONTENT_ENCODING: &'static str = "response-content-encoding";
+    pub const RESPONSE_CONTENT_LANGUAGE: &'static str = "response-content-language";
+    pub const RESPONSE_CONTENT_TYPE: &'static str = "response-content-type";
+    pub const RESPONSE_EXPIRES: &'static str = "response-expires";
 }
 
 pub trait QueryParam {
@@ -1,63 +1,231 @@
 use serde::{
     Deserialize,
+    Deserializer,
     Serialize,
+    Serializer,
 };
+use std::{
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+};
+
+macro_rules! regions {
+    ($($variant: ident = $name: literal in $partition: ident),* $(,)?) => {
+        /// An AWS region. Every region of the `aws`, `aws-cn` and `aws-us-gov`
+        /// partitions is known, while `Custom` holds any other name, such as
+        /// a region launched after this release or one of an S3 compatible
+        /// server. Names are compared ignoring case, so a `Custom` region
+        /// with the name of a known region is equal to it.
+        #[derive(Debug, Clone)]
+        pub enum Region {
+            $($variant,)*
+            Custom(String),
+        }
+
+        impl Region {
+            /// The name of the region, e.g. `eu-north-1`.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Region::$variant => $name,)*
+                    Region::Custom(name) => name,
+                }
+            }
+
+            /// The partition the region belongs to. A custom region is
+            /// assumed to follow the naming of the partitions, and to be in
+            /// `aws` otherwise.
+            pub fn partition(&self) -> Partition {
+                match self {
+                    $(Region::$variant => Partition::$partition,)*
+                    Region::Custom(name) if name.starts_with("cn-") => Partition::AwsCn,
+                    Region::Custom(name) if name.starts_with("us-gov-") => Partition::AwsUsGov,
+                    Region::Custom(_) => Partition::Aws,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for Region {
+            /// Parse a region name, matching known regions ignoring case.
+            /// `EU`, the location constraint of buckets created in eu-west-1
+            /// before it was given its current name, is read as `EuWest1`.
+            /// Any other name is kept as given, as it is signed as is.
+            fn from(value: &'a str) -> Region {
+                match normalize(value).as_str() {
+                    $($name => Region::$variant,)*
+                    _ => Region::Custom(value.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+regions! {
+    AfSouth1 = "af-south-1" in Aws,
+    ApEast1 = "ap-east-1" in Aws,
+    ApNorthEast1 = "ap-northeast-1" in Aws,
+    ApNorthEast2 = "ap-northeast-2" in Aws,
+    ApNorthEast3 = "ap-northeast-3" in Aws,
+    ApSouth1 = "ap-south-1" in Aws,
+    ApSouth2 = "ap-south-2" in Aws,
+    ApSouthEast1 = "ap-southeast-1" in Aws,
+    ApSouthEast2 = "ap-southeast-2" in Aws,
+    ApSouthEast3 = "ap-southeast-3" in Aws,
+    ApSouthEast4 = "ap-southeast-4" in Aws,
+    ApSouthEast5 = "ap-southeast-5" in Aws,
+    ApSouthEast7 = "ap-southeast-7" in Aws,
+    CaCentral1 = "ca-central-1" in Aws,
+    CaWest1 = "ca-west-1" in Aws,
+    EuCentral1 = "eu-central-1" in Aws,
+    EuCentral2 = "eu-central-2" in Aws,
+    EuNorth1 = "eu-north-1" in Aws,
+    EuSouth1 = "eu-south-1" in Aws,
+    EuSouth2 = "eu-south-2" in Aws,
+    EuWest1 = "eu-west-1" in Aws,
+    EuWest2 = "eu-west-2" in Aws,
+    EuWest3 = "eu-west-3" in Aws,
+    IlCentral1 = "il-central-1" in Aws,
+    MeCentral1 = "me-central-1" in Aws,
+    MeSouth1 = "me-south-1" in Aws,
+    MxCentral1 = "mx-central-1" in Aws,
+    SaEast1 = "sa-east-1" in Aws,
+    UsEast1 = "us-east-1" in Aws,
+    UsEast2 = "us-east-2" in Aws,
+    UsWest1 = "us-west-1" in Aws,
+    UsWest2 = "us-west-2" in Aws,
+    CnNorth1 = "cn-north-1" in AwsCn,
+    CnNorthWest1 = "cn-northwest-1" in AwsCn,
+    UsGovEast1 = "us-gov-east-1" in AwsUsGov,
+    UsGovWest1 = "us-gov-west-1" in AwsUsGov,
+}
+
+fn normalize(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    if name == "eu" {
+        "eu-west-1".to_owned()
+    } else {
+        name
+    }
+}
+
+impl From<String> for Region {
+    fn from(value: String) -> Region {
+        match Region::from(value.as_str()) {
+            Region::Custom(_) => Region::Custom(value),
+            region => region,
+        }
+    }
+}
+
+impl PartialEq for Region {
+    fn eq(&self, other: &Region) -> bool {
+        match (self, other) {
+            (Region::Custom(_), _) | (_, Region::Custom(_)) => {
+                normalize(self.as_str()) == normalize(other.as_str())
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Region {}
+
+impl Hash for Region {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        normalize(self.as_str()).hash(state)
+    }
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> String {
+        match region {
+            Region::Custom(name) => name,
+            region => region.as_str().to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for Region {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Region {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Region::from)
+    }
+}
+
+/// A group of regions sharing the same accounts and credentials. Buckets and
+/// credentials of one partition can't be used in another.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Partition {
+    Aws,
+    AwsCn,
+    AwsUsGov,
+}
 
-#[derive(Debug, Copy, Clone, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "kebab-case")]
-/// All the possible AWS regions
-pub enum Region {
-    #[serde(rename = "EU")]
-    Eu,
-    EuWest1,
-    UsEast1,
-    UsWest1,
-    UsWest2,
-    ApSouth1,
-    ApSouthEast1,
-    ApSouthEast2,
-    ApNorthEast1,
-    SaEast1,
-    CnNorth1,
-    EuCentral1,
-}
-
-impl Into<String> for Region {
-    fn into(self) -> String {
+impl Partition {
+    pub fn as_str(self) -> &'static str {
         match self {
-            Region::Eu => "EU",
-            Region::EuWest1 => "eu-west-1",
-            Region::UsEast1 => "us-east-1",
-            Region::UsWest1 => "us-west-1",
-            Region::UsWest2 => "us-west-2",
-            Region::ApSouth1 => "ap-south-1",
-            Region::ApSouthEast1 => "ap-southeast-1",
-            Region::ApSouthEast2 => "ap-southeast-2",
-            Region::ApNorthEast1 => "ap-northeast-1",
-            Region::SaEast1 => "sa-east-1",
-            Region::CnNorth1 => "cn-north-1",
-            Region::EuCentral1 => "eu-central-1",
-        }
-        .to_owned()
-    }
-}
-
-impl<'a> From<&'a str> for Region {
-    fn from(value: &'a str) -> Region {
-        match value {
-            "EU" => Region::Eu,
-            "eu-west-1" => Region::EuWest1,
-            "us-east-1" => Region::UsEast1,
-            "us-west-1" => Region::UsWest1,
-            "us-west-2" => Region::UsWest2,
-            "ap-south-1" => Region::ApSouth1,
-            "ap-southeast-1" => Region::ApSouthEast1,
-            "ap-southeast-2" => Region::ApSouthEast2,
-            "ap-northeast-1" => Region::ApNorthEast1,
-            "sa-east-1" => Region::SaEast1,
-            "cn-north-1" => Region::CnNorth1,
-            "eu-central-1" => Region::EuCentral1,
-            _ => Region::UsEast1,
+            Partition::Aws => "aws",
+            Partition::AwsCn => "aws-cn",
+            Partition::AwsUsGov => "aws-us-gov",
         }
     }
+
+    /// The domain the S3 endpoints of the partition are under.
+    pub fn domain(self) -> &'static str {
+        match self {
+            Partition::Aws | Partition::AwsUsGov => "amazonaws.com",
+            Partition::AwsCn => "amazonaws.com.cn",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_regions_are_kept() {
+        assert_eq!(Region::from("eu-north-1"), Region::EuNorth1);
+        assert_eq!(
+            Region::from("us-gov-west-1").partition(),
+            Partition::AwsUsGov
+        );
+
+        let custom = Region::from("xx-east-1".to_owned());
+        assert_eq!(custom, Region::Custom("xx-east-1".to_owned()));
+        assert_eq!(custom.to_string(), "xx-east-1");
+        assert_eq!(Region::from("cn-east-9").partition(), Partition::AwsCn);
+        assert_eq!(String::from(Region::ApSouthEast2), "ap-southeast-2");
+    }
+
+    #[test]
+    fn names_are_normalized() {
+        assert_eq!(Region::from("EU-WEST-1"), Region::EuWest1);
+        assert_eq!(Region::from("EU"), Region::EuWest1);
+        assert_eq!(Region::from("MyRegion").as_str(), "MyRegion");
+        assert_eq!(Region::from("MyRegion".to_owned()).as_str(), "MyRegion");
+        assert_eq!(Region::from("MyRegion"), Region::from("myregion"));
+
+        assert_eq!(Region::Custom("us-east-1".to_owned()), Region::UsEast1);
+        assert_eq!(Region::Custom("EU".to_owned()), Region::EuWest1);
+        assert_ne!(Region::Custom("xx-east-1".to_owned()), Region::UsEast1);
+        assert_ne!(Region::UsEast1, Region::UsEast2);
+
+        let mut regions = std::collections::HashSet::new();
+        regions.insert(Region::UsEast1);
+        assert!(regions.contains(&Region::Custom("US-EAST-1".to_owned())));
+    }
 }
@@ -0,0 +1,483 @@
+use crate::{
+    aws_response::error_in_body,
+    encryption::EncryptionHeaders,
+    error,
+    request::put_object::ContentHeaders,
+    types::{
+        CopyObjectResult,
+        MAX_OBJECT_TAGS,
+    },
+    Acl,
+    AwsRequest,
+    AwsResponse,
+    CacheControl,
+    Error,
+    Gmt,
+    Grantee,
+    Headers,
+    Metadata,
+    ObjectEncryption,
+    Permission,
+    Region,
+    Request,
+    ServerSideEncryption,
+    SigningKey,
+    SseCustomerKey,
+    StorageClass,
+    TagSet,
+    UriEncode,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+/// Specifies whether the metadata is copied from the source object or replaced
+/// with metadata provided in the request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MetadataDirective {
+    Copy,
+    Replace,
+}
+
+impl From<MetadataDirective> for &'static str {
+    fn from(directive: MetadataDirective) -> &'static str {
+        match directive {
+            MetadataDirective::Copy => "COPY",
+            MetadataDirective::Replace => "REPLACE",
+        }
+    }
+}
+
+/// Specifies whether the object tag-set are copied from the source object or
+/// replaced with tag-set provided in the request.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TaggingDirective {
+    Copy,
+    Replace,
+}
+
+impl From<TaggingDirective> for &'static str {
+    fn from(directive: TaggingDirective) -> &'static str {
+        match directive {
+            TaggingDirective::Copy => "COPY",
+            TaggingDirective::Replace => "REPLACE",
+        }
+    }
+}
+
+/// The source object of a copy along with the conditions under which the
+/// copy is allowed to happen. Shared by `CopyObject` and `UploadPartCopy`.
+pub(crate) struct CopySource<'a> {
+    pub(crate) bucket: &'a str,
+    pub(crate) key: &'a str,
+    pub(crate) version_id: Option<&'a str>,
+    pub(crate) if_match: Option<&'a str>,
+    pub(crate) if_modified_since: Option<DateTime<Utc>>,
+    pub(crate) if_none_match: Option<&'a str>,
+    pub(crate) if_unmodified_since: Option<DateTime<Utc>>,
+    pub(crate) sse_customer_key: Option<&'a SseCustomerKey>,
+}
+
+impl<'a> CopySource<'a> {
+    pub(crate) fn new(bucket: &'a str, key: &'a str) -> Self {
+        CopySource {
+            bucket,
+            key,
+            version_id: None,
+            if_match: None,
+            if_modified_since: None,
+            if_none_match: None,
+            if_unmodified_since: None,
+            sse_customer_key: None,
+        }
+    }
+
+    /// Value of the `x-amz-copy-source` header: `/bucket/key[?versionId=id]`
+    pub(crate) fn header_value(&self) -> String {
+        let source = format!("/{}/{}", self.bucket, self.key.uri_encode_path());
+
+        if let Some(version_id) = self.version_id {
+            format!("{}?versionId={}", source, version_id.uri_encode())
+        } else {
+            source
+        }
+    }
+
+    pub(crate) fn headers(&self, request: Request<'a>) -> Result<Request<'a>, Error> {
+        Ok(request
+            .copy_source_sse_customer_key(self.sse_customer_key)?
+            .header(Headers::X_AMZ_COPY_SOURCE, Some(self.header_value()))
+            .header(
+                Headers::X_AMZ_COPY_SOURCE_IF_MATCH,
+                self.if_match.map(str::to_owned),
+            )
+            .header(
+                Headers::X_AMZ_COPY_SOURCE_IF_MODIFIED_SINCE,
+                self.if_modified_since.map(|since| since.to_gmt()),
+            )
+            .header(
+                Headers::X_AMZ_COPY_SOURCE_IF_NONE_MATCH,
+                self.if_none_match.map(str::to_owned),
+            )
+            .header(
+                Headers::X_AMZ_COPY_SOURCE_IF_UNMODIFIED_SINCE,
+                self.if_unmodified_since.map(|since| since.to_gmt()),
+            ))
+    }
+}
+
+pub struct CopyObject<'a> {
+    /// The name of the destination bucket.
+    pub bucket: &'a str,
+
+    /// The key of the destination object.
+    pub key: &'a str,
+
+    source: CopySource<'a>,
+    content: ContentHeaders<'a>,
+    metadata: Option<Metadata>,
+    metadata_directive: Option<MetadataDirective>,
+    tagging: Option<TagSet>,
+    tagging_directive: Option<TaggingDirective>,
+    storage_class: Option<StorageClass>,
+    grants: Vec<(Permission, Grantee, &'a str)>,
+    acl: Option<Acl>,
+    encryption: EncryptionHeaders<'a>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+}
+
+impl<'a> CopyObject<'a> {
+    /// Create a new CopyObject request which copies `source_bucket/source_key`
+    /// to `bucket/key` without downloading the object.
+    pub fn new(bucket: &'a str, key: &'a str, source_bucket: &'a str, source_key: &'a str) -> Self {
+        CopyObject {
+            bucket,
+            key,
+            source: CopySource::new(source_bucket, source_key),
+            content: ContentHeaders::default(),
+            metadata: None,
+            metadata_directive: None,
+            tagging: None,
+            tagging_directive: None,
+            storage_class: None,
+            grants: Vec::new(),
+            acl: None,
+            encryption: EncryptionHeaders::default(),
+            sse_customer_key: None,
+        }
+    }
+
+    /// Copy a specific version of the source object.
+    pub fn source_version_id(mut self, version_id: &'a str) -> Self {
+        self.source.version_id = Some(version_id);
+        self
+    }
+
+    /// The customer-provided key the source object was encrypted with (SSE-C).
+    pub fn copy_source_sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.source.sse_customer_key = Some(key);
+        self
+    }
+
+    /// Copies the object only if its entity tag (ETag) matches the specified tag.
+    pub fn copy_source_if_match(mut self, etag: &'a str) -> Self {
+        self.source.if_match = Some(etag);
+        self
+    }
+
+    /// Copies the object only if it has been modified since the specified time.
+    pub fn copy_source_if_modified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.source.if_modified_since = Some(since);
+        self
+    }
+
+    /// Copies the object only if its entity tag (ETag) is different than the specified ETag.
+    pub fn copy_source_if_none_match(mut self, etag: &'a str) -> Self {
+        self.source.if_none_match = Some(etag);
+        self
+    }
+
+    /// Copies the object only if it hasn't been modified since the specified time.
+    pub fn copy_source_if_unmodified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.source.if_unmodified_since = Some(since);
+        self
+    }
+
+    /// The date and time at which the object is no longer cacheable.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.content.expires = Some(expires);
+        self
+    }
+
+    /// Can be used to specify caching behavior along the request/reply chain.
+    /// Can be called multiple times to add several directives.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn cache(mut self, cache: CacheControl<'a>) -> Self {
+        self.content.cache.push(cache);
+        self
+    }
+
+    /// Specifies presentational information for the object.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn content_disposition(mut self, disposition: &'a str) -> Self {
+        self.content.disposition = Some(disposition);
+        self
+    }
+
+    /// Specifies what content encodings have been applied to the object.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn content_encoding(mut self, encoding: &'a str) -> Self {
+        self.content.encoding = Some(encoding);
+        self
+    }
+
+    /// The language the content is in.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn content_language(mut self, language: &'a str) -> Self {
+        self.content.language = Some(language);
+        self
+    }
+
+    /// A standard MIME type describing the format of the object data.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn content_type(mut self, content_type: &'a str) -> Self {
+        self.content.content_type = Some(content_type);
+        self
+    }
+
+    /// Encrypt the destination object with a customer-provided key (SSE-C).
+    /// **Note:** The request is refused unless the client uses https
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+
+    /// User-defined metadata to store with the destination object.
+    /// **Note:** This implies `MetadataDirective::Replace` unless a directive was given
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Specifies whether the metadata is copied from the source object or replaced.
+    pub fn metadata_directive(mut self, directive: MetadataDirective) -> Self {
+        self.metadata_directive = Some(directive);
+        self
+    }
+
+    /// Specifies whether the object tag-set is copied from the source object or replaced.
+    pub fn tagging_directive(mut self, directive: TaggingDirective) -> Self {
+        self.tagging_directive = Some(directive);
+        self
+    }
+
+    /// The tag-set to add to the destination object. An object can have at most 10 tags.
+    /// **Note:** This implies `TaggingDirective::Replace` unless a directive was given
+    pub fn tagging(mut self, tag_set: TagSet) -> Self {
+        self.tagging = Some(tag_set);
+        self
+    }
+
+    /// The storage class of the destination object. Defaults to `STANDARD`.
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    /// The canned ACL to apply to the object.
+    /// **Note:** Granting explicit permission will overwrite this setting
+    pub fn acl(mut self, acl: Acl) -> Self {
+        self.acl = Some(acl);
+        self
+    }
+
+    /// The server-side encryption algorithm used when storing the destination object.
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.encryption.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// The AWS KMS key ID to use for object encryption.
+    /// **Note:** This implies `ServerSideEncryption::AwsKms` unless another KMS algorithm was given
+    pub fn kms_key_id(mut self, key_id: &'a str) -> Self {
+        if self.encryption.server_side_encryption != Some(ServerSideEncryption::AwsKmsDsse) {
+            self.encryption.server_side_encryption = Some(ServerSideEncryption::AwsKms);
+        }
+        self.encryption.kms_key_id = Some(key_id);
+        self
+    }
+
+    /// Adds a key-value pair to the encryption context used with SSE-KMS.
+    pub fn encryption_context(mut self, key: &'a str, value: &'a str) -> Self {
+        self.encryption.context.insert(key, value);
+        self
+    }
+
+    /// Whether to use an S3 Bucket Key with SSE-KMS, reducing the requests made to AWS KMS.
+    pub fn bucket_key_enabled(mut self, enabled: bool) -> Self {
+        self.encryption.bucket_key_enabled = Some(enabled);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct CopyObjectResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub version_id: Option<String>,
+    pub copy_source_version_id: Option<String>,
+    pub encryption: ObjectEncryption,
+}
+
+impl<'a> AwsRequest for CopyObject<'a> {
+    type Response = CopyObjectResponse;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        // Metadata and content headers sent with a copy are ignored by S3
+        // unless it is told to replace them
+        let replace = self.metadata.is_some() || !self.content.is_empty();
+        let metadata_directive = self
+            .metadata_directive
+            .or_else(|| Some(MetadataDirective::Replace).filter(|_| replace))
+            .map(<&'static str>::from);
+        let metadata = self.metadata.unwrap_or_default();
+        let tagging_directive = self
+            .tagging_directive
+            .or(self.tagging.as_ref().map(|_| TaggingDirective::Replace))
+            .map(<&'static str>::from);
+        let tagging = self
+            .tagging
+            .map(|tag_set| {
+                tag_set.check_limit(MAX_OBJECT_TAGS)?;
+                Ok::<_, Error>(tag_set.header_value())
+            })
+            .transpose()?;
+        let storage_class = self.storage_class.map(<&'static str>::from);
+
+        let request = Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .header(Headers::X_AMZ_METADATA_DIRECTIVE, metadata_directive)
+            .header(Headers::X_AMZ_TAGGING, tagging)
+            .header(Headers::X_AMZ_TAGGING_DIRECTIVE, tagging_directive)
+            .header(Headers::X_AMZ_STORAGE_CLASS, storage_class)
+            .grants(self.acl, self.grants)
+            .metadata(&metadata);
+
+        let request = self.content.headers(request);
+        let request = self.encryption.headers(request)?;
+
+        self.source
+            .headers(request)?
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            // A copy can still fail after S3 responded with `200 OK`
+            error_in_body(response.status(), &string)?;
+
+            let result: CopyObjectResult =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(CopyObjectResponse {
+                etag: result.etag,
+                last_modified: result.last_modified,
+                version_id: response.version_id()?,
+                copy_source_version_id: response.copy_source_version_id()?,
+                encryption: response.encryption()?,
+            })
+        })
+    }
+}
+
+macro_rules! grant_method {
+    ($ty: ty, $fn: ident, $permission: ident, $grantee: ident) => {
+        impl<'a> $ty {
+            pub fn $fn(mut self, value: &'a str) -> Self {
+                self.grants
+                    .push((Permission::$permission, Grantee::$grantee, value));
+                self
+            }
+        }
+    };
+}
+
+grant_method!(CopyObject<'a>, grant_read_id, Read, Id);
+grant_method!(CopyObject<'a>, grant_read_acp_id, ReadAcp, Id);
+grant_method!(CopyObject<'a>, grant_write_acp_id, WriteAcp, Id);
+grant_method!(CopyObject<'a>, grant_full_id, FullControl, Id);
+
+grant_method!(CopyObject<'a>, grant_read_email, Read, Email);
+grant_method!(CopyObject<'a>, grant_read_acp_email, ReadAcp, Email);
+grant_method!(CopyObject<'a>, grant_write_acp_email, WriteAcp, Email);
+grant_method!(CopyObject<'a>, grant_full_email, FullControl, Email);
+
+grant_method!(CopyObject<'a>, grant_read_uri, Read, Uri);
+grant_method!(CopyObject<'a>, grant_read_acp_uri, ReadAcp, Uri);
+grant_method!(CopyObject<'a>, grant_write_acp_uri, WriteAcp, Uri);
+grant_method!(CopyObject<'a>, grant_full_uri, FullControl, Uri);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_headers_replace_metadata() {
+        let signing_key = SigningKey::from_date("secret", &Utc::now(), Region::UsEast1);
+        let url = Url::parse("http://localhost").unwrap();
+
+        let request = CopyObject::new("bucket", "copy", "bucket", "key")
+            .content_type("text/plain")
+            .into_request(url.clone(), "access", &signing_key, Region::UsEast1)
+            .unwrap();
+        assert_eq!(
+            request.headers()[Headers::X_AMZ_METADATA_DIRECTIVE],
+            "REPLACE"
+        );
+
+        let request = CopyObject::new("bucket", "copy", "bucket", "key")
+            .into_request(url, "access", &signing_key, Region::UsEast1)
+            .unwrap();
+        assert!(request
+            .headers()
+            .get(Headers::X_AMZ_METADATA_DIRECTIVE)
+            .is_none());
+    }
+
+    #[test]
+    fn copy_source_is_encoded() {
+        let mut source = CopySource::new("bucket", "some dir/file+1.txt");
+        assert_eq!(source.header_value(), "/bucket/some%20dir/file%2B1.txt");
+
+        source.version_id = Some("3/L4kqtJl40Nr8X8gdRQBpUMLUo");
+        assert_eq!(
+            source.header_value(),
+            "/bucket/some%20dir/file%2B1.txt?versionId=3%2FL4kqtJl40Nr8X8gdRQBpUMLUo"
+        );
+    }
+}
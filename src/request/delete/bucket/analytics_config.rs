@@ -0,0 +1 @@
+impl_sub_resource!(DELETE DeleteBucketAnalyticsConfiguration[id => ID] => (), (ANALYTICS => None));
@@ -0,0 +1 @@
+impl_sub_resource!(DELETE DeleteBucketCors => (), (CORS => None));
@@ -0,0 +1 @@
+impl_sub_resource!(DELETE DeleteBucketLifecycle => (), (LIFECYCLE => None));
@@ -1,9 +1,7 @@
-pub mod bucket;
-pub mod object;
-
-pub mod public_access_block;
+mod bucket;
+mod object;
+mod public_access_block;
 
 pub use bucket::*;
 pub use object::*;
-
 pub use public_access_block::*;
@@ -1,21 +1,24 @@
 use crate::{
-    Request,
     AwsRequest,
     AwsResponse,
     Error,
+    Headers,
+    Mfa,
+    QueryParameter,
     Region,
+    Request,
     SigningKey,
 };
 use futures_core::future::BoxFuture;
-use http::method::Method;
 use hyper::{
     Body as HttpBody,
+    Method,
     Request as HttpRequest,
     Response,
 };
 use url::Url;
 
-pub mod tagging;
+mod tagging;
 
 pub use tagging::*;
 
@@ -25,12 +28,34 @@ pub struct DeleteObject<'a> {
 
     /// Key name of the object to delete.
     pub key: &'a str,
+
+    version_id: Option<&'a str>,
+    mfa: Option<&'a Mfa>,
 }
 
 impl<'a> DeleteObject<'a> {
     /// Creates a new DeleteObject request with the given bucket and key
     pub fn new(bucket: &'a str, key: &'a str) -> Self {
-        DeleteObject { bucket, key }
+        DeleteObject {
+            bucket,
+            key,
+            version_id: None,
+            mfa: None,
+        }
+    }
+
+    /// Permanently delete the given version rather than adding a delete
+    /// marker to a versioned bucket.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    /// Authenticate with the given device, which is required to delete a
+    /// version once MFA delete is enabled on the bucket.
+    pub fn mfa(mut self, mfa: &'a Mfa) -> Self {
+        self.mfa = Some(mfa);
+        self
     }
 }
 
@@ -47,9 +72,11 @@ impl<'a> AwsRequest for DeleteObject<'a> {
         Request::new(Method::DELETE)
             .bucket(self.bucket)
             .key(self.key)
-            .host(url.clone())?
+            .host(url)?
             .region(region)
-            .build(&access_key.as_ref(), &signing_key)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .header(Headers::X_AMZ_MFA, self.mfa.map(Mfa::header_value))
+            .build(access_key.as_ref(), signing_key)
     }
 
     fn into_response(
@@ -1,20 +1,76 @@
-impl_sub_resource!(DeleteObjectTagging => ());
+use crate::{
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct DeleteObjectTagging<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to remove the tag-set from.
+    pub key: &'a str,
+
+    version_id: Option<&'a str>,
+}
 
 impl<'a> DeleteObjectTagging<'a> {
     /// Create a new DeleteObjectTagging request with default parameters
     pub fn new(bucket: &'a str, key: &'a str) -> Self {
-        DeleteObjectTagging(SubResource {
+        DeleteObjectTagging {
             bucket,
-            method: Method::DELETE,
-            key: Some(key),
-            params: vec![(QueryParameter::TAGGING, None)],
-        })
+            key,
+            version_id: None,
+        }
     }
 
+    /// The version of the object to remove the tag-set from.
     pub fn version_id(mut self, version_id: &'a str) -> Self {
-        self.0
-            .params
-            .push((QueryParameter::VERSION_ID, Some(version_id)));
+        self.version_id = Some(version_id);
         self
     }
 }
+
+impl<'a> AwsRequest for DeleteObjectTagging<'a> {
+    /// The version of the object the tag-set was removed from
+    type Response = Option<String>;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::DELETE)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::TAGGING, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            response.version_id()
+        })
+    }
+}
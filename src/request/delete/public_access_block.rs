@@ -1,13 +1 @@
-impl_sub_resource!(DeletePublicAccessBlock => ());
-
-impl<'a> DeletePublicAccessBlock<'a> {
-    /// Create a new DeletePublicAccessBlock request with default parameters
-    pub fn new(bucket: &'a str) -> Self {
-        DeletePublicAccessBlock(SubResource {
-            bucket,
-            method: Method::DELETE,
-            key: None,
-            params: vec![(QueryParameter::PUBLIC_ACCESS_BLOCK, None)],
-        })
-    }
-}
+impl_sub_resource!(DELETE DeletePublicAccessBlock => (), (PUBLIC_ACCESS_BLOCK => None));
@@ -0,0 +1,124 @@
+use crate::{
+    error,
+    types::{
+        Delete,
+        DeleteResult,
+        ObjectIdentifier,
+    },
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    Mfa,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+// The most objects a single request may delete
+const MAX_OBJECTS: usize = 1000;
+
+/// Deletes up to 1000 objects, or versions of objects, with a single
+/// request. Objects which couldn't be deleted are listed in the result
+/// rather than failing the request.
+pub struct DeleteObjects<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    delete: Delete,
+    mfa: Option<&'a Mfa>,
+}
+
+impl<'a> DeleteObjects<'a> {
+    /// Create a new DeleteObjects request without any objects.
+    pub fn new(bucket: &'a str) -> Self {
+        DeleteObjects {
+            bucket,
+            delete: Delete::default(),
+            mfa: None,
+        }
+    }
+
+    /// Delete the object with the given key.
+    pub fn key<T: Into<String>>(mut self, key: T) -> Self {
+        self.delete.objects.push(ObjectIdentifier {
+            key: key.into(),
+            version_id: None,
+        });
+        self
+    }
+
+    /// Permanently delete the given version of an object.
+    pub fn version<K: Into<String>, V: Into<String>>(mut self, key: K, version_id: V) -> Self {
+        self.delete.objects.push(ObjectIdentifier {
+            key: key.into(),
+            version_id: Some(version_id.into()),
+        });
+        self
+    }
+
+    /// Only list the objects which couldn't be deleted in the result.
+    pub fn quiet(mut self) -> Self {
+        self.delete.quiet = Some(true);
+        self
+    }
+
+    /// Authenticate with the given device, which is required to delete
+    /// versions once MFA delete is enabled on the bucket.
+    pub fn mfa(mut self, mfa: &'a Mfa) -> Self {
+        self.mfa = Some(mfa);
+        self
+    }
+}
+
+impl<'a> AwsRequest for DeleteObjects<'a> {
+    type Response = DeleteResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let count = self.delete.objects.len();
+        if count == 0 || count > MAX_OBJECTS {
+            return Err(Error::InvalidDelete(format!(
+                "between 1 and {} objects can be deleted at once, found {}",
+                MAX_OBJECTS, count
+            )));
+        }
+
+        let body = xml::to_string(&self.delete).map_err(error::Internal::from)?;
+
+        Request::new(Method::POST)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::DELETE, None)
+            .header(Headers::X_AMZ_MFA, self.mfa.map(Mfa::header_value))
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            Ok(quick_xml::de::from_str(&string).map_err(error::Internal::from)?)
+        })
+    }
+}
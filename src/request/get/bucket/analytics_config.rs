@@ -1,18 +1,77 @@
-use crate::types::BucketAnalytics;
+use crate::{
+    error,
+    types::ListBucketAnalyticsConfigurationResult,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
 
-impl_sub_resource!(GetBucketAnalyticsConfig => BucketAnalytics);
+impl_sub_resource!(GET GetBucketAnalyticsConfiguration[id => ID] => crate::types::AnalyticsConfiguration, (ANALYTICS => None));
 
-impl<'a> GetBucketAnalyticsConfig<'a> {
-    /// Create a new GetBucketAnalyticsConfig request with default parameters
-    pub fn new(bucket: &'a str, id: &'a str) -> Self {
-        GetBucketAnalyticsConfig(SubResource {
+/// Lists the analytics configurations of a bucket, at most 100 per page.
+pub struct ListBucketAnalyticsConfigurations<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    continuation_token: Option<&'a str>,
+}
+
+impl<'a> ListBucketAnalyticsConfigurations<'a> {
+    /// Create a new ListBucketAnalyticsConfigurations request for the first page
+    pub fn new(bucket: &'a str) -> Self {
+        ListBucketAnalyticsConfigurations {
             bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![
-                (QueryParameter::ANALYTICS, None),
-                (QueryParameter::ID, Some(id)),
-            ],
+            continuation_token: None,
+        }
+    }
+
+    /// Request the page following the one that returned this token as
+    /// `next_continuation_token`.
+    pub fn continuation_token(mut self, token: &'a str) -> Self {
+        self.continuation_token = Some(token);
+        self
+    }
+}
+
+impl<'a> AwsRequest for ListBucketAnalyticsConfigurations<'a> {
+    type Response = ListBucketAnalyticsConfigurationResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::ANALYTICS, None)
+            .optional_query(QueryParameter::CONTINUATION_TOKEN, self.continuation_token)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            Ok(quick_xml::de::from_str(&string).map_err(error::Internal::from)?)
         })
     }
 }
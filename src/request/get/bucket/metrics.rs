@@ -1,18 +1,77 @@
-use crate::types::BucketMetrics;
+use crate::{
+    error,
+    types::ListMetricsConfigurationsResult,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
 
-impl_sub_resource!(GetBucketMetrics => BucketMetrics);
+impl_sub_resource!(GET GetBucketMetricsConfiguration[id => ID] => crate::types::MetricsConfiguration, (METRICS => None));
 
-impl<'a> GetBucketMetrics<'a> {
-    /// Create a new GetBucketMetrics request with default parameters
-    pub fn new(bucket: &'a str, id: &'a str) -> Self {
-        GetBucketMetrics(SubResource {
+/// Lists the metrics configurations of a bucket, at most 100 per page.
+pub struct ListBucketMetricsConfigurations<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    continuation_token: Option<&'a str>,
+}
+
+impl<'a> ListBucketMetricsConfigurations<'a> {
+    /// Create a new ListBucketMetricsConfigurations request for the first page
+    pub fn new(bucket: &'a str) -> Self {
+        ListBucketMetricsConfigurations {
             bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![
-                (QueryParameter::METRICS, None),
-                (QueryParameter::ID, Some(id)),
-            ],
+            continuation_token: None,
+        }
+    }
+
+    /// Request the page following the one that returned this token as
+    /// `next_continuation_token`.
+    pub fn continuation_token(mut self, token: &'a str) -> Self {
+        self.continuation_token = Some(token);
+        self
+    }
+}
+
+impl<'a> AwsRequest for ListBucketMetricsConfigurations<'a> {
+    type Response = ListMetricsConfigurationsResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::METRICS, None)
+            .optional_query(QueryParameter::CONTINUATION_TOKEN, self.continuation_token)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            Ok(quick_xml::de::from_str(&string).map_err(error::Internal::from)?)
         })
     }
 }
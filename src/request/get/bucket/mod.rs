@@ -1,37 +1,41 @@
-// pub mod accelerate_config;
+pub mod accelerate_config;
 pub mod acl;
-// pub mod analytics_config;
-// pub mod cors;
+pub mod analytics_config;
+pub mod cors;
 // pub mod encryption;
-// pub mod inventory_config;
-// pub mod lifecycle;
+pub mod inventory_config;
+pub mod lifecycle_config;
 // pub mod location;
-// pub mod logging;
-// pub mod metrics;
-// pub mod notification;
-// pub mod policy;
-// pub mod policy_status;
-// pub mod replication;
-// pub mod request_payment;
-// pub mod tagging;
-// pub mod versioning;
-// pub mod website;
+pub mod logging;
+pub mod metrics;
+pub mod notification;
+pub mod object_lock;
+pub mod policy;
+pub mod policy_status;
+pub mod public_access_block;
+pub mod replication;
+pub mod request_payment;
+pub mod tagging;
+pub mod versioning;
+pub mod website;
 
-// pub use accelerate_config::*;
+pub use accelerate_config::*;
 pub use acl::*;
-// pub use analytics_config::*;
-// pub use cors::*;
+pub use analytics_config::*;
+pub use cors::*;
 // pub use encryption::*;
-// pub use inventory_config::*;
-// pub use lifecycle::*;
+pub use inventory_config::*;
+pub use lifecycle_config::*;
 // pub use location::*;
-// pub use logging::*;
-// pub use metrics::*;
-// pub use notification::*;
-// pub use policy::*;
-// pub use policy_status::*;
-// pub use replication::*;
-// pub use request_payment::*;
-// pub use tagging::*;
-// pub use versioning::*;
-// pub use website::*;
+pub use logging::*;
+pub use metrics::*;
+pub use notification::*;
+pub use object_lock::*;
+pub use policy::*;
+pub use policy_status::*;
+pub use public_access_block::*;
+pub use replication::*;
+pub use request_payment::*;
+pub use tagging::*;
+pub use versioning::*;
+pub use website::*;
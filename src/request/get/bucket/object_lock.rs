@@ -0,0 +1 @@
+impl_sub_resource!(GET GetObjectLockConfiguration => crate::types::ObjectLockConfiguration, (OBJECT_LOCK => None));
@@ -1,36 +1,37 @@
 use crate::{
-    request::sub_resource::SubResource,
+    error,
+    types::Policy,
     AwsRequest,
+    AwsResponse,
     Error,
     QueryParameter,
     Region,
+    Request,
     SigningKey,
 };
 use futures_core::future::BoxFuture;
-use http::method::Method;
 use hyper::{
     Body as HttpBody,
-    Request,
+    Method,
+    Request as HttpRequest,
     Response,
 };
 use url::Url;
 
-pub struct GetBucketPolicy<'a>(SubResource<'a>);
+pub struct GetBucketPolicy<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+}
 
 impl<'a> GetBucketPolicy<'a> {
     /// Create a new GetBucketPolicy request with default parameters
     pub fn new(bucket: &'a str) -> Self {
-        GetBucketPolicy(SubResource {
-            bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![(QueryParameter::POLICY, None)],
-        })
+        GetBucketPolicy { bucket }
     }
 }
 
 impl<'a> AwsRequest for GetBucketPolicy<'a> {
-    type Response = String;
+    type Response = Policy;
 
     fn into_request<AR: AsRef<str>>(
         self,
@@ -38,18 +39,25 @@ impl<'a> AwsRequest for GetBucketPolicy<'a> {
         access_key: AR,
         signing_key: &SigningKey,
         region: Region,
-    ) -> Result<Request<HttpBody>, Error> {
-        self.0.into_request(url, access_key, signing_key, region)
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::POLICY, None)
+            .build(access_key.as_ref(), signing_key)
     }
 
     fn into_response(
-        response: Response<HttpBody>,
+        mut response: Response<HttpBody>,
     ) -> BoxFuture<'static, Result<Self::Response, Error>> {
         Box::pin(async move {
-            let bytes = SubResource::<'a>::into_response(response).await?;
-            let string = String::from_utf8_lossy(&bytes).into_owned();
+            let bytes = response.error().await?;
+
+            // Unlike every other sub-resource the policy is a json document
+            let policy: Policy = serde_json::from_slice(&bytes).map_err(error::Internal::from)?;
 
-            Ok(string)
+            Ok(policy)
         })
     }
 }
@@ -1,15 +1 @@
-use crate::types::PolicyStatus;
-
-impl_sub_resource!(GetBucketPolicyStatus => PolicyStatus);
-
-impl<'a> GetBucketPolicyStatus<'a> {
-    /// Create a new GetBucketLogging request with default parameters
-    pub fn new(bucket: &'a str) -> Self {
-        GetBucketPolicyStatus(SubResource {
-            bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![(QueryParameter::POLICY_STATUS, None)],
-        })
-    }
-}
+impl_sub_resource!(GET GetBucketPolicyStatus => crate::types::PolicyStatus, (POLICY_STATUS => None));
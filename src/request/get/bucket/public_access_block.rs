@@ -0,0 +1 @@
+impl_sub_resource!(GET GetPublicAccessBlock => crate::types::PublicAccessBlockConfiguration, (PUBLIC_ACCESS_BLOCK => None));
@@ -1,15 +1 @@
-use crate::types::RequestPayment;
-
-impl_sub_resource!(GetBucketRequestPayment => RequestPayment);
-
-impl<'a> GetBucketRequestPayment<'a> {
-    /// Create a new GetBucketLogging request with default parameters
-    pub fn new(bucket: &'a str) -> Self {
-        GetBucketRequestPayment(SubResource {
-            bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![(QueryParameter::REQUEST_PAYMENT, None)],
-        })
-    }
-}
+impl_sub_resource!(GET GetBucketRequestPayment => crate::types::RequestPaymentConfiguration, (REQUEST_PAYMENT => None));
@@ -1,15 +1,80 @@
-use crate::types::BucketTagging;
+use crate::{
+    error::{
+        self,
+        ResponseError,
+    },
+    types::{
+        TagSet,
+        Tagging,
+    },
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
 
-impl_sub_resource!(GetBucketTagging => BucketTagging);
+pub struct GetBucketTagging<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+}
 
 impl<'a> GetBucketTagging<'a> {
-    /// Create a new GetBucketLogging request with default parameters
+    /// Create a new GetBucketTagging request with default parameters
     pub fn new(bucket: &'a str) -> Self {
-        GetBucketTagging(SubResource {
-            bucket,
-            method: Method::GET,
-            key: None,
-            params: vec![(QueryParameter::TAGGING, None)],
+        GetBucketTagging { bucket }
+    }
+}
+
+impl<'a> AwsRequest for GetBucketTagging<'a> {
+    type Response = TagSet;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::TAGGING, None)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = match response.error().await {
+                Ok(bytes) => bytes,
+
+                // A bucket without any tags responds with an error rather than an empty tag-set
+                Err(Error::ResponseError(ResponseError {
+                    error: Some(ref error),
+                    ..
+                })) if error.code == "NoSuchTagSet" => return Ok(TagSet::new()),
+
+                Err(err) => return Err(err),
+            };
+            let string = String::from_utf8_lossy(&bytes);
+
+            let tagging: Tagging =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(tagging.tag_set)
         })
     }
 }
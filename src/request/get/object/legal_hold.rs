@@ -0,0 +1,82 @@
+use crate::{
+    error,
+    types::LegalHold,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    LegalHoldStatus,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct GetObjectLegalHold<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to get the legal hold status of.
+    pub key: &'a str,
+
+    version_id: Option<&'a str>,
+}
+
+impl<'a> GetObjectLegalHold<'a> {
+    /// Create a new GetObjectLegalHold request with default parameters
+    pub fn new(bucket: &'a str, key: &'a str) -> Self {
+        GetObjectLegalHold {
+            bucket,
+            key,
+            version_id: None,
+        }
+    }
+
+    /// The version of the object to get the legal hold status of.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+}
+
+impl<'a> AwsRequest for GetObjectLegalHold<'a> {
+    type Response = LegalHoldStatus;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::LEGAL_HOLD, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            let legal_hold: LegalHold =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(legal_hold.status)
+        })
+    }
+}
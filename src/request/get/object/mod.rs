@@ -4,9 +4,15 @@ use crate::{
     Error,
     Gmt,
     Headers,
+    Metadata,
+    ObjectEncryption,
+    ObjectLock,
+    QueryParameter,
     Region,
+    ReplicationStatus,
     Request,
     SigningKey,
+    SseCustomerKey,
     StorageClass,
 };
 use chrono::{
@@ -31,6 +37,14 @@ use std::{
 };
 use url::Url;
 
+mod legal_hold;
+mod retention;
+mod tagging;
+
+pub use legal_hold::*;
+pub use retention::*;
+pub use tagging::*;
+
 // Reason for `R: FromGetObjectResponse` is because the response
 // of the get request will become optional if any of the `if_*`
 // options are set. Otherwise, the request will always return a
@@ -44,23 +58,25 @@ pub struct GetObject<'a, R: FromGetObjectResponse> {
     if_modified_since: Option<DateTime<Utc>>,
     if_none_match: Option<&'a str>,
     if_unmodified_since: Option<DateTime<Utc>>,
+    part_number: Option<u64>,
     range: Option<String>,
     version_id: Option<&'a str>,
+    overrides: ResponseOverrides<'a>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
     _phantom: PhantomData<R>,
 }
 
-// TODO:
-// pub part_number: Option<u64>,
-// pub request_payer: Option<T>,
-// pub response_cache_control: Option<T>,
-// pub response_content_disposition: Option<T>,
-// pub response_content_encoding: Option<T>,
-// pub response_content_language: Option<T>,
-// pub response_content_type: Option<T>,
-// pub response_expires: Option<T>,
-// pub sse_customer_algorithm: Option<T>,
-// pub sse_customer_key: Option<T>,
-// pub sse_customer_key_md5: Option<T>,
+/// Headers of the response to override. S3 only honors these as (signed)
+/// query parameters, which also makes them usable in download links.
+#[derive(Default)]
+struct ResponseOverrides<'a> {
+    cache_control: Option<&'a str>,
+    content_disposition: Option<&'a str>,
+    content_encoding: Option<&'a str>,
+    content_language: Option<&'a str>,
+    content_type: Option<&'a str>,
+    expires: Option<String>,
+}
 
 impl<'a> GetObject<'a, GetObjectResponse> {
     /// Create a new GetObject request with default parameters and non-optional
@@ -73,8 +89,11 @@ impl<'a> GetObject<'a, GetObjectResponse> {
             if_modified_since: None,
             if_none_match: None,
             if_unmodified_since: None,
+            part_number: None,
             range: None,
             version_id: None,
+            overrides: ResponseOverrides::default(),
+            sse_customer_key: None,
             _phantom: PhantomData,
         }
     }
@@ -89,8 +108,11 @@ impl<'a> GetObject<'a, GetObjectResponse> {
             if_modified_since: self.if_modified_since,
             if_none_match: self.if_none_match,
             if_unmodified_since: self.if_unmodified_since,
+            part_number: self.part_number,
             range: self.range,
             version_id: self.version_id,
+            overrides: self.overrides,
+            sse_customer_key: self.sse_customer_key,
             _phantom: PhantomData,
         }
     }
@@ -108,8 +130,11 @@ impl<'a> GetObject<'a, GetObjectResponse> {
             if_modified_since: Some(since),
             if_none_match: self.if_none_match,
             if_unmodified_since: self.if_unmodified_since,
+            part_number: self.part_number,
             range: self.range,
             version_id: self.version_id,
+            overrides: self.overrides,
+            sse_customer_key: self.sse_customer_key,
             _phantom: PhantomData,
         }
     }
@@ -124,8 +149,11 @@ impl<'a> GetObject<'a, GetObjectResponse> {
             if_modified_since: self.if_modified_since,
             if_none_match: Some(etag),
             if_unmodified_since: self.if_unmodified_since,
+            part_number: self.part_number,
             range: self.range,
             version_id: self.version_id,
+            overrides: self.overrides,
+            sse_customer_key: self.sse_customer_key,
             _phantom: PhantomData,
         }
     }
@@ -143,8 +171,11 @@ impl<'a> GetObject<'a, GetObjectResponse> {
             if_modified_since: self.if_modified_since,
             if_none_match: self.if_none_match,
             if_unmodified_since: Some(since),
+            part_number: self.part_number,
             range: self.range,
             version_id: self.version_id,
+            overrides: self.overrides,
+            sse_customer_key: self.sse_customer_key,
             _phantom: PhantomData,
         }
     }
@@ -160,6 +191,59 @@ impl<'a> GetObject<'a, GetObjectResponse> {
         self.version_id = Some(version_id);
         self
     }
+
+    /// Part number of the object being read. This is a positive integer between 1 and 10,000.
+    /// Effectively performs a 'ranged' GET request for the part specified.
+    pub fn part_number(mut self, part_number: u64) -> Self {
+        self.part_number = Some(part_number);
+        self
+    }
+}
+
+impl<'a, R: FromGetObjectResponse> GetObject<'a, R> {
+    /// The customer-provided key the object was encrypted with (SSE-C).
+    /// **Note:** The request is refused unless the client uses https
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+
+    /// Sets the `Cache-Control` header of the response.
+    pub fn response_cache_control(mut self, cache_control: &'a str) -> Self {
+        self.overrides.cache_control = Some(cache_control);
+        self
+    }
+
+    /// Sets the `Content-Disposition` header of the response, e.g.
+    /// `attachment; filename="report.pdf"` to download under a different name.
+    pub fn response_content_disposition(mut self, disposition: &'a str) -> Self {
+        self.overrides.content_disposition = Some(disposition);
+        self
+    }
+
+    /// Sets the `Content-Encoding` header of the response.
+    pub fn response_content_encoding(mut self, encoding: &'a str) -> Self {
+        self.overrides.content_encoding = Some(encoding);
+        self
+    }
+
+    /// Sets the `Content-Language` header of the response.
+    pub fn response_content_language(mut self, language: &'a str) -> Self {
+        self.overrides.content_language = Some(language);
+        self
+    }
+
+    /// Sets the `Content-Type` header of the response.
+    pub fn response_content_type(mut self, content_type: &'a str) -> Self {
+        self.overrides.content_type = Some(content_type);
+        self
+    }
+
+    /// Sets the `Expires` header of the response.
+    pub fn response_expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.overrides.expires = Some(expires.to_gmt());
+        self
+    }
 }
 
 impl<'a> AwsRequest for GetObject<'a, GetObjectResponse> {
@@ -172,11 +256,40 @@ impl<'a> AwsRequest for GetObject<'a, GetObjectResponse> {
         signing_key: &SigningKey,
         region: Region,
     ) -> Result<HttpRequest<HttpBody>, Error> {
+        let part_number = self.part_number.map(|part_number| part_number.to_string());
+
         Request::new(Method::GET)
             .bucket(self.bucket)
             .key(self.key)
             .host(url)?
             .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .optional_query(QueryParameter::PART_NUMBER, part_number.as_deref())
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .optional_query(
+                QueryParameter::RESPONSE_CACHE_CONTROL,
+                self.overrides.cache_control,
+            )
+            .optional_query(
+                QueryParameter::RESPONSE_CONTENT_DISPOSITION,
+                self.overrides.content_disposition,
+            )
+            .optional_query(
+                QueryParameter::RESPONSE_CONTENT_ENCODING,
+                self.overrides.content_encoding,
+            )
+            .optional_query(
+                QueryParameter::RESPONSE_CONTENT_LANGUAGE,
+                self.overrides.content_language,
+            )
+            .optional_query(
+                QueryParameter::RESPONSE_CONTENT_TYPE,
+                self.overrides.content_type,
+            )
+            .optional_query(
+                QueryParameter::RESPONSE_EXPIRES,
+                self.overrides.expires.as_deref(),
+            )
             .header(Headers::IF_MATCH, self.if_match)
             .header(
                 Headers::IF_MODIFIED_SINCE,
@@ -188,7 +301,6 @@ impl<'a> AwsRequest for GetObject<'a, GetObjectResponse> {
                 self.if_unmodified_since.map(|since| since.to_gmt()),
             )
             .header(Headers::RANGE, self.range.clone())
-            .header(Headers::VERSION_ID, self.version_id)
             .build(&access_key.as_ref(), &signing_key)
     }
 
@@ -217,8 +329,11 @@ impl<'a> AwsRequest for GetObject<'a, Option<GetObjectResponse>> {
                 if_modified_since: self.if_modified_since,
                 if_none_match: self.if_none_match,
                 if_unmodified_since: self.if_unmodified_since,
+                part_number: self.part_number,
                 range: self.range,
                 version_id: self.version_id,
+                overrides: self.overrides,
+                sse_customer_key: self.sse_customer_key,
                 _phantom: PhantomData,
             },
             url,
@@ -249,6 +364,19 @@ pub struct GetObjectResponse {
     pub expires: Option<DateTime<Utc>>,
     pub storage_class: StorageClass,
     pub parts_count: Option<u64>,
+    pub content_type: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub cache_control: Option<String>,
+    pub metadata: Metadata,
+    pub encryption: ObjectEncryption,
+    pub object_lock: ObjectLock,
+    pub replication_status: Option<ReplicationStatus>,
+
+    /// Whether the requester was charged for the request.
+    pub request_charged: bool,
+
     pub body: Vec<u8>,
 }
 
@@ -283,6 +411,16 @@ impl FromGetObjectResponse for GetObjectResponse {
             let expires = response.expires()?;
             let storage_class = response.storage_class()?;
             let parts_count = response.parts_count()?;
+            let content_type = response.content_type()?;
+            let content_disposition = response.content_disposition()?;
+            let content_encoding = response.content_encoding()?;
+            let content_language = response.content_language()?;
+            let cache_control = response.cache_control()?;
+            let metadata = response.metadata();
+            let encryption = response.encryption()?;
+            let object_lock = response.object_lock()?;
+            let replication_status = response.replication_status()?;
+            let request_charged = response.request_charged()?;
 
             Ok(GetObjectResponse {
                 last_modified,
@@ -291,6 +429,16 @@ impl FromGetObjectResponse for GetObjectResponse {
                 storage_class,
                 expires,
                 parts_count,
+                content_type,
+                content_disposition,
+                content_encoding,
+                content_language,
+                cache_control,
+                metadata,
+                encryption,
+                object_lock,
+                replication_status,
+                request_charged,
                 body: bytes,
             })
         })
@@ -0,0 +1,81 @@
+use crate::{
+    error,
+    types::Retention,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct GetObjectRetention<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to get the retention of.
+    pub key: &'a str,
+
+    version_id: Option<&'a str>,
+}
+
+impl<'a> GetObjectRetention<'a> {
+    /// Create a new GetObjectRetention request with default parameters
+    pub fn new(bucket: &'a str, key: &'a str) -> Self {
+        GetObjectRetention {
+            bucket,
+            key,
+            version_id: None,
+        }
+    }
+
+    /// The version of the object to get the retention of.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+}
+
+impl<'a> AwsRequest for GetObjectRetention<'a> {
+    type Response = Retention;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::RETENTION, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            let retention: Retention =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(retention)
+        })
+    }
+}
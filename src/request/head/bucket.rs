@@ -0,0 +1,104 @@
+use crate::{
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+    StatusCode,
+};
+use url::Url;
+
+pub struct HeadBucket<'a> {
+    /// The bucket name.
+    pub bucket: &'a str,
+}
+
+impl<'a> HeadBucket<'a> {
+    /// Create a new HeadBucket request. The response is `None` if the bucket
+    /// does not exist.
+    pub fn new(bucket: &'a str) -> Self {
+        HeadBucket { bucket }
+    }
+}
+
+#[derive(Debug)]
+pub struct HeadBucketResponse {
+    /// The region the bucket is located in, as reported by `x-amz-bucket-region`.
+    pub bucket_region: Option<String>,
+}
+
+impl<'a> AwsRequest for HeadBucket<'a> {
+    type Response = Option<HeadBucketResponse>;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::HEAD)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            match response.status() {
+                StatusCode::NOT_FOUND => return Ok(None),
+
+                // The bucket exists, but in a different region than the one requested
+                StatusCode::MOVED_PERMANENTLY => {}
+
+                _ => {
+                    response.error().await?;
+                }
+            }
+
+            Ok(Some(HeadBucketResponse {
+                bucket_region: response.bucket_region()?,
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Headers;
+
+    #[tokio::test]
+    async fn missing_and_redirected_buckets() {
+        let response = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(HttpBody::empty())
+            .unwrap();
+        assert!(HeadBucket::into_response(response).await.unwrap().is_none());
+
+        let response = Response::builder()
+            .status(StatusCode::MOVED_PERMANENTLY)
+            .header(Headers::X_AMZ_BUCKET_REGION, "eu-north-1")
+            .body(HttpBody::empty())
+            .unwrap();
+        let bucket = HeadBucket::into_response(response).await.unwrap().unwrap();
+        assert_eq!(bucket.bucket_region.as_deref(), Some("eu-north-1"));
+
+        let response = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(HttpBody::empty())
+            .unwrap();
+        assert!(HeadBucket::into_response(response).await.is_err());
+    }
+}
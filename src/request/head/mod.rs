@@ -0,0 +1,5 @@
+mod bucket;
+mod object;
+
+pub use bucket::*;
+pub use object::*;
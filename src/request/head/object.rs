@@ -0,0 +1,234 @@
+use crate::{
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Gmt,
+    Headers,
+    Metadata,
+    ObjectEncryption,
+    ObjectLock,
+    QueryParameter,
+    Region,
+    ReplicationStatus,
+    Request,
+    SigningKey,
+    SseCustomerKey,
+    StorageClass,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+    StatusCode,
+};
+use url::Url;
+
+pub struct HeadObject<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+    /// Key of the object.
+    pub key: &'a str,
+    if_match: Option<&'a str>,
+    if_modified_since: Option<DateTime<Utc>>,
+    if_none_match: Option<&'a str>,
+    if_unmodified_since: Option<DateTime<Utc>>,
+    part_number: Option<u64>,
+    range: Option<String>,
+    version_id: Option<&'a str>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+}
+
+impl<'a> HeadObject<'a> {
+    /// Create a new HeadObject request with default parameters.
+    pub fn new(bucket: &'a str, key: &'a str) -> Self {
+        HeadObject {
+            bucket,
+            key,
+            if_match: None,
+            if_modified_since: None,
+            if_none_match: None,
+            if_unmodified_since: None,
+            part_number: None,
+            range: None,
+            version_id: None,
+            sse_customer_key: None,
+        }
+    }
+
+    /// Return the metadata only if its entity tag (ETag) is the same as the one specified, otherwise return a 412 (precondition failed).
+    pub fn if_match(mut self, etag: &'a str) -> Self {
+        self.if_match = Some(etag);
+        self
+    }
+
+    /// Return the metadata only if it has been modified since the specified time, otherwise fail with `Error::NotModified`.
+    pub fn if_modified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.if_modified_since = Some(since);
+        self
+    }
+
+    /// Return the metadata only if its entity tag (ETag) is different from the one specified, otherwise fail with `Error::NotModified`.
+    pub fn if_none_match(mut self, etag: &'a str) -> Self {
+        self.if_none_match = Some(etag);
+        self
+    }
+
+    /// Return the metadata only if it has not been modified since the specified time, otherwise return a 412 (precondition failed).
+    pub fn if_unmodified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.if_unmodified_since = Some(since);
+        self
+    }
+
+    /// Part number of the object being read. Returns the `content_length` and
+    /// `parts_count` for the part specified.
+    pub fn part_number(mut self, part_number: u64) -> Self {
+        self.part_number = Some(part_number);
+        self
+    }
+
+    /// The specified range bytes of an object.
+    pub fn range(mut self, start: u64, end: u64) -> Self {
+        self.range = Some(format!("bytes={}-{}", start, end));
+        self
+    }
+
+    /// VersionId used to reference a specific version of the object.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    /// The customer-provided key the object was encrypted with (SSE-C).
+    /// **Note:** The request is refused unless the client uses https
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+}
+
+/// Object metadata as returned in the headers of a `HeadObject` request.
+#[derive(Debug)]
+pub struct ObjectMetadata {
+    pub last_modified: DateTime<Utc>,
+    pub etag: String,
+    pub version_id: Option<String>,
+    pub expires: Option<DateTime<Utc>>,
+    pub storage_class: StorageClass,
+    pub parts_count: Option<u64>,
+    pub content_length: Option<u64>,
+    pub content_type: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub cache_control: Option<String>,
+    pub delete_marker: Option<bool>,
+    pub metadata: Metadata,
+    pub encryption: ObjectEncryption,
+    pub object_lock: ObjectLock,
+    pub replication_status: Option<ReplicationStatus>,
+
+    /// Whether the requester was charged for the request.
+    pub request_charged: bool,
+}
+
+impl<'a> AwsRequest for HeadObject<'a> {
+    type Response = Option<ObjectMetadata>;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let part_number = self.part_number.map(|part_number| part_number.to_string());
+
+        Request::new(Method::HEAD)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .optional_query(QueryParameter::PART_NUMBER, part_number.as_deref())
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .header(Headers::IF_MATCH, self.if_match)
+            .header(
+                Headers::IF_MODIFIED_SINCE,
+                self.if_modified_since.map(|since| since.to_gmt()),
+            )
+            .header(Headers::IF_NONE_MATCH, self.if_none_match)
+            .header(
+                Headers::IF_UNMODIFIED_SINCE,
+                self.if_unmodified_since.map(|since| since.to_gmt()),
+            )
+            .header(Headers::RANGE, self.range.clone())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            // A HEAD response never has a body, so a missing object can't
+            // be told apart from other errors by parsing it.
+            match response.status() {
+                StatusCode::NOT_FOUND => return Ok(None),
+                StatusCode::NOT_MODIFIED => return Err(Error::NotModified),
+                _ => {}
+            }
+
+            response.error().await?;
+
+            Ok(Some(ObjectMetadata {
+                last_modified: response.last_modified()?,
+                etag: response.etag()?,
+                version_id: response.version_id()?,
+                expires: response.expires()?,
+                storage_class: response.storage_class()?,
+                parts_count: response.parts_count()?,
+                content_length: response.content_length()?,
+                content_type: response.content_type()?,
+                content_disposition: response.content_disposition()?,
+                content_encoding: response.content_encoding()?,
+                content_language: response.content_language()?,
+                cache_control: response.cache_control()?,
+                delete_marker: response.delete_marker()?,
+                metadata: response.metadata(),
+                encryption: response.encryption()?,
+                object_lock: response.object_lock()?,
+                replication_status: response.replication_status()?,
+                request_charged: response.request_charged()?,
+            }))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn head(status: StatusCode) -> Result<Option<ObjectMetadata>, Error> {
+        let response = Response::builder()
+            .status(status)
+            .body(HttpBody::empty())
+            .unwrap();
+
+        HeadObject::into_response(response).await
+    }
+
+    #[tokio::test]
+    async fn missing_and_unmodified_objects() {
+        assert!(matches!(head(StatusCode::NOT_FOUND).await, Ok(None)));
+        assert!(matches!(
+            head(StatusCode::NOT_MODIFIED).await,
+            Err(Error::NotModified)
+        ));
+        assert!(head(StatusCode::FORBIDDEN).await.is_err());
+    }
+}
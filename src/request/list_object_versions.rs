@@ -0,0 +1,109 @@
+use crate::{
+    error,
+    types::ListVersionsResult,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+/// Lists the versions and delete markers of the objects of a bucket in key
+/// order, at most 1000 per page.
+pub struct ListObjectVersions<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    prefix: Option<&'a str>,
+    delimiter: Option<&'a str>,
+    key_marker: Option<&'a str>,
+    version_id_marker: Option<&'a str>,
+    max_keys: Option<String>,
+}
+
+impl<'a> ListObjectVersions<'a> {
+    /// Create a new ListObjectVersions request for the first page of every
+    /// object version
+    pub fn new(bucket: &'a str) -> Self {
+        ListObjectVersions {
+            bucket,
+            prefix: None,
+            delimiter: None,
+            key_marker: None,
+            version_id_marker: None,
+            max_keys: None,
+        }
+    }
+
+    /// Only list keys starting with `prefix`.
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Group keys containing `delimiter` after the prefix into common
+    /// prefixes rather than listing them.
+    pub fn delimiter(mut self, delimiter: &'a str) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Request the page following the one that returned these markers as
+    /// `next_key_marker` and `next_version_id_marker`.
+    pub fn marker(mut self, key_marker: &'a str, version_id_marker: Option<&'a str>) -> Self {
+        self.key_marker = Some(key_marker);
+        self.version_id_marker = version_id_marker;
+        self
+    }
+
+    /// List at most `max_keys` versions per page.
+    pub fn max_keys(mut self, max_keys: u32) -> Self {
+        self.max_keys = Some(max_keys.to_string());
+        self
+    }
+}
+
+impl<'a> AwsRequest for ListObjectVersions<'a> {
+    type Response = ListVersionsResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::VERSIONS, None)
+            .optional_query(QueryParameter::PREFIX, self.prefix)
+            .optional_query(QueryParameter::DELIMITER, self.delimiter)
+            .optional_query(QueryParameter::KEY_MARKER, self.key_marker)
+            .optional_query(QueryParameter::VERSION_ID_MARKER, self.version_id_marker)
+            .optional_query(QueryParameter::MAX_KEYS, self.max_keys.as_deref())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            Ok(quick_xml::de::from_str(&string).map_err(error::Internal::from)?)
+        })
+    }
+}
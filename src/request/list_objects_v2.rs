@@ -0,0 +1,112 @@
+use crate::{
+    error,
+    types::ListBucketResult,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+/// Lists the objects of a bucket in key order, at most 1000 per page.
+pub struct ListObjectsV2<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    prefix: Option<&'a str>,
+    delimiter: Option<&'a str>,
+    start_after: Option<&'a str>,
+    continuation_token: Option<&'a str>,
+    max_keys: Option<String>,
+}
+
+impl<'a> ListObjectsV2<'a> {
+    /// Create a new ListObjectsV2 request for the first page of every object
+    pub fn new(bucket: &'a str) -> Self {
+        ListObjectsV2 {
+            bucket,
+            prefix: None,
+            delimiter: None,
+            start_after: None,
+            continuation_token: None,
+            max_keys: None,
+        }
+    }
+
+    /// Only list keys starting with `prefix`.
+    pub fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Group keys containing `delimiter` after the prefix into common
+    /// prefixes rather than listing them.
+    pub fn delimiter(mut self, delimiter: &'a str) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Only list keys after `key`.
+    pub fn start_after(mut self, key: &'a str) -> Self {
+        self.start_after = Some(key);
+        self
+    }
+
+    /// Request the page following the one that returned this token as
+    /// `next_continuation_token`.
+    pub fn continuation_token(mut self, token: &'a str) -> Self {
+        self.continuation_token = Some(token);
+        self
+    }
+
+    /// List at most `max_keys` objects per page.
+    pub fn max_keys(mut self, max_keys: u32) -> Self {
+        self.max_keys = Some(max_keys.to_string());
+        self
+    }
+}
+
+impl<'a> AwsRequest for ListObjectsV2<'a> {
+    type Response = ListBucketResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::GET)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::LIST_TYPE, Some("2"))
+            .optional_query(QueryParameter::PREFIX, self.prefix)
+            .optional_query(QueryParameter::DELIMITER, self.delimiter)
+            .optional_query(QueryParameter::START_AFTER, self.start_after)
+            .optional_query(QueryParameter::CONTINUATION_TOKEN, self.continuation_token)
+            .optional_query(QueryParameter::MAX_KEYS, self.max_keys.as_deref())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            Ok(quick_xml::de::from_str(&string).map_err(error::Internal::from)?)
+        })
+    }
+}
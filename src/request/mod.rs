@@ -1,11 +1,21 @@
 use crate::{
+    accelerate,
     error,
+    grant::Grants,
+    sign_request,
+    AccelerateEndpoint,
+    Acl,
+    Error,
+    Grantee,
     Headers,
+    Metadata,
+    Permission,
     Region,
     Result,
     SigningKey,
+    SseCustomerKey,
+    UriEncode,
 };
-use chrono::Utc;
 use http::{
     uri::{
         PathAndQuery,
@@ -29,96 +39,146 @@ use std::{
 };
 use url::Url;
 
+// Implements a request on a bucket sub-resource, such as `?acl` or `?tagging`,
+// that takes no parameters other than the bucket name. Sub-resources holding
+// several configurations list the parameters selecting one in brackets, e.g.
+// `[id => ID]`, which become fields of the request. Requests returning `()`
+// ignore the response body, any other output is deserialized from its xml.
 macro_rules! impl_sub_resource {
-    ($name: ident => $output: ty, $(($query: ident => $value: expr)),*) => {
-        use crate::{
-            error,
-            AwsRequest,
-            AwsResponse,
-            Error,
-            QueryParameter,
-            Region,
-            SigningKey,
-            Request,
-        };
-        use futures_core::future::BoxFuture;
-        use hyper::{
-            Body as HttpBody,
-            Method,
-            Request as HttpRequest,
-            Response,
-        };
-        use url::Url;
+    ($method: ident $name: ident $([$($field: ident => $param: ident),*])? => (), $(($query: ident => $value: expr)),*) => {
+        impl_sub_resource!(@impl $method $name [$($($field => $param),*)?] => (), [$(($query => $value)),*], |response| {
+            response.error().await?;
+
+            Ok(())
+        });
+    };
+    ($method: ident $name: ident $([$($field: ident => $param: ident),*])? => $output: ty, $(($query: ident => $value: expr)),*) => {
+        impl_sub_resource!(@impl $method $name [$($($field => $param),*)?] => $output, [$(($query => $value)),*], |response| {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            let resp: $output =
+                quick_xml::de::from_str(&string).map_err(crate::error::Internal::from)?;
 
+            Ok(resp)
+        });
+    };
+    (@impl $method: ident $name: ident [$($field: ident => $param: ident),*] => $output: ty, [$(($query: ident => $value: expr)),*], |$response: ident| $body: block) => {
         pub struct $name<'a> {
-            bucket: &'a str
+            /// The name of the bucket.
+            pub bucket: &'a str,
+            $(
+                #[doc = concat!("The ", stringify!($field), " of the configuration.")]
+                pub $field: &'a str,
+            )*
         }
 
         impl<'a> $name<'a> {
-            /// Create a new GetBucketAcl request with default parameters
-            pub fn new(bucket: &'a str) -> Self {
-                Self { bucket }
+            #[doc = concat!("Create a new ", stringify!($name), " request with default parameters")]
+            pub fn new(bucket: &'a str $(, $field: &'a str)*) -> Self {
+                Self { bucket $(, $field)* }
             }
         }
 
-        impl<'a> AwsRequest for $name<'a> {
+        impl<'a> crate::AwsRequest for $name<'a> {
             type Response = $output;
 
             fn into_request<AR: AsRef<str>>(
                 self,
-                url: Url,
+                url: url::Url,
                 access_key: AR,
-                signing_key: &SigningKey,
-                region: Region,
-            ) -> Result<HttpRequest<HttpBody>, Error> {
-                Request::new(Method::DELETE)
+                signing_key: &crate::SigningKey,
+                region: crate::Region,
+            ) -> Result<hyper::Request<hyper::Body>, crate::Error> {
+                crate::Request::new(hyper::Method::$method)
                     .bucket(self.bucket)
-                    .host(url.clone())?
+                    .host(url)?
                     .region(region)
                     $(
-                        .query(QueryParameter::$query, $value)
+                        .query(crate::QueryParameter::$query, $value)
+                    )*
+                    $(
+                        .query(crate::QueryParameter::$param, Some(self.$field))
                     )*
-                    .build(&access_key.as_ref(), &signing_key)
+                    .build(access_key.as_ref(), signing_key)
             }
 
             fn into_response(
-                mut response: Response<HttpBody>,
-            ) -> BoxFuture<'static, Result<Self::Response, Error>> {
-                Box::pin(async move {
-                    let bytes = response.error().await?;
-                    let string = String::from_utf8_lossy(&bytes);
-
-                    println!("-------------------------------------");
-                    println!("{}", string);
-                    println!("-------------------------------------");
+                mut $response: hyper::Response<hyper::Body>,
+            ) -> futures_core::future::BoxFuture<'static, Result<Self::Response, crate::Error>> {
+                use crate::AwsResponse;
 
-                    let resp: $output =
-                        quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
-
-                    Ok(resp)
-                })
+                Box::pin(async move $body)
             }
         }
     };
 }
 
+pub mod copy_object;
+pub mod delete_objects;
 // pub mod create_bucket;
 // pub mod list_buckets;
+pub mod list_object_versions;
+pub mod list_objects_v2;
 // pub mod put_bucket_encryption;
-// pub mod put_object;
-
-// pub mod delete;
+pub mod put_bucket_accelerate_configuration;
+pub mod put_bucket_analytics_configuration;
+pub mod put_bucket_cors;
+pub mod put_bucket_inventory_configuration;
+pub mod put_bucket_lifecycle_configuration;
+pub mod put_bucket_logging;
+pub mod put_bucket_metrics_configuration;
+pub mod put_bucket_notification_configuration;
+pub mod put_bucket_policy;
+pub mod put_bucket_replication;
+pub mod put_bucket_request_payment;
+pub mod put_bucket_tagging;
+pub mod put_bucket_versioning;
+pub mod put_bucket_website;
+pub mod put_object;
+pub mod put_object_legal_hold;
+pub mod put_object_lock_configuration;
+pub mod put_object_retention;
+pub mod put_object_tagging;
+pub mod put_public_access_block;
+
+pub mod delete;
 pub mod get;
+pub mod head;
+pub mod multipart;
 
-pub(crate) mod sub_resource;
-
+pub use copy_object::*;
+pub use delete_objects::*;
 // pub use create_bucket::*;
 // pub use list_buckets::*;
+pub use list_object_versions::*;
+pub use list_objects_v2::*;
 // pub use put_bucket_encryption::*;
-// pub use put_object::*;
-
-// pub use delete::*;
+pub use put_bucket_accelerate_configuration::*;
+pub use put_bucket_analytics_configuration::*;
+pub use put_bucket_cors::*;
+pub use put_bucket_inventory_configuration::*;
+pub use put_bucket_lifecycle_configuration::*;
+pub use put_bucket_logging::*;
+pub use put_bucket_metrics_configuration::*;
+pub use put_bucket_notification_configuration::*;
+pub use put_bucket_policy::*;
+pub use put_bucket_replication::*;
+pub use put_bucket_request_payment::*;
+pub use put_bucket_tagging::*;
+pub use put_bucket_versioning::*;
+pub use put_bucket_website::*;
+pub use put_object::*;
+pub use put_object_legal_hold::*;
+pub use put_object_lock_configuration::*;
+pub use put_object_retention::*;
+pub use put_object_tagging::*;
+pub use put_public_access_block::*;
+
+pub use delete::*;
 pub use get::*;
+pub use head::*;
+pub use multipart::*;
 
 const NO_PAYLOAD_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
 
@@ -129,7 +189,7 @@ pub struct Request<'a> {
     pub query: Vec<(&'static str, Option<&'a str>)>,
     pub body: Option<HttpBody>,
     pub region: Option<String>,
-    pub headers: Vec<(&'static str, Cow<'a, str>)>,
+    pub headers: Vec<(Cow<'a, str>, Cow<'a, str>)>,
     uri: Option<Uri>,
     hash: Option<Cow<'static, str>>,
 }
@@ -173,9 +233,32 @@ impl<'a> Request<'a> {
         self
     }
 
-    pub fn header<T: Into<Cow<'a, str>>>(mut self, key: &'static str, value: Option<T>) -> Self {
+    /// Same as `Request::query()`, but the parameter is only added if a value is present
+    pub fn optional_query(self, key: &'static str, value: Option<&'a str>) -> Self {
+        if value.is_some() {
+            self.query(key, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn header<T: Into<Cow<'a, str>>>(self, key: &'static str, value: Option<T>) -> Self {
+        self.dynamic_header(key, value)
+    }
+
+    /// Same as `Request::header()`, but for header names only known at runtime,
+    /// such as user-defined `x-amz-meta-*` headers. Names must be lowercase.
+    fn dynamic_header<K, T>(mut self, key: K, value: Option<T>) -> Self
+    where
+        K: Into<Cow<'a, str>>,
+        T: Into<Cow<'a, str>>,
+    {
         if let Some(value) = value {
-            match self.headers.binary_search_by(|&(k, _)| k.cmp(key)) {
+            let key = key.into();
+            match self
+                .headers
+                .binary_search_by(|(k, _)| k.as_ref().cmp(key.as_ref()))
+            {
                 Ok(_) => {}
                 Err(index) => self.headers.insert(index, (key, value.into())),
             }
@@ -183,9 +266,86 @@ impl<'a> Request<'a> {
         self
     }
 
+    pub(crate) fn metadata(mut self, metadata: &Metadata) -> Self {
+        for (name, value) in metadata.headers() {
+            self = self.dynamic_header(name, Some(value));
+        }
+        self
+    }
+
+    /// Encrypt or decrypt the object with a customer-provided key (SSE-C).
+    /// Must be called after `Request::host()`, since the key is refused
+    /// unless the request is sent over https.
+    pub(crate) fn sse_customer_key(self, key: Option<&'a SseCustomerKey>) -> Result<Self> {
+        self.customer_key(
+            key,
+            Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+            Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+            Headers::X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+        )
+    }
+
+    /// The customer-provided key used to decrypt the source of a copy.
+    pub(crate) fn copy_source_sse_customer_key(
+        self,
+        key: Option<&'a SseCustomerKey>,
+    ) -> Result<Self> {
+        self.customer_key(
+            key,
+            Headers::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM,
+            Headers::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY,
+            Headers::X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_KEY_MD5,
+        )
+    }
+
+    fn customer_key(
+        self,
+        key: Option<&'a SseCustomerKey>,
+        algorithm_header: &'static str,
+        key_header: &'static str,
+        md5_header: &'static str,
+    ) -> Result<Self> {
+        let key = match key {
+            Some(key) => key,
+            None => return Ok(self),
+        };
+
+        let scheme = self.uri.as_ref().and_then(Uri::scheme_str);
+        if scheme != Some("https") {
+            return Err(Error::SseCustomerKeyOverHttp);
+        }
+
+        Ok(self
+            .header(algorithm_header, Some(SseCustomerKey::ALGORITHM))
+            .header(key_header, Some(key.key()))
+            .header(md5_header, Some(key.key_md5())))
+    }
+
+    pub(crate) fn grants<T: AsRef<str>>(
+        mut self,
+        acl: Option<Acl>,
+        grants: Vec<(Permission, Grantee, T)>,
+    ) -> Self {
+        if let (Some(acl), true) = (acl, grants.is_empty()) {
+            let acl: &'static str = acl.into();
+            self.header(Headers::X_AMZ_ACL, Some(acl))
+        } else {
+            let grants: Grants = grants.into();
+            self = self.header(Headers::X_AMZ_GRANT_READ, grants.read);
+            self = self.header(Headers::X_AMZ_GRANT_WRITE_ACP, grants.write_acp);
+            self = self.header(Headers::X_AMZ_GRANT_READ_ACP, grants.read_acp);
+            self.header(Headers::X_AMZ_GRANT_FULL_CONTROL, grants.full_control)
+        }
+    }
+
     pub fn host(mut self, url: Url) -> Result<Self> {
         let url_domain = url.domain().unwrap();
 
+        if let (Some(bucket), Some(_)) = (self.bucket, AccelerateEndpoint::from_domain(url_domain))
+        {
+            accelerate::check_bucket(bucket)?;
+        }
+
         let domain = if let Some(bucket) = self.bucket {
             format!("{}.{}", bucket, url.domain().unwrap())
         } else {
@@ -197,7 +357,7 @@ impl<'a> Request<'a> {
             url.scheme(),
             domain,
             url.port().map(|v| v.to_string()).unwrap_or("".to_owned()),
-            self.key.unwrap_or("")
+            self.key.unwrap_or("").uri_encode_path()
         );
 
         let domain = if let Some(ref region) = self.region {
@@ -228,27 +388,23 @@ impl<'a> Request<'a> {
         self
     }
 
-    fn build(
+    pub(crate) fn build(
         mut self,
         access_key: &'a str,
         signing_key: &'a SigningKey,
     ) -> Result<HttpRequest<HttpBody>> {
-        let mut canonical: Vec<u8> = Vec::new();
-        let mut signed: Vec<&str> = Vec::new();
-
+        // Query parameters are already sorted by name, which is the order
+        // required by the canonical request. Parameters without a value
+        // are still required to have the trailing '='.
         let mut query = Vec::new();
         for (key, value) in self.query.iter() {
-            query.push(if let Some(value) = value.as_ref() {
-                format!("{}={}", key, value)
-            } else {
-                format!("{}", key)
-            });
+            query.push(format!(
+                "{}={}",
+                key.uri_encode(),
+                value.unwrap_or("").uri_encode()
+            ));
         }
-        let query = if self.query.len() > 0 {
-            format!("?{}", query.join(","))
-        } else {
-            query.join(",")
-        };
+        let query = query.join("&");
 
         let uri = self
             .uri
@@ -258,121 +414,48 @@ impl<'a> Request<'a> {
         let mut parts = uri.into_parts();
         parts.path_and_query = Some(
             PathAndQuery::try_from(
-                format!("/{}{}", self.key.unwrap_or(""), query.as_str()).as_str(),
+                if query.is_empty() {
+                    format!("/{}", self.key.unwrap_or("").uri_encode_path())
+                } else {
+                    format!("/{}?{}", self.key.unwrap_or("").uri_encode_path(), query)
+                }
+                .as_str(),
             )
             .map_err(error::Internal::from)?,
         );
 
         let uri = Uri::from_parts(parts).map_err(error::Internal::from)?;
 
-        let mut request = HttpRequest::builder();
-
-        // Request Method
-        canonical.extend_from_slice(&self.method.as_str().as_bytes());
-        canonical.push(b'\n');
-
-        request = request.method(self.method.clone());
-
-        // Request Uri
-        canonical.extend_from_slice(&uri.path().as_bytes());
-        canonical.push(b'\n');
-
-        request = request.uri(uri);
-
-        // Request Query Parameters
-        canonical.extend_from_slice(&query.as_bytes());
-        canonical.push(b'\n');
-
-        // Request Query Parameters ready set above
+        let mut request = HttpRequest::builder().method(self.method.clone()).uri(uri);
 
         // The `X_AMZ_CONTENT_SHA256` header is *always* requried. If `Request::body()`
         // was not called then we simply set the header to the NO_PAYLOAD_HASH constant
         if self.hash.is_none() {
             self = self.header(Headers::X_AMZ_CONTENT_SHA256, Some(NO_PAYLOAD_HASH));
         }
-        // All requets should have date
-        // Formatting date in rfc1123 was rejected by minio even though it says to use that format
-        // instead using format from aws examples YYYYMMDDTHHMMSSZ
-        let date = Utc::now();
-        self = self.header(
-            Headers::X_AMZ_DATE,
-            Some(format!("{}", date.format("%Y%m%dT%H%M%SZ"))),
-        );
 
         // Request Headers
         for (header, value) in self.headers.iter() {
             request = request.header(
-                *header,
+                header.as_ref(),
                 HeaderValue::from_str(value.deref()).map_err(error::Internal::from)?,
             );
-            canonical.extend_from_slice(&header.as_bytes());
-            canonical.push(b':');
-            canonical.extend_from_slice(&value.as_bytes());
-            canonical.push(b'\n');
-
-            // Signed contains an ordered list of headers that are used to sign the request
-            signed.push(header)
         }
 
-        // End of Headers
-        canonical.push(b'\n');
-
-        // Signed Headers
-        let signed = signed.join(";");
-        canonical.extend_from_slice(&signed.as_bytes());
-        canonical.push(b'\n');
+        let region = self.region.ok_or(error::Internal::Message(
+            "Region was not set before signing".to_string(),
+        ))?;
 
-        let hash: &Cow<'static, str> = if let Some(ref hash) = self.hash {
-            hash
+        let mut request = if let Some(body) = self.body {
+            request.body(body).map_err(error::Internal::from)?
         } else {
-            &Cow::Borrowed(NO_PAYLOAD_HASH)
+            request
+                .body(HttpBody::empty())
+                .map_err(error::Internal::from)?
         };
 
-        canonical.extend_from_slice(&hash.deref().as_bytes());
-
-        println!("{}", String::from_utf8_lossy(&canonical));
-
-        let mut hasher = Sha256::new();
-        hasher.input(canonical);
-
-        let hash = hex::encode(hasher.result().as_slice());
-
-        let scope = format!(
-            "{date}/{region}/s3/aws4_request",
-            date = date.format("%Y%m%d"),
-            region = self.region.ok_or(error::Internal::Message(
-                "Region was not set before signing".to_string()
-            ))?
-        );
-
-        let string_to_sign = format!(
-            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{hash}",
-            timestamp = date.format("%Y%m%dT%H%M%SZ"),
-            scope = scope,
-            hash = hash
-        );
+        sign_request::sign(&mut request, access_key, signing_key, &region)?;
 
-        let sig = signing_key.sign(string_to_sign);
-
-        let auth = format!(
-            "AWS4-HMAC-SHA256 Credential={access_key}/{scope},SignedHeaders={signed_headers},Signature={signature}",
-            access_key = access_key,
-            scope = scope,
-            signed_headers = signed,
-            signature = sig
-        );
-
-        request = request.header(
-            Headers::AUTHORIZATION,
-            HeaderValue::from_str(&auth).map_err(error::Internal::from)?,
-        );
-
-        if let Some(body) = self.body {
-            Ok(request.body(body).map_err(error::Internal::from)?)
-        } else {
-            Ok(request
-                .body(HttpBody::empty())
-                .map_err(error::Internal::from)?)
-        }
+        Ok(request)
     }
 }
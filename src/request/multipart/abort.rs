@@ -0,0 +1,68 @@
+use crate::{
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct AbortMultipartUpload<'a> {
+    /// Bucket name to which the upload was taking place.
+    pub bucket: &'a str,
+
+    /// Key of the object for which the multipart upload was initiated.
+    pub key: &'a str,
+
+    upload_id: &'a str,
+}
+
+impl<'a> AbortMultipartUpload<'a> {
+    /// Create a new AbortMultipartUpload request for the given upload id
+    pub fn new(bucket: &'a str, key: &'a str, upload_id: &'a str) -> Self {
+        AbortMultipartUpload {
+            bucket,
+            key,
+            upload_id,
+        }
+    }
+}
+
+impl<'a> AwsRequest for AbortMultipartUpload<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        Request::new(Method::DELETE)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::UPLOAD_ID, Some(self.upload_id))
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
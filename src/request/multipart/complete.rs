@@ -0,0 +1,101 @@
+use crate::{
+    aws_response::error_in_body,
+    error,
+    types::{
+        CompleteMultipartUpload as CompleteMultipartUploadBody,
+        CompleteMultipartUploadResult,
+        CompletedPart,
+    },
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct CompleteMultipartUpload<'a> {
+    /// Name of the bucket to which the multipart upload was initiated.
+    pub bucket: &'a str,
+
+    /// Object key for which the multipart upload was initiated.
+    pub key: &'a str,
+
+    upload_id: &'a str,
+    parts: Vec<CompletedPart>,
+}
+
+impl<'a> CompleteMultipartUpload<'a> {
+    /// Create a new CompleteMultipartUpload request for the given upload id
+    pub fn new(bucket: &'a str, key: &'a str, upload_id: &'a str) -> Self {
+        CompleteMultipartUpload {
+            bucket,
+            key,
+            upload_id,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Add an uploaded part by its part number and the ETag returned when it was uploaded.
+    pub fn part<T: Into<String>>(mut self, part_number: u64, etag: T) -> Self {
+        self.parts.push(CompletedPart {
+            part_number,
+            etag: etag.into(),
+        });
+        self
+    }
+}
+
+impl<'a> AwsRequest for CompleteMultipartUpload<'a> {
+    type Response = CompleteMultipartUploadResult;
+
+    fn into_request<AR: AsRef<str>>(
+        mut self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        // Parts must be listed in ascending order of part number
+        self.parts.sort_by_key(|part| part.part_number);
+
+        let payload = xml::to_string(&CompleteMultipartUploadBody { parts: self.parts })
+            .map_err(error::Internal::from)?;
+
+        Request::new(Method::POST)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::UPLOAD_ID, Some(self.upload_id))
+            .body(payload.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            // Completing an upload can still fail after S3 responded with `200 OK`
+            error_in_body(response.status(), &string)?;
+
+            let result: CompleteMultipartUploadResult =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(result)
+        })
+    }
+}
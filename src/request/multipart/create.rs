@@ -0,0 +1,199 @@
+use crate::{
+    encryption::EncryptionHeaders,
+    error,
+    object_lock::ObjectLockHeaders,
+    request::put_object::ContentHeaders,
+    types::InitiateMultipartUploadResult,
+    AwsRequest,
+    AwsResponse,
+    CacheControl,
+    Error,
+    LegalHoldStatus,
+    Metadata,
+    ObjectLockMode,
+    QueryParameter,
+    Region,
+    Request,
+    ServerSideEncryption,
+    SigningKey,
+    SseCustomerKey,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct CreateMultipartUpload<'a> {
+    /// The name of the bucket to which to initiate the upload
+    pub bucket: &'a str,
+
+    /// Object key for which the multipart upload is to be initiated.
+    pub key: &'a str,
+
+    content: ContentHeaders<'a>,
+    metadata: Metadata,
+    encryption: EncryptionHeaders<'a>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+    object_lock: ObjectLockHeaders,
+}
+
+impl<'a> CreateMultipartUpload<'a> {
+    /// Create a new CreateMultipartUpload request with default parameters
+    pub fn new(bucket: &'a str, key: &'a str) -> Self {
+        CreateMultipartUpload {
+            bucket,
+            key,
+            content: ContentHeaders::default(),
+            metadata: Metadata::new(),
+            encryption: EncryptionHeaders::default(),
+            sse_customer_key: None,
+            object_lock: ObjectLockHeaders::default(),
+        }
+    }
+
+    /// The date and time at which the object is no longer cacheable.
+    pub fn expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.content.expires = Some(expires);
+        self
+    }
+
+    /// Can be used to specify caching behavior along the request/reply chain.
+    /// Can be called multiple times to add several directives.
+    pub fn cache(mut self, cache: CacheControl<'a>) -> Self {
+        self.content.cache.push(cache);
+        self
+    }
+
+    /// Specifies presentational information for the object.
+    pub fn content_disposition(mut self, disposition: &'a str) -> Self {
+        self.content.disposition = Some(disposition);
+        self
+    }
+
+    /// Specifies what content encodings have been applied to the object.
+    pub fn content_encoding(mut self, encoding: &'a str) -> Self {
+        self.content.encoding = Some(encoding);
+        self
+    }
+
+    /// The language the content is in.
+    pub fn content_language(mut self, language: &'a str) -> Self {
+        self.content.language = Some(language);
+        self
+    }
+
+    /// A standard MIME type describing the format of the object data.
+    pub fn content_type(mut self, content_type: &'a str) -> Self {
+        self.content.content_type = Some(content_type);
+        self
+    }
+
+    /// The server-side encryption algorithm used when storing the object.
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.encryption.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// The AWS KMS key ID to use for object encryption.
+    /// **Note:** This implies `ServerSideEncryption::AwsKms` unless another KMS algorithm was given
+    pub fn kms_key_id(mut self, key_id: &'a str) -> Self {
+        if self.encryption.server_side_encryption != Some(ServerSideEncryption::AwsKmsDsse) {
+            self.encryption.server_side_encryption = Some(ServerSideEncryption::AwsKms);
+        }
+        self.encryption.kms_key_id = Some(key_id);
+        self
+    }
+
+    /// Adds a key-value pair to the encryption context used with SSE-KMS.
+    pub fn encryption_context(mut self, key: &'a str, value: &'a str) -> Self {
+        self.encryption.context.insert(key, value);
+        self
+    }
+
+    /// Whether to use an S3 Bucket Key with SSE-KMS, reducing the requests made to AWS KMS.
+    pub fn bucket_key_enabled(mut self, enabled: bool) -> Self {
+        self.encryption.bucket_key_enabled = Some(enabled);
+        self
+    }
+
+    /// Encrypt the object with a customer-provided key (SSE-C).
+    /// The same key must be given to every `UploadPart` of the upload.
+    /// **Note:** The request is refused unless the client uses https
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+
+    /// Lock the object until `retain_until_date`. The bucket must have object lock enabled.
+    pub fn object_lock_retention(
+        mut self,
+        mode: ObjectLockMode,
+        retain_until_date: DateTime<Utc>,
+    ) -> Self {
+        self.object_lock.mode = Some(mode);
+        self.object_lock.retain_until_date = Some(retain_until_date);
+        self
+    }
+
+    /// Place a legal hold on the object. The bucket must have object lock enabled.
+    pub fn object_lock_legal_hold(mut self, status: LegalHoldStatus) -> Self {
+        self.object_lock.legal_hold = Some(status);
+        self
+    }
+
+    /// User-defined metadata to store with the completed object.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+impl<'a> AwsRequest for CreateMultipartUpload<'a> {
+    type Response = InitiateMultipartUploadResult;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let request = Request::new(Method::POST)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .query(QueryParameter::UPLOADS, None)
+            .metadata(&self.metadata);
+
+        let request = self.encryption.headers(request)?;
+        let request = self.object_lock.headers(request);
+
+        self.content
+            .headers(request)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            let result: InitiateMultipartUploadResult =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(result)
+        })
+    }
+}
@@ -0,0 +1,11 @@
+pub mod abort;
+pub mod complete;
+pub mod create;
+pub mod upload_part;
+pub mod upload_part_copy;
+
+pub use abort::*;
+pub use complete::*;
+pub use create::*;
+pub use upload_part::*;
+pub use upload_part_copy::*;
@@ -0,0 +1,95 @@
+use crate::{
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+    SseCustomerKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct UploadPart<'a> {
+    /// Name of the bucket to which the multipart upload was initiated.
+    pub bucket: &'a str,
+
+    /// Object key for which the multipart upload was initiated.
+    pub key: &'a str,
+
+    upload_id: &'a str,
+    part_number: u64,
+    contents: Vec<u8>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+}
+
+impl<'a> UploadPart<'a> {
+    /// Create a new UploadPart request. Part numbers range from 1 to 10,000
+    /// and every part except the last must be at least 5 MB.
+    pub fn new(
+        bucket: &'a str,
+        key: &'a str,
+        upload_id: &'a str,
+        part_number: u64,
+        contents: Vec<u8>,
+    ) -> Self {
+        UploadPart {
+            bucket,
+            key,
+            upload_id,
+            part_number,
+            contents,
+            sse_customer_key: None,
+        }
+    }
+
+    /// The customer-provided key given when the multipart upload was initiated (SSE-C).
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+}
+
+impl<'a> AwsRequest for UploadPart<'a> {
+    type Response = String;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let part_number = self.part_number.to_string();
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .query(QueryParameter::PART_NUMBER, Some(&part_number))
+            .query(QueryParameter::UPLOAD_ID, Some(self.upload_id))
+            .body(self.contents)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            let etag = response.etag()?;
+
+            Ok(etag)
+        })
+    }
+}
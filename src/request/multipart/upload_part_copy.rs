@@ -0,0 +1,169 @@
+use crate::{
+    aws_response::error_in_body,
+    error,
+    request::copy_object::CopySource,
+    types::CopyPartResult,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+    SseCustomerKey,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct UploadPartCopy<'a> {
+    /// Name of the bucket to which the multipart upload was initiated.
+    pub bucket: &'a str,
+
+    /// Object key for which the multipart upload was initiated.
+    pub key: &'a str,
+
+    upload_id: &'a str,
+    part_number: u64,
+    source: CopySource<'a>,
+    range: Option<String>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+}
+
+impl<'a> UploadPartCopy<'a> {
+    /// Create a new UploadPartCopy request which uploads a part by copying
+    /// data from `source_bucket/source_key`.
+    pub fn new(
+        bucket: &'a str,
+        key: &'a str,
+        upload_id: &'a str,
+        part_number: u64,
+        source_bucket: &'a str,
+        source_key: &'a str,
+    ) -> Self {
+        UploadPartCopy {
+            bucket,
+            key,
+            upload_id,
+            part_number,
+            source: CopySource::new(source_bucket, source_key),
+            range: None,
+            sse_customer_key: None,
+        }
+    }
+
+    /// The inclusive range of bytes to copy from the source object. Required
+    /// to copy objects larger than 5 GB, as a single part is limited to 5 GB.
+    pub fn range(mut self, first: u64, last: u64) -> Self {
+        self.range = Some(format!("bytes={}-{}", first, last));
+        self
+    }
+
+    /// The customer-provided key given when the multipart upload was initiated (SSE-C).
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+
+    /// The customer-provided key the source object was encrypted with (SSE-C).
+    pub fn copy_source_sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.source.sse_customer_key = Some(key);
+        self
+    }
+
+    /// Copy from a specific version of the source object.
+    pub fn source_version_id(mut self, version_id: &'a str) -> Self {
+        self.source.version_id = Some(version_id);
+        self
+    }
+
+    /// Copies the part only if the source entity tag (ETag) matches the specified tag.
+    pub fn copy_source_if_match(mut self, etag: &'a str) -> Self {
+        self.source.if_match = Some(etag);
+        self
+    }
+
+    /// Copies the part only if the source has been modified since the specified time.
+    pub fn copy_source_if_modified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.source.if_modified_since = Some(since);
+        self
+    }
+
+    /// Copies the part only if the source entity tag (ETag) is different than the specified ETag.
+    pub fn copy_source_if_none_match(mut self, etag: &'a str) -> Self {
+        self.source.if_none_match = Some(etag);
+        self
+    }
+
+    /// Copies the part only if the source hasn't been modified since the specified time.
+    pub fn copy_source_if_unmodified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.source.if_unmodified_since = Some(since);
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct UploadPartCopyResponse {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub copy_source_version_id: Option<String>,
+}
+
+impl<'a> AwsRequest for UploadPartCopy<'a> {
+    type Response = UploadPartCopyResponse;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let part_number = self.part_number.to_string();
+
+        let request = Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .query(QueryParameter::PART_NUMBER, Some(&part_number))
+            .query(QueryParameter::UPLOAD_ID, Some(self.upload_id))
+            .header(Headers::X_AMZ_COPY_SOURCE_RANGE, self.range);
+
+        self.source
+            .headers(request)?
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            let bytes = response.error().await?;
+            let string = String::from_utf8_lossy(&bytes);
+
+            // A copy can still fail after S3 responded with `200 OK`
+            error_in_body(response.status(), &string)?;
+
+            let result: CopyPartResult =
+                quick_xml::de::from_str(&string).map_err(error::Internal::from)?;
+
+            Ok(UploadPartCopyResponse {
+                etag: result.etag,
+                last_modified: result.last_modified,
+                copy_source_version_id: response.copy_source_version_id()?,
+            })
+        })
+    }
+}
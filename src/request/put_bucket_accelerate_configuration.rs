@@ -0,0 +1,73 @@
+use crate::{
+    accelerate,
+    error,
+    types::AccelerateConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketAccelerateConfiguration<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: AccelerateConfiguration,
+}
+
+impl<'a> PutBucketAccelerateConfiguration<'a> {
+    /// Create a new PutBucketAccelerateConfiguration request which enables
+    /// or suspends transfer acceleration of the bucket. Only buckets without
+    /// dots in their name can be accelerated.
+    pub fn new(bucket: &'a str, config: AccelerateConfiguration) -> Self {
+        PutBucketAccelerateConfiguration { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketAccelerateConfiguration<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        if self.config.is_enabled() {
+            accelerate::check_bucket(self.bucket)?;
+        }
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::ACCELERATE, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
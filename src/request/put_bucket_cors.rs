@@ -0,0 +1,70 @@
+use crate::{
+    error,
+    types::CorsConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketCors<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: CorsConfiguration,
+}
+
+impl<'a> PutBucketCors<'a> {
+    /// Create a new PutBucketCors request which replaces the cors
+    /// configuration of the bucket. The configuration is validated with
+    /// `CorsConfiguration::validate()` before sending.
+    pub fn new(bucket: &'a str, config: CorsConfiguration) -> Self {
+        PutBucketCors { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketCors<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.config.validate()?;
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::CORS, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
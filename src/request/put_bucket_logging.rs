@@ -0,0 +1,70 @@
+use crate::{
+    error,
+    types::BucketLoggingStatus,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketLogging<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    status: BucketLoggingStatus,
+}
+
+impl<'a> PutBucketLogging<'a> {
+    /// Create a new PutBucketLogging request which enables or disables
+    /// access logging of the bucket. The status is validated with
+    /// `BucketLoggingStatus::validate()` before sending.
+    pub fn new(bucket: &'a str, status: BucketLoggingStatus) -> Self {
+        PutBucketLogging { bucket, status }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketLogging<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.status.validate()?;
+
+        let body = xml::to_string(&self.status).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::LOGGING, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    error,
+    types::MetricsConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketMetricsConfiguration<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: MetricsConfiguration,
+}
+
+impl<'a> PutBucketMetricsConfiguration<'a> {
+    /// Create a new PutBucketMetricsConfiguration request which adds the
+    /// configuration to the bucket, or replaces the one with the same id.
+    /// The configuration is validated with `MetricsConfiguration::validate()`
+    /// before sending.
+    pub fn new(bucket: &'a str, config: MetricsConfiguration) -> Self {
+        PutBucketMetricsConfiguration { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketMetricsConfiguration<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.config.validate()?;
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::METRICS, None)
+            .query(QueryParameter::ID, Some(&self.config.id))
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    error,
+    types::NotificationConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketNotificationConfiguration<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: NotificationConfiguration,
+    skip_destination_validation: bool,
+}
+
+impl<'a> PutBucketNotificationConfiguration<'a> {
+    /// Create a new PutBucketNotificationConfiguration request which
+    /// replaces the notification configuration of the bucket. The
+    /// configuration is validated with `NotificationConfiguration::validate()`
+    /// before sending.
+    pub fn new(bucket: &'a str, config: NotificationConfiguration) -> Self {
+        PutBucketNotificationConfiguration {
+            bucket,
+            config,
+            skip_destination_validation: false,
+        }
+    }
+
+    /// Don't check that the destinations exist and that S3 may publish to them.
+    pub fn skip_destination_validation(mut self) -> Self {
+        self.skip_destination_validation = true;
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutBucketNotificationConfiguration<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.config.validate()?;
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        let skip = if self.skip_destination_validation {
+            Some("true")
+        } else {
+            None
+        };
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::NOTIFICATION, None)
+            .header(Headers::X_AMZ_SKIP_DESTINATION_VALIDATION, skip)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
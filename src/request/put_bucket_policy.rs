@@ -0,0 +1,85 @@
+use crate::{
+    error,
+    types::Policy,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketPolicy<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    policy: Policy,
+    confirm_remove_self_bucket_access: bool,
+}
+
+impl<'a> PutBucketPolicy<'a> {
+    /// Create a new PutBucketPolicy request which replaces the policy of the bucket
+    pub fn new(bucket: &'a str, policy: Policy) -> Self {
+        PutBucketPolicy {
+            bucket,
+            policy,
+            confirm_remove_self_bucket_access: false,
+        }
+    }
+
+    /// Confirm that the policy may remove the permission of the calling
+    /// account to change the policy again.
+    pub fn confirm_remove_self_bucket_access(mut self) -> Self {
+        self.confirm_remove_self_bucket_access = true;
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutBucketPolicy<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = serde_json::to_vec(&self.policy).map_err(error::Internal::from)?;
+
+        let confirm = if self.confirm_remove_self_bucket_access {
+            Some("true")
+        } else {
+            None
+        };
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::POLICY, None)
+            .header(Headers::X_AMZ_CONFIRM_REMOVE_SELF_BUCKET_ACCESS, confirm)
+            .body(body)
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    error,
+    types::ReplicationConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketReplication<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: ReplicationConfiguration,
+    token: Option<&'a str>,
+}
+
+impl<'a> PutBucketReplication<'a> {
+    /// Create a new PutBucketReplication request which replaces the
+    /// replication configuration of the bucket. The configuration is
+    /// validated with `ReplicationConfiguration::validate()` before sending.
+    pub fn new(bucket: &'a str, config: ReplicationConfiguration) -> Self {
+        PutBucketReplication {
+            bucket,
+            config,
+            token: None,
+        }
+    }
+
+    /// The object lock token, required when the source bucket has object
+    /// lock enabled.
+    pub fn token(mut self, token: &'a str) -> Self {
+        self.token = Some(token);
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutBucketReplication<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.config.validate()?;
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::REPLICATION, None)
+            .header(Headers::X_AMZ_BUCKET_OBJECT_LOCK_TOKEN, self.token)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
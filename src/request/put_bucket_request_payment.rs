@@ -0,0 +1,67 @@
+use crate::{
+    error,
+    types::RequestPaymentConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketRequestPayment<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: RequestPaymentConfiguration,
+}
+
+impl<'a> PutBucketRequestPayment<'a> {
+    /// Create a new PutBucketRequestPayment request which sets who pays for
+    /// requests to the bucket.
+    pub fn new(bucket: &'a str, config: RequestPaymentConfiguration) -> Self {
+        PutBucketRequestPayment { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketRequestPayment<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::REQUEST_PAYMENT, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    error,
+    types::{
+        TagSet,
+        Tagging,
+        MAX_BUCKET_TAGS,
+    },
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketTagging<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    tag_set: TagSet,
+}
+
+impl<'a> PutBucketTagging<'a> {
+    /// Create a new PutBucketTagging request which replaces the tag-set of
+    /// the bucket. A bucket can have at most 50 tags.
+    pub fn new(bucket: &'a str, tag_set: TagSet) -> Self {
+        PutBucketTagging { bucket, tag_set }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketTagging<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.tag_set.check_limit(MAX_BUCKET_TAGS)?;
+
+        let body = xml::to_string(&Tagging {
+            tag_set: self.tag_set,
+        })
+        .map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::TAGGING, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    error,
+    types::BucketVersioning,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    Mfa,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketVersioning<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    versioning: BucketVersioning,
+    mfa: Option<&'a Mfa>,
+}
+
+impl<'a> PutBucketVersioning<'a> {
+    /// Create a new PutBucketVersioning request which enables or suspends
+    /// versioning of the bucket.
+    pub fn new(bucket: &'a str, versioning: BucketVersioning) -> Self {
+        PutBucketVersioning {
+            bucket,
+            versioning,
+            mfa: None,
+        }
+    }
+
+    /// Authenticate with the given device, which is required to change
+    /// `mfa_delete`, and to change the status once MFA delete is enabled.
+    pub fn mfa(mut self, mfa: &'a Mfa) -> Self {
+        self.mfa = Some(mfa);
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutBucketVersioning<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&self.versioning).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::VERSIONING, None)
+            .header(Headers::X_AMZ_MFA, self.mfa.map(Mfa::header_value))
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
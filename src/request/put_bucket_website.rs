@@ -0,0 +1,70 @@
+use crate::{
+    error,
+    types::BucketWebsite,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutBucketWebsite<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: BucketWebsite,
+}
+
+impl<'a> PutBucketWebsite<'a> {
+    /// Create a new PutBucketWebsite request which replaces the website
+    /// configuration of the bucket. The configuration is validated with
+    /// `BucketWebsite::validate()` before sending.
+    pub fn new(bucket: &'a str, config: BucketWebsite) -> Self {
+        PutBucketWebsite { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutBucketWebsite<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.config.validate()?;
+
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::WEBSITE, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
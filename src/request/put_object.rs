@@ -1,5 +1,8 @@
 use crate::{
-    error,
+    cache,
+    encryption::EncryptionHeaders,
+    object_lock::ObjectLockHeaders,
+    types::MAX_OBJECT_TAGS,
     Acl,
     AwsRequest,
     AwsResponse,
@@ -8,44 +11,66 @@ use crate::{
     Gmt,
     Grantee,
     Headers,
-    Host,
-    OptionalGrants,
-    OptionalHeader,
-    PayloadHash,
+    LegalHoldStatus,
+    Metadata,
+    ObjectLockMode,
     Permission,
     Region,
-    SignRequest,
+    Request,
+    ServerSideEncryption,
     SigningKey,
+    SseCustomerKey,
+    TagSet,
 };
 use chrono::{
     DateTime,
     Utc,
 };
 use futures_core::future::BoxFuture;
-use http::method::Method;
 use hyper::{
     Body as HttpBody,
-    Request,
+    Method,
+    Request as HttpRequest,
     Response,
 };
 use url::Url;
 
-// PutObject requset Headers, this list *MUST* be in
-// sorted order as it is used in the signing process
-// of each request.
-const HEADERS: [&str; 11] = [
-    Headers::CONTENT_MD5,
-    Headers::EXPIRES,
-    Headers::HOST,
-    Headers::X_AMZ_ACL,
-    Headers::X_AMZ_CONTENT_SHA256,
-    Headers::X_AMZ_DATE,
-    Headers::X_AMZ_GRANT_WRITE,
-    Headers::X_AMZ_GRANT_READ,
-    Headers::X_AMZ_GRANT_WRITE_ACP,
-    Headers::X_AMZ_GRANT_READ_ACP,
-    Headers::X_AMZ_GRANT_FULL_CONTROL,
-];
+/// The standard headers stored with an object and returned when it is
+/// downloaded. Shared by `PutObject`, `CreateMultipartUpload` and `CopyObject`.
+#[derive(Default)]
+pub(crate) struct ContentHeaders<'a> {
+    pub(crate) cache: Vec<CacheControl<'a>>,
+    pub(crate) disposition: Option<&'a str>,
+    pub(crate) encoding: Option<&'a str>,
+    pub(crate) language: Option<&'a str>,
+    pub(crate) content_type: Option<&'a str>,
+    pub(crate) expires: Option<DateTime<Utc>>,
+}
+
+impl<'a> ContentHeaders<'a> {
+    /// Whether none of the headers were set.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+            && self.disposition.is_none()
+            && self.encoding.is_none()
+            && self.language.is_none()
+            && self.content_type.is_none()
+            && self.expires.is_none()
+    }
+
+    pub(crate) fn headers<'r>(&self, request: Request<'r>) -> Request<'r> {
+        request
+            .header(Headers::CACHE_CONTROL, cache::header_value(&self.cache))
+            .header(
+                Headers::CONTENT_DISPOSITION,
+                self.disposition.map(str::to_owned),
+            )
+            .header(Headers::CONTENT_ENCODING, self.encoding.map(str::to_owned))
+            .header(Headers::CONTENT_LANGUAGE, self.language.map(str::to_owned))
+            .header(Headers::CONTENT_TYPE, self.content_type.map(str::to_owned))
+            .header(Headers::EXPIRES, self.expires.map(|since| since.to_gmt()))
+    }
+}
 
 pub struct PutObject<'a> {
     /// Bucket name to which the PUT operation was initiated.
@@ -55,10 +80,14 @@ pub struct PutObject<'a> {
     pub key: &'a str,
 
     contents: Vec<u8>,
-    expires: Option<DateTime<Utc>>,
+    content: ContentHeaders<'a>,
     grants: Vec<(Permission, Grantee, &'a str)>,
-    cache: Option<CacheControl<'a>>,
     acl: Option<Acl>,
+    metadata: Metadata,
+    tagging: Option<TagSet>,
+    encryption: EncryptionHeaders<'a>,
+    sse_customer_key: Option<&'a SseCustomerKey>,
+    object_lock: ObjectLockHeaders,
 }
 
 impl<'a> PutObject<'a> {
@@ -68,22 +97,52 @@ impl<'a> PutObject<'a> {
             bucket,
             key,
             contents,
-            expires: None,
+            content: ContentHeaders::default(),
             grants: Vec::new(),
-            cache: None,
             acl: None,
+            metadata: Metadata::new(),
+            tagging: None,
+            encryption: EncryptionHeaders::default(),
+            sse_customer_key: None,
+            object_lock: ObjectLockHeaders::default(),
         }
     }
 
     /// The date and time at which the object is no longer cacheable.
     pub fn expires(mut self, expires: DateTime<Utc>) -> Self {
-        self.expires = Some(expires);
+        self.content.expires = Some(expires);
         self
     }
 
     /// Can be used to specify caching behavior along the request/reply chain.
+    /// Can be called multiple times to add several directives.
     pub fn cache(mut self, cache: CacheControl<'a>) -> Self {
-        self.cache = Some(cache);
+        self.content.cache.push(cache);
+        self
+    }
+
+    /// Specifies presentational information for the object, e.g.
+    /// `attachment; filename="report.pdf"`.
+    pub fn content_disposition(mut self, disposition: &'a str) -> Self {
+        self.content.disposition = Some(disposition);
+        self
+    }
+
+    /// Specifies what content encodings have been applied to the object, e.g. `gzip`.
+    pub fn content_encoding(mut self, encoding: &'a str) -> Self {
+        self.content.encoding = Some(encoding);
+        self
+    }
+
+    /// The language the content is in.
+    pub fn content_language(mut self, language: &'a str) -> Self {
+        self.content.language = Some(language);
+        self
+    }
+
+    /// A standard MIME type describing the format of the contents.
+    pub fn content_type(mut self, content_type: &'a str) -> Self {
+        self.content.content_type = Some(content_type);
         self
     }
 
@@ -93,6 +152,70 @@ impl<'a> PutObject<'a> {
         self.acl = Some(acl);
         self
     }
+
+    /// The server-side encryption algorithm used when storing the object.
+    pub fn server_side_encryption(mut self, encryption: ServerSideEncryption) -> Self {
+        self.encryption.server_side_encryption = Some(encryption);
+        self
+    }
+
+    /// The AWS KMS key ID to use for object encryption.
+    /// **Note:** This implies `ServerSideEncryption::AwsKms` unless another KMS algorithm was given
+    pub fn kms_key_id(mut self, key_id: &'a str) -> Self {
+        if self.encryption.server_side_encryption != Some(ServerSideEncryption::AwsKmsDsse) {
+            self.encryption.server_side_encryption = Some(ServerSideEncryption::AwsKms);
+        }
+        self.encryption.kms_key_id = Some(key_id);
+        self
+    }
+
+    /// Adds a key-value pair to the encryption context used with SSE-KMS.
+    pub fn encryption_context(mut self, key: &'a str, value: &'a str) -> Self {
+        self.encryption.context.insert(key, value);
+        self
+    }
+
+    /// Whether to use an S3 Bucket Key with SSE-KMS, reducing the requests made to AWS KMS.
+    pub fn bucket_key_enabled(mut self, enabled: bool) -> Self {
+        self.encryption.bucket_key_enabled = Some(enabled);
+        self
+    }
+
+    /// Encrypt the object with a customer-provided key (SSE-C).
+    /// **Note:** The request is refused unless the client uses https
+    pub fn sse_customer_key(mut self, key: &'a SseCustomerKey) -> Self {
+        self.sse_customer_key = Some(key);
+        self
+    }
+
+    /// Lock the object until `retain_until_date`. The bucket must have object lock enabled.
+    pub fn object_lock_retention(
+        mut self,
+        mode: ObjectLockMode,
+        retain_until_date: DateTime<Utc>,
+    ) -> Self {
+        self.object_lock.mode = Some(mode);
+        self.object_lock.retain_until_date = Some(retain_until_date);
+        self
+    }
+
+    /// Place a legal hold on the object. The bucket must have object lock enabled.
+    pub fn object_lock_legal_hold(mut self, status: LegalHoldStatus) -> Self {
+        self.object_lock.legal_hold = Some(status);
+        self
+    }
+
+    /// User-defined metadata to store with the object.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// The tag-set to add to the object. An object can have at most 10 tags.
+    pub fn tagging(mut self, tag_set: TagSet) -> Self {
+        self.tagging = Some(tag_set);
+        self
+    }
 }
 
 impl<'a> AwsRequest for PutObject<'a> {
@@ -104,24 +227,32 @@ impl<'a> AwsRequest for PutObject<'a> {
         access_key: AR,
         signing_key: &SigningKey,
         region: Region,
-    ) -> Result<Request<HttpBody>, Error> {
-        let cache = self.cache.map(|cache| {
-            let cache: String = cache.into();
-            cache
-        });
-
-        let request = Request::builder()
-            .method(Method::PUT)
-            .host(url, self.bucket, self.key, None)?
-            .optional_header(Headers::EXPIRES, &self.expires.map(|since| since.to_gmt()))?
-            .optional_header(Headers::CACHE_CONTROL, &cache)?
-            .optional_grants(self.acl, self.grants)?
-            .payload_hash(Some(&self.contents))?
-            .sign(&access_key.as_ref(), &signing_key, region.clone(), &HEADERS)?;
-
-        Ok(request
-            .body(HttpBody::from(self.contents))
-            .map_err(error::Internal::from)?)
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let tagging = self
+            .tagging
+            .map(|tag_set| {
+                tag_set.check_limit(MAX_OBJECT_TAGS)?;
+                Ok::<_, Error>(tag_set.header_value())
+            })
+            .transpose()?;
+
+        let request = Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .sse_customer_key(self.sse_customer_key)?
+            .grants(self.acl, self.grants)
+            .metadata(&self.metadata)
+            .header(Headers::X_AMZ_TAGGING, tagging);
+
+        let request = self.encryption.headers(request)?;
+        let request = self.object_lock.headers(request);
+
+        self.content
+            .headers(request)
+            .body(self.contents)
+            .build(access_key.as_ref(), signing_key)
     }
 
     fn into_response(
@@ -149,17 +280,17 @@ macro_rules! grant_method {
     };
 }
 
-grant_method!(PutObject<'a>, grant_read_id, WriteAcp, Id);
+grant_method!(PutObject<'a>, grant_read_id, Read, Id);
 grant_method!(PutObject<'a>, grant_read_acp_id, ReadAcp, Id);
-grant_method!(PutObject<'a>, grant_write_acp_id, Read, Id);
+grant_method!(PutObject<'a>, grant_write_acp_id, WriteAcp, Id);
 grant_method!(PutObject<'a>, grant_full_id, FullControl, Id);
 
-grant_method!(PutObject<'a>, grant_read_email, WriteAcp, Email);
+grant_method!(PutObject<'a>, grant_read_email, Read, Email);
 grant_method!(PutObject<'a>, grant_read_acp_email, ReadAcp, Email);
-grant_method!(PutObject<'a>, grant_write_acp_email, Read, Email);
+grant_method!(PutObject<'a>, grant_write_acp_email, WriteAcp, Email);
 grant_method!(PutObject<'a>, grant_full_email, FullControl, Email);
 
-grant_method!(PutObject<'a>, grant_read_uri, WriteAcp, Uri);
+grant_method!(PutObject<'a>, grant_read_uri, Read, Uri);
 grant_method!(PutObject<'a>, grant_read_acp_uri, ReadAcp, Uri);
-grant_method!(PutObject<'a>, grant_write_acp_uri, Read, Uri);
+grant_method!(PutObject<'a>, grant_write_acp_uri, WriteAcp, Uri);
 grant_method!(PutObject<'a>, grant_full_uri, FullControl, Uri);
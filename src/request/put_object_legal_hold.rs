@@ -0,0 +1,88 @@
+use crate::{
+    error,
+    types::LegalHold,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    LegalHoldStatus,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutObjectLegalHold<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to place or remove the legal hold on.
+    pub key: &'a str,
+
+    status: LegalHoldStatus,
+    version_id: Option<&'a str>,
+}
+
+impl<'a> PutObjectLegalHold<'a> {
+    /// Create a new PutObjectLegalHold request which places (`LegalHoldStatus::On`)
+    /// or removes (`LegalHoldStatus::Off`) a legal hold on the object.
+    pub fn new(bucket: &'a str, key: &'a str, status: LegalHoldStatus) -> Self {
+        PutObjectLegalHold {
+            bucket,
+            key,
+            status,
+            version_id: None,
+        }
+    }
+
+    /// The version of the object to place or remove the legal hold on.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutObjectLegalHold<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&LegalHold {
+            status: self.status,
+        })
+        .map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::LEGAL_HOLD, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    error,
+    types::ObjectLockConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutObjectLockConfiguration<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: ObjectLockConfiguration,
+    token: Option<&'a str>,
+}
+
+impl<'a> PutObjectLockConfiguration<'a> {
+    /// Create a new PutObjectLockConfiguration request which replaces the
+    /// object lock configuration of the bucket.
+    pub fn new(bucket: &'a str, config: ObjectLockConfiguration) -> Self {
+        PutObjectLockConfiguration {
+            bucket,
+            config,
+            token: None,
+        }
+    }
+
+    /// The token required to enable object lock on an existing bucket.
+    pub fn token(mut self, token: &'a str) -> Self {
+        self.token = Some(token);
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutObjectLockConfiguration<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::OBJECT_LOCK, None)
+            .header(Headers::X_AMZ_BUCKET_OBJECT_LOCK_TOKEN, self.token)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
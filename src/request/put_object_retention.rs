@@ -0,0 +1,101 @@
+use crate::{
+    error,
+    types::Retention,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    Headers,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutObjectRetention<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to set the retention of.
+    pub key: &'a str,
+
+    retention: Retention,
+    version_id: Option<&'a str>,
+    bypass_governance_retention: bool,
+}
+
+impl<'a> PutObjectRetention<'a> {
+    /// Create a new PutObjectRetention request which places the object
+    /// under the given retention.
+    pub fn new(bucket: &'a str, key: &'a str, retention: Retention) -> Self {
+        PutObjectRetention {
+            bucket,
+            key,
+            retention,
+            version_id: None,
+            bypass_governance_retention: false,
+        }
+    }
+
+    /// The version of the object to set the retention of.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+
+    /// Allow shortening or removing a `GOVERNANCE` mode retention.
+    /// Requires the `s3:BypassGovernanceRetention` permission.
+    pub fn bypass_governance_retention(mut self) -> Self {
+        self.bypass_governance_retention = true;
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutObjectRetention<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&self.retention).map_err(error::Internal::from)?;
+
+        let bypass = if self.bypass_governance_retention {
+            Some("true")
+        } else {
+            None
+        };
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::RETENTION, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .header(Headers::X_AMZ_BYPASS_GOVERNANCE_RETENTION, bypass)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    error,
+    types::{
+        TagSet,
+        Tagging,
+        MAX_OBJECT_TAGS,
+    },
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutObjectTagging<'a> {
+    /// The bucket name containing the object.
+    pub bucket: &'a str,
+
+    /// Key of the object to set the tag-set of.
+    pub key: &'a str,
+
+    tag_set: TagSet,
+    version_id: Option<&'a str>,
+}
+
+impl<'a> PutObjectTagging<'a> {
+    /// Create a new PutObjectTagging request which replaces the tag-set of
+    /// the object. An object can have at most 10 tags.
+    pub fn new(bucket: &'a str, key: &'a str, tag_set: TagSet) -> Self {
+        PutObjectTagging {
+            bucket,
+            key,
+            tag_set,
+            version_id: None,
+        }
+    }
+
+    /// The version of the object to set the tag-set of.
+    pub fn version_id(mut self, version_id: &'a str) -> Self {
+        self.version_id = Some(version_id);
+        self
+    }
+}
+
+impl<'a> AwsRequest for PutObjectTagging<'a> {
+    /// The version of the object the tag-set was added to
+    type Response = Option<String>;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        self.tag_set.check_limit(MAX_OBJECT_TAGS)?;
+
+        let body = xml::to_string(&Tagging {
+            tag_set: self.tag_set,
+        })
+        .map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .key(self.key)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::TAGGING, None)
+            .optional_query(QueryParameter::VERSION_ID, self.version_id)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            response.version_id()
+        })
+    }
+}
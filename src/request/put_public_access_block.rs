@@ -0,0 +1,67 @@
+use crate::{
+    error,
+    types::PublicAccessBlockConfiguration,
+    xml,
+    AwsRequest,
+    AwsResponse,
+    Error,
+    QueryParameter,
+    Region,
+    Request,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    Body as HttpBody,
+    Method,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+pub struct PutPublicAccessBlock<'a> {
+    /// The name of the bucket.
+    pub bucket: &'a str,
+
+    config: PublicAccessBlockConfiguration,
+}
+
+impl<'a> PutPublicAccessBlock<'a> {
+    /// Create a new PutPublicAccessBlock request which replaces the public
+    /// access block of the bucket.
+    pub fn new(bucket: &'a str, config: PublicAccessBlockConfiguration) -> Self {
+        PutPublicAccessBlock { bucket, config }
+    }
+}
+
+impl<'a> AwsRequest for PutPublicAccessBlock<'a> {
+    type Response = ();
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let body = xml::to_string(&self.config).map_err(error::Internal::from)?;
+
+        Request::new(Method::PUT)
+            .bucket(self.bucket)
+            .host(url)?
+            .region(region)
+            .query(QueryParameter::PUBLIC_ACCESS_BLOCK, None)
+            .body(body.into_bytes())
+            .build(access_key.as_ref(), signing_key)
+    }
+
+    fn into_response(
+        mut response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<Self::Response, Error>> {
+        Box::pin(async move {
+            response.error().await?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    sign_request,
+    AwsRequest,
+    Error,
+    Headers,
+    Region,
+    SigningKey,
+};
+use futures_core::future::BoxFuture;
+use hyper::{
+    header::HeaderValue,
+    Body as HttpBody,
+    Request as HttpRequest,
+    Response,
+};
+use url::Url;
+
+/// Sends the wrapped request with `x-amz-request-payer: requester`,
+/// acknowledging that the requester is charged for requests to, and data
+/// transferred from, a requester pays bucket. Without it S3 rejects every
+/// request not made by the bucket owner with `403 Forbidden`.
+///
+/// To send every request this way use `Client::set_requester_pays()`
+/// instead.
+pub struct RequesterPays<T: AwsRequest>(pub T);
+
+impl<T: AwsRequest> AwsRequest for RequesterPays<T> {
+    type Response = T::Response;
+
+    fn into_request<AR: AsRef<str>>(
+        self,
+        url: Url,
+        access_key: AR,
+        signing_key: &SigningKey,
+        region: Region,
+    ) -> Result<HttpRequest<HttpBody>, Error> {
+        let region_name = region.to_string();
+        let mut request = self
+            .0
+            .into_request(url, access_key.as_ref(), signing_key, region)?;
+
+        set_requester_pays(&mut request);
+        sign_request::sign(&mut request, access_key.as_ref(), signing_key, &region_name)?;
+        Ok(request)
+    }
+
+    fn into_response(
+        response: Response<HttpBody>,
+    ) -> BoxFuture<'static, Result<T::Response, Error>> {
+        T::into_response(response)
+    }
+}
+
+/// Adds the requester pays header to a built request, which has to be
+/// signed again afterwards.
+pub(crate) fn set_requester_pays(request: &mut HttpRequest<HttpBody>) {
+    request.headers_mut().insert(
+        Headers::X_AMZ_REQUEST_PAYER,
+        HeaderValue::from_static("requester"),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Request;
+    use chrono::Utc;
+    use hyper::Method;
+
+    #[test]
+    fn header_is_signed() {
+        let signing_key = SigningKey::from_date("secret", &Utc::now(), Region::UsEast1);
+        let mut request = Request::new(Method::GET)
+            .bucket("bucket")
+            .key("key")
+            .host(Url::parse("http://localhost").unwrap())
+            .unwrap()
+            .region(Region::UsEast1)
+            .build("access", &signing_key)
+            .unwrap();
+
+        for _ in 0..2 {
+            set_requester_pays(&mut request);
+            sign_request::sign(&mut request, "access", &signing_key, "us-east-1").unwrap();
+        }
+
+        let headers = request.headers();
+        assert_eq!(headers.get_all(Headers::AUTHORIZATION).iter().count(), 1);
+        assert!(headers[Headers::AUTHORIZATION]
+            .to_str()
+            .unwrap()
+            .contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-request-payer,"));
+    }
+}
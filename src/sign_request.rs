@@ -1,162 +1,156 @@
 use crate::{
     error,
     Headers,
-    Region,
     SigningKey,
 };
-use chrono::{
-    NaiveDateTime,
-    Utc,
+use chrono::Utc;
+use hyper::{
+    header::HeaderValue,
+    Body,
+    HeaderMap,
+    Request,
 };
-use http::request::Builder;
-use hyper::header::HeaderValue;
 use sha2::{
     Digest,
     Sha256,
 };
 
-pub trait SignRequest {
-    fn sign<T: AsRef<str>>(
-        self,
-        access_key: T,
-        signing_key: &SigningKey,
-        region: Region,
-        headers: &'static [&'static str],
-    ) -> Result<Self, error::Error>
-    where
-        Self: Sized;
+/// Sign a request built by `Request::build()`, replacing any previous
+/// signature so that a request can be changed and signed again. Every header
+/// of the request is signed, and the query is expected to be in canonical
+/// form already.
+pub(crate) fn sign(
+    request: &mut Request<Body>,
+    access_key: &str,
+    signing_key: &SigningKey,
+    region: &str,
+) -> Result<(), error::Error> {
+    // All requets should have date
+    // Formatting date in rfc1123 was rejected by minio even though it says to use that format
+    // instead using format from aws examples YYYYMMDDTHHMMSSZ
+    let date = Utc::now();
+    let headers = request.headers_mut();
+    headers.remove(Headers::AUTHORIZATION);
+    headers.insert(
+        Headers::X_AMZ_DATE,
+        HeaderValue::from_str(&date.format("%Y%m%dT%H%M%SZ").to_string())
+            .map_err(error::Internal::from)?,
+    );
+
+    let mut canonical: Vec<u8> = Vec::new();
+
+    // Request Method
+    canonical.extend_from_slice(request.method().as_str().as_bytes());
+    canonical.push(b'\n');
+
+    // Request Uri
+    canonical.extend_from_slice(request.uri().path().as_bytes());
+    canonical.push(b'\n');
+
+    // Request Query Parameters
+    canonical.extend_from_slice(request.uri().query().unwrap_or("").as_bytes());
+    canonical.push(b'\n');
+
+    // Request Headers
+    let (headers, signed) = canonical_headers(request.headers());
+    canonical.extend_from_slice(&headers);
+    canonical.push(b'\n');
+
+    // Signed Headers
+    canonical.extend_from_slice(signed.as_bytes());
+    canonical.push(b'\n');
+
+    // X_AMZ_CONTENT_SHA256 should ALWAYS be set
+    canonical.extend_from_slice(
+        request
+            .headers()
+            .get(Headers::X_AMZ_CONTENT_SHA256)
+            .ok_or(error::Internal::Message(
+                "Payload hash was not set before signing".to_string(),
+            ))?
+            .as_bytes(),
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.input(canonical);
+
+    let hash = hex::encode(hasher.result().as_slice());
+
+    let scope = format!(
+        "{date}/{region}/s3/aws4_request",
+        date = date.format("%Y%m%d"),
+        region = region
+    );
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{hash}",
+        timestamp = date.format("%Y%m%dT%H%M%SZ"),
+        scope = scope,
+        hash = hash
+    );
+
+    let sig = signing_key.sign(string_to_sign);
+
+    let auth = format!(
+        "AWS4-HMAC-SHA256 Credential={access_key}/{scope},SignedHeaders={signed_headers},Signature={signature}",
+        access_key = access_key,
+        scope = scope,
+        signed_headers = signed,
+        signature = sig
+    );
+
+    request.headers_mut().insert(
+        Headers::AUTHORIZATION,
+        HeaderValue::from_str(&auth).map_err(error::Internal::from)?,
+    );
+
+    Ok(())
 }
 
-impl SignRequest for Builder {
-    fn sign<T: AsRef<str>>(
-        mut self,
-        access_key: T,
-        signing_key: &SigningKey,
-        region: Region,
-        headers: &'static [&'static str],
-    ) -> Result<Self, error::Error>
-    where
-        Self: Sized,
-    {
-        // All requets should have date
-        // Formatting date in rfc1123 was rejected by minio even though it says to use that format
-        // instead using format from aws examples YYYYMMDDTHHMMSSZ
-        let date = &format!("{}", Utc::now().format("%Y%m%dT%H%M%SZ"));
-        self = self.header(
-            Headers::X_AMZ_DATE,
-            HeaderValue::from_str(&date).map_err(error::Internal::from)?,
-        );
-
-        let mut canonical: Vec<u8> = Vec::new();
-        let mut signed: Vec<&str> = Vec::new();
-
-        // Request Method
-        canonical.extend_from_slice(
-            &self
-                .method_ref()
-                .ok_or(error::Internal::MethodNotSet)?
-                .as_str()
-                .as_bytes(),
-        );
-        canonical.push(b'\n');
-
-        // Resource
-        canonical.extend_from_slice(
-            &self
-                .uri_ref()
-                .ok_or(error::Internal::UriNotSet)?
-                .path()
-                .as_bytes(),
-        );
-        canonical.push(b'\n');
-
-        if let Some(params) = self.uri_ref().ok_or(error::Internal::UriNotSet)?.query() {
-            canonical.extend_from_slice(&params.as_bytes());
-        }
-        canonical.push(b'\n');
-
-        for header in headers {
-            if let Some(value) = self
-                .headers_ref()
-                .ok_or(error::Internal::HeadersNotSet)?
-                .get(*header)
-            {
-                canonical.extend_from_slice(&header.as_bytes());
-                canonical.push(b':');
-                canonical.extend_from_slice(&value.as_bytes());
-                canonical.push(b'\n');
-
-                signed.push(header)
+// The canonical headers, one line for each header ordered by name, and the
+// list of signed headers. A header sent more than once is signed as a single
+// line with its values joined by commas.
+fn canonical_headers(headers: &HeaderMap) -> (Vec<u8>, String) {
+    let mut names: Vec<&str> = headers.keys().map(|name| name.as_str()).collect();
+    names.sort_unstable();
+
+    let mut canonical = Vec::new();
+    for name in names.iter() {
+        canonical.extend_from_slice(name.as_bytes());
+        canonical.push(b':');
+        for (i, value) in headers.get_all(*name).iter().enumerate() {
+            if i > 0 {
+                canonical.push(b',');
             }
+            canonical.extend_from_slice(value.as_bytes());
         }
-
-        // End of Headers
-        canonical.push(b'\n');
-
-        let signed = signed.join(";");
-
-        // Signed Headers
-        canonical.extend_from_slice(&signed.as_bytes());
-
         canonical.push(b'\n');
+    }
 
-        // X_AMZ_CONTENT_SHA256 should ALWAYS be set
-        if let Some(header) = self
-            .headers_ref()
-            .ok_or(error::Error::from(error::Internal::HeadersNotSet))?
-            .get(Headers::X_AMZ_CONTENT_SHA256)
-        {
-            canonical.extend_from_slice(&header.as_bytes());
-        }
-
-        println!("{}", String::from_utf8_lossy(&canonical));
-
-        let mut hasher = Sha256::new();
-        hasher.input(canonical);
-
-        let hash = hex::encode(hasher.result().as_slice());
-
-        let region: String = region.into();
-
-        let date = self
-            .headers_ref()
-            .ok_or(error::Error::from(error::Internal::HeadersNotSet))?
-            .get(Headers::X_AMZ_DATE)
-            .ok_or(error::Error::from(
-                error::Internal::DateHeaderUnsetWhenSigning,
-            ))?
-            .to_str()
-            .map_err(error::Internal::from)?;
-
-        let date =
-            NaiveDateTime::parse_from_str(date, "%Y%m%dT%H%M%SZ").map_err(error::Internal::from)?;
-
-        let scope = format!(
-            "{date}/{region}/s3/aws4_request",
-            date = date.format("%Y%m%d"),
-            region = region
-        );
-
-        let string_to_sign = format!(
-            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{hash}",
-            timestamp = date.format("%Y%m%dT%H%M%SZ"),
-            scope = scope,
-            hash = hash
-        );
-
-        let sig = signing_key.sign(string_to_sign);
+    (canonical, names.join(";"))
+}
 
-        let auth = format!(
-            "AWS4-HMAC-SHA256 Credential={access_key}/{scope},SignedHeaders={signed_headers},Signature={signature}",
-            access_key = access_key.as_ref(),
-            scope = scope,
-            signed_headers = signed,
-            signature = sig
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_headers_are_joined() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amz-date", HeaderValue::from_static("20200101T000000Z"));
+        headers.append("x-amz-meta-tag", HeaderValue::from_static("a"));
+        headers.append("x-amz-meta-tag", HeaderValue::from_static("b"));
+        headers.insert("host", HeaderValue::from_static("bucket.s3.amazonaws.com"));
+
+        let (canonical, signed) = canonical_headers(&headers);
+        assert_eq!(
+            String::from_utf8(canonical).unwrap(),
+            concat!(
+                "host:bucket.s3.amazonaws.com\n",
+                "x-amz-date:20200101T000000Z\n",
+                "x-amz-meta-tag:a,b\n"
+            )
         );
-
-        Ok(self.header(
-            Headers::AUTHORIZATION,
-            HeaderValue::from_str(&auth).map_err(error::Internal::from)?,
-        ))
+        assert_eq!(signed, "host;x-amz-date;x-amz-meta-tag");
     }
 }
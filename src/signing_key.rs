@@ -16,35 +16,47 @@ pub struct SigningKey {
 
 impl SigningKey {
     pub fn from_date(secret: &str, date: &DateTime<Utc>, region: Region) -> Self {
-        let key = SigningKey::generate_key(secret, &date, region);
-        Self {
-            key,
-            // date,
-        }
+        DateKey::new(secret, date).signing_key(&region)
     }
 
-    /// Generates a new key from a secret, date, and a region
-    fn generate_key(secret: &str, date: &DateTime<Utc>, region: Region) -> Key {
-        let region: String = region.into();
+    /// Signs the string and returns the hex encoded signature
+    #[inline(always)]
+    pub fn sign<T: AsRef<str>>(&self, s: T) -> String {
+        let tag = hmac::sign(&self.key, s.as_ref().as_bytes());
+        hex::encode(tag.as_ref())
+    }
+}
+
+/// The first step of deriving a signing key, which only depends on the
+/// secret and the date. The client keeps it to derive keys for the regions
+/// of other buckets without keeping the secret itself.
+#[derive(Debug)]
+pub(crate) struct DateKey {
+    key: Key,
+}
+
+impl DateKey {
+    pub(crate) fn new(secret: &str, date: &DateTime<Utc>) -> Self {
         let date = format!("{}", date.date().format("%Y%m%d"));
 
         let tag = hmac::sign(
             &Key::new(hmac::HMAC_SHA256, &format!("AWS4{}", secret).as_bytes()),
             date.as_bytes(),
         );
-        let tag = hmac::sign(
-            &Key::new(hmac::HMAC_SHA256, tag.as_ref()),
-            region.as_bytes(),
-        );
+
+        DateKey {
+            key: Key::new(hmac::HMAC_SHA256, tag.as_ref()),
+        }
+    }
+
+    /// Generates the signing key of a region
+    pub(crate) fn signing_key(&self, region: &Region) -> SigningKey {
+        let tag = hmac::sign(&self.key, region.as_str().as_bytes());
         let tag = hmac::sign(&Key::new(hmac::HMAC_SHA256, tag.as_ref()), b"s3");
         let tag = hmac::sign(&Key::new(hmac::HMAC_SHA256, tag.as_ref()), b"aws4_request");
-        Key::new(hmac::HMAC_SHA256, tag.as_ref())
-    }
 
-    /// Signs the string and returns the hex encoded signature
-    #[inline(always)]
-    pub fn sign<T: AsRef<str>>(&self, s: T) -> String {
-        let tag = hmac::sign(&self.key, s.as_ref().as_bytes());
-        hex::encode(tag.as_ref())
+        SigningKey {
+            key: Key::new(hmac::HMAC_SHA256, tag.as_ref()),
+        }
     }
 }
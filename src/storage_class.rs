@@ -1,8 +1,16 @@
-use crate::error;
-use serde::Deserialize;
+use crate::{
+    error,
+    xml,
+};
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+};
 use std::str::FromStr;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 /// Provides storage class information of the object.
 /// Default storage class is `STANDARD`
 pub enum StorageClass {
@@ -25,8 +33,29 @@ impl FromStr for StorageClass {
             "STANDARD_IA" => Ok(Self::StandardIa),
             "ONEZONE_IA" => Ok(Self::OnezoneIa),
             "INTELLIGENT_TIERING" => Ok(Self::IntelligentTiering),
+            "GLACIER" => Ok(Self::Glacier),
             "DEEP_ARCHIVE" => Ok(Self::DeepArchive),
             _ => Err(error::Error::from(error::Internal::ParseStorageClassError)),
         }
     }
 }
+
+impl<'de> Deserialize<'de> for StorageClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        xml::from_text(deserializer)
+    }
+}
+
+impl From<StorageClass> for &'static str {
+    fn from(storage_class: StorageClass) -> &'static str {
+        match storage_class {
+            StorageClass::Standard => "STANDARD",
+            StorageClass::ReducedRedundancy => "REDUCED_REDUNDANCY",
+            StorageClass::StandardIa => "STANDARD_IA",
+            StorageClass::OnezoneIa => "ONEZONE_IA",
+            StorageClass::IntelligentTiering => "INTELLIGENT_TIERING",
+            StorageClass::Glacier => "GLACIER",
+            StorageClass::DeepArchive => "DEEP_ARCHIVE",
+        }
+    }
+}
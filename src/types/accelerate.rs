@@ -0,0 +1,64 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The transfer acceleration configuration of a bucket. The status is
+/// `None` if acceleration was never configured.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "AccelerateConfiguration")]
+#[serde(rename_all = "PascalCase")]
+pub struct AccelerateConfiguration {
+    pub status: Option<BucketAccelerateStatus>,
+}
+
+impl AccelerateConfiguration {
+    pub fn enabled() -> Self {
+        AccelerateConfiguration {
+            status: Some(BucketAccelerateStatus::Enabled),
+        }
+    }
+
+    pub fn suspended() -> Self {
+        AccelerateConfiguration {
+            status: Some(BucketAccelerateStatus::Suspended),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status == Some(BucketAccelerateStatus::Enabled)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BucketAccelerateStatus {
+    Enabled,
+    Suspended,
+}
+
+impl_text_enum!(BucketAccelerateStatus, "accelerate status", { Enabled, Suspended });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let string = xml::to_string(&AccelerateConfiguration::enabled()).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<AccelerateConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Status>Enabled</Status></AccelerateConfiguration>"
+            )
+        );
+
+        let parsed: AccelerateConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert!(parsed.is_enabled());
+
+        let never: AccelerateConfiguration =
+            quick_xml::de::from_str("<AccelerateConfiguration></AccelerateConfiguration>").unwrap();
+        assert_eq!(never.status, None);
+    }
+}
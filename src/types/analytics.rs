@@ -1,89 +1,256 @@
-use super::Tag;
-use serde::Deserialize;
+use super::{
+    FilterElement,
+    TagSet,
+};
+use crate::Error;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucaetAnalyticsConfig")]
+/// A storage class analysis configuration of a bucket, which observes the
+/// access patterns of the filtered objects and optionally exports the
+/// results as a daily csv report.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "AnalyticsConfiguration")]
 #[serde(rename_all = "PascalCase")]
-pub struct BucketAnalytics {
-    #[serde(rename = "ID")]
-    id: String,
+pub struct AnalyticsConfiguration {
+    pub id: String,
+    pub filter: Option<AnalyticsFilter>,
+    pub storage_class_analysis: StorageClassAnalysis,
+}
+
+impl AnalyticsConfiguration {
+    /// Create a new configuration analysing every object of the bucket
+    /// without exporting the results.
+    pub fn new<T: Into<String>>(id: T) -> Self {
+        AnalyticsConfiguration {
+            id: id.into(),
+            filter: None,
+            storage_class_analysis: StorageClassAnalysis::default(),
+        }
+    }
+
+    /// Only analyse the objects matching `filter`.
+    pub fn filter(mut self, filter: AnalyticsFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Export the results of the analysis to `destination`.
+    pub fn export(mut self, destination: AnalyticsS3BucketDestination) -> Self {
+        self.storage_class_analysis.data_export = Some(StorageClassAnalysisDataExport {
+            output_schema_version: OUTPUT_SCHEMA_VERSION.to_owned(),
+            destination: AnalyticsExportDestination {
+                s3_bucket_destination: destination,
+            },
+        });
+        self
+    }
 
-    filter: AnalyticsFilter,
-    storage_class_analysis: StorageClassAnalysis,
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketAnalyticsConfiguration` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !super::is_valid_configuration_id(&self.id) {
+            return Err(Error::InvalidAnalytics(format!(
+                "id {:?} must be between 1 and {} letters, numbers, `-`, `_` or `.`",
+                self.id,
+                super::MAX_CONFIGURATION_ID_LENGTH
+            )));
+        }
+
+        if let Some(ref export) = self.storage_class_analysis.data_export {
+            let destination = &export.destination.s3_bucket_destination;
+
+            if !destination.bucket.starts_with("arn:") {
+                return Err(Error::InvalidAnalytics(format!(
+                    "destination must be a bucket arn, found {:?}",
+                    destination.bucket
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize)]
+// The only version of the export schema S3 supports
+const OUTPUT_SCHEMA_VERSION: &str = "V_1";
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct StorageClassAnalysis {
-    data_export: Option<StorageClassAnalysisDataExport>,
+    pub data_export: Option<StorageClassAnalysisDataExport>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct StorageClassAnalysisDataExport {
-    destination: AnalyticsExportDestination,
-    output_schema_version: String,
+    pub output_schema_version: String,
+    pub destination: AnalyticsExportDestination,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct AnalyticsFilter {
-    and: Option<AndOperator>,
-    prefix: Option<String>,
-    tag: Option<Tag>,
+pub struct AnalyticsExportDestination {
+    #[serde(rename = "S3BucketDestination")]
+    pub s3_bucket_destination: AnalyticsS3BucketDestination,
 }
 
-#[derive(Debug, Deserialize)]
+/// The bucket analysis results are exported to.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct AndOperator {
-    prefix: Option<String>,
+pub struct AnalyticsS3BucketDestination {
+    pub format: AnalyticsFormat,
+
+    /// The account expected to own the destination bucket.
+    pub bucket_account_id: Option<String>,
+
+    /// The arn of the destination bucket.
+    pub bucket: String,
 
-    #[serde(rename = "Tag")]
-    tags: Vec<Tag>,
+    pub prefix: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct StorageClassAnalytics {
-    data_export: Option<StorageClassAnalyticsDataExport>,
+impl AnalyticsS3BucketDestination {
+    /// Create a new destination for the bucket with the given arn, e.g.
+    /// `arn:aws:s3:::reports`.
+    pub fn new<T: Into<String>>(bucket_arn: T) -> Self {
+        AnalyticsS3BucketDestination {
+            format: AnalyticsFormat::Csv,
+            bucket_account_id: None,
+            bucket: bucket_arn.into(),
+            prefix: None,
+        }
+    }
+
+    pub fn bucket_account_id<T: Into<String>>(mut self, account_id: T) -> Self {
+        self.bucket_account_id = Some(account_id.into());
+        self
+    }
+
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct StorageClassAnalyticsDataExport {
-    destination: AnalyticsExportDestination,
-    output_schema_version: String,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AnalyticsFormat {
+    Csv,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct AnalyticsExportDestination {
-    #[serde(rename = "S3BucketDestination")]
-    bucket_destination: AnalyticsS3BucketDestination,
+impl_text_enum!(AnalyticsFormat, "analytics format", { Csv = "CSV" });
+
+/// Selects the objects a storage class analysis applies to. Objects must
+/// match the prefix and every tag.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "FilterElement", into = "FilterElement")]
+pub struct AnalyticsFilter {
+    pub prefix: Option<String>,
+    pub tags: TagSet,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct AnalyticsS3BucketDestination {
-    bucket: String,
-    bucket_account_id: Option<String>,
-    format: String,
-    prefix: Option<String>,
+impl AnalyticsFilter {
+    /// Create a new filter which matches every object
+    pub fn new() -> Self {
+        AnalyticsFilter::default()
+    }
+
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only match objects with this tag.
+    pub fn tag<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Result<Self, Error> {
+        self.tags.insert(key, value)?;
+        Ok(self)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "ListBucketAnalyticsConfiguration")]
-#[serde(rename_all = "PascalCase")]
-pub struct BucketAnalyticsConfiguration {
-    analytics_configuration: Vec<AnalyticsConfiguration>,
-    continuation_token: String,
-    is_truncated: bool,
-    next_continuation_token: String,
+impl From<FilterElement> for AnalyticsFilter {
+    fn from(element: FilterElement) -> Self {
+        let (prefix, tags, _) = element.into_parts();
+        AnalyticsFilter { prefix, tags }
+    }
+}
+
+impl From<AnalyticsFilter> for FilterElement {
+    fn from(filter: AnalyticsFilter) -> Self {
+        FilterElement::new(filter.prefix, filter.tags, None)
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// A page of `ListBucketAnalyticsConfigurations`.
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct AnalyticsConfiguration {
-    filter: Option<AnalyticsFilter>,
-    id: String,
-    storage_class_analysis: StorageClassAnalysis,
+pub struct ListBucketAnalyticsConfigurationResult {
+    #[serde(rename = "AnalyticsConfiguration", default)]
+    pub analytics_configurations: Vec<AnalyticsConfiguration>,
+
+    pub continuation_token: Option<String>,
+    pub is_truncated: bool,
+
+    /// The token to request the next page with, if the list is truncated.
+    pub next_continuation_token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = AnalyticsConfiguration::new("cold-data")
+            .filter(
+                AnalyticsFilter::new()
+                    .prefix("logs/")
+                    .tag("team", "storage")
+                    .unwrap(),
+            )
+            .export(
+                AnalyticsS3BucketDestination::new("arn:aws:s3:::reports")
+                    .bucket_account_id("123456789012")
+                    .prefix("analytics"),
+            );
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<AnalyticsConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Id>cold-data</Id><Filter><And><Prefix>logs/</Prefix>",
+                "<Tag><Key>team</Key><Value>storage</Value></Tag></And></Filter>",
+                "<StorageClassAnalysis><DataExport><OutputSchemaVersion>V_1</OutputSchemaVersion>",
+                "<Destination><S3BucketDestination><Format>CSV</Format>",
+                "<BucketAccountId>123456789012</BucketAccountId>",
+                "<Bucket>arn:aws:s3:::reports</Bucket><Prefix>analytics</Prefix>",
+                "</S3BucketDestination></Destination></DataExport></StorageClassAnalysis>",
+                "</AnalyticsConfiguration>"
+            )
+        );
+
+        let parsed: AnalyticsConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(parsed.validate().is_ok());
+
+        let list: ListBucketAnalyticsConfigurationResult = quick_xml::de::from_str(concat!(
+            "<ListBucketAnalyticsConfigurationResult>",
+            "<AnalyticsConfiguration><Id>all</Id><StorageClassAnalysis/></AnalyticsConfiguration>",
+            "<IsTruncated>true</IsTruncated><NextContinuationToken>next</NextContinuationToken>",
+            "</ListBucketAnalyticsConfigurationResult>"
+        ))
+        .unwrap();
+        assert_eq!(
+            list.analytics_configurations,
+            vec![AnalyticsConfiguration::new("all")]
+        );
+        assert_eq!(list.next_continuation_token.as_deref(), Some("next"));
+    }
 }
@@ -1,18 +1,404 @@
-use serde::Deserialize;
+use crate::{
+    Error,
+    Headers,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketCorsOutput")]
-pub struct BucketCors {
-    #[serde(rename = "CORSRule")]
-    rules: Vec<CorsRule>,
+// Limits enforced by S3 on a cors configuration
+const MAX_RULES: usize = 100;
+
+/// The cors configuration of a bucket. A request is allowed by the first
+/// rule matching its origin, method and headers.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "CORSConfiguration")]
+pub struct CorsConfiguration {
+    #[serde(rename = "CORSRule", default)]
+    pub rules: Vec<CorsRule>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+impl CorsConfiguration {
+    /// Create a new empty cors configuration
+    pub fn new() -> Self {
+        CorsConfiguration::default()
+    }
+
+    /// Add a rule to the configuration.
+    pub fn rule(mut self, rule: CorsRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketCors` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.rules.is_empty() || self.rules.len() > MAX_RULES {
+            return Err(Error::InvalidCors(format!(
+                "a configuration needs between 1 and {} rules, found {}",
+                MAX_RULES,
+                self.rules.len()
+            )));
+        }
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            if rule.allowed_origins.is_empty() || rule.allowed_methods.is_empty() {
+                return Err(Error::InvalidCors(format!(
+                    "rule {} needs at least one allowed origin and method",
+                    index
+                )));
+            }
+
+            let wildcards = rule
+                .allowed_origins
+                .iter()
+                .chain(rule.allowed_headers.iter())
+                .find(|pattern| pattern.matches('*').count() > 1);
+            if let Some(pattern) = wildcards {
+                return Err(Error::InvalidCors(format!(
+                    "{:?} of rule {} may contain at most one wildcard",
+                    pattern, index
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate a preflight `OPTIONS` request the same way S3 does,
+    /// returning the rule that allows it and the cors headers S3 would
+    /// respond with. `method` and `headers` are those of the actual request,
+    /// as given by `Access-Control-Request-Method` and
+    /// `Access-Control-Request-Headers`. `None` means the request is denied.
+    pub fn evaluate_preflight<'c>(
+        &'c self,
+        origin: &str,
+        method: &str,
+        headers: &[&str],
+    ) -> Option<CorsResponse<'c>> {
+        self.respond(origin, method, headers)
+    }
+
+    /// Evaluate an actual cross-origin request the same way S3 does. Only
+    /// its origin and method are checked, as its headers were already
+    /// allowed by the preflight request if one was needed.
+    pub fn evaluate<'c>(&'c self, origin: &str, method: &str) -> Option<CorsResponse<'c>> {
+        self.respond(origin, method, &[])
+    }
+
+    fn respond<'c>(
+        &'c self,
+        origin: &str,
+        method: &str,
+        headers: &[&str],
+    ) -> Option<CorsResponse<'c>> {
+        let rule = self.rules.iter().find(|rule| {
+            rule.allowed_origins
+                .iter()
+                .any(|pattern| wildcard_match(pattern, origin, false))
+                && rule
+                    .allowed_methods
+                    .iter()
+                    .any(|allowed| <&'static str>::from(*allowed) == method)
+                && headers.iter().all(|header| {
+                    rule.allowed_headers
+                        .iter()
+                        .any(|pattern| wildcard_match(pattern, header, true))
+                })
+        })?;
+
+        let mut response = Vec::new();
+
+        // Only a rule allowing every origin responds with a wildcard, any
+        // other rule echoes the origin and allows credentials
+        if rule.allowed_origins.iter().any(|pattern| pattern == "*") {
+            response.push((Headers::ACCESS_CONTROL_ALLOW_ORIGIN, "*".to_owned()));
+        } else {
+            response.push((Headers::ACCESS_CONTROL_ALLOW_ORIGIN, origin.to_owned()));
+            response.push((Headers::ACCESS_CONTROL_ALLOW_CREDENTIALS, "true".to_owned()));
+        }
+
+        response.push((
+            Headers::ACCESS_CONTROL_ALLOW_METHODS,
+            rule.allowed_methods
+                .iter()
+                .map(|method| <&'static str>::from(*method))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ));
+
+        if !headers.is_empty() {
+            response.push((
+                Headers::ACCESS_CONTROL_ALLOW_HEADERS,
+                headers
+                    .iter()
+                    .map(|header| header.to_ascii_lowercase())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ));
+        }
+
+        if !rule.expose_headers.is_empty() {
+            response.push((
+                Headers::ACCESS_CONTROL_EXPOSE_HEADERS,
+                rule.expose_headers.join(", "),
+            ));
+        }
+
+        if let Some(max_age) = rule.max_age_seconds {
+            response.push((Headers::ACCESS_CONTROL_MAX_AGE, max_age.to_string()));
+        }
+
+        response.push((
+            Headers::VARY,
+            "Origin, Access-Control-Request-Headers, Access-Control-Request-Method".to_owned(),
+        ));
+
+        Some(CorsResponse {
+            rule,
+            headers: response,
+        })
+    }
+}
+
+/// The outcome of `CorsConfiguration::evaluate()` or
+/// `CorsConfiguration::evaluate_preflight()` for an allowed request.
+#[derive(Debug, Clone)]
+pub struct CorsResponse<'c> {
+    /// The first rule that allows the request.
+    pub rule: &'c CorsRule,
+
+    /// The cors headers of the response, in the order S3 sends them.
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl<'c> CorsResponse<'c> {
+    /// Get the value of a response header.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Matches `value` against a pattern containing at most one `*`.
+fn wildcard_match(pattern: &str, value: &str, ignore_case: bool) -> bool {
+    let (pattern, value) = if ignore_case {
+        (pattern.to_ascii_lowercase(), value.to_ascii_lowercase())
+    } else {
+        (pattern.to_owned(), value.to_owned())
+    };
+
+    match pattern.find('*') {
+        Some(index) => {
+            let (prefix, suffix) = (&pattern[..index], &pattern[index + 1..]);
+            value.len() >= prefix.len() + suffix.len()
+                && value.starts_with(prefix)
+                && value.ends_with(suffix)
+        }
+        None => pattern == value,
+    }
+}
+
+/// A set of origins and methods allowed to access the bucket.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct CorsRule {
-    allowed_headers: Vec<String>,
-    allowed_methods: Vec<String>,
-    allowed_origins: Vec<String>,
-    exposed_headers: Vec<String>,
-    max_age_seconds: Option<u64>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+
+    #[serde(rename = "AllowedHeader", default)]
+    pub allowed_headers: Vec<String>,
+
+    #[serde(rename = "AllowedMethod", default)]
+    pub allowed_methods: Vec<CorsMethod>,
+
+    #[serde(rename = "AllowedOrigin", default)]
+    pub allowed_origins: Vec<String>,
+
+    #[serde(rename = "ExposeHeader", default)]
+    pub expose_headers: Vec<String>,
+
+    #[serde(rename = "MaxAgeSeconds")]
+    pub max_age_seconds: Option<u32>,
+}
+
+impl CorsRule {
+    /// Create a new rule which doesn't allow anything yet
+    pub fn new() -> Self {
+        CorsRule::default()
+    }
+
+    /// A unique identifier for the rule.
+    pub fn id<T: Into<String>>(mut self, id: T) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// An origin allowed to make requests, e.g. `https://*.example.com`.
+    /// The origin may contain a single `*` wildcard.
+    pub fn allowed_origin<T: Into<String>>(mut self, origin: T) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    pub fn allowed_method(mut self, method: CorsMethod) -> Self {
+        self.allowed_methods.push(method);
+        self
+    }
+
+    /// A header allowed in the `Access-Control-Request-Headers` of a
+    /// preflight request. The header may contain a single `*` wildcard.
+    pub fn allowed_header<T: Into<String>>(mut self, header: T) -> Self {
+        self.allowed_headers.push(header.into());
+        self
+    }
+
+    /// A response header the browser may expose to the application.
+    pub fn expose_header<T: Into<String>>(mut self, header: T) -> Self {
+        self.expose_headers.push(header.into());
+        self
+    }
+
+    /// How long the browser may cache the response to a preflight request.
+    pub fn max_age_seconds(mut self, seconds: u32) -> Self {
+        self.max_age_seconds = Some(seconds);
+        self
+    }
+}
+
+/// The HTTP methods that can be allowed by a cors rule.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CorsMethod {
+    Get,
+    Put,
+    Head,
+    Post,
+    Delete,
+}
+
+impl_text_enum!(CorsMethod, "cors method", {
+    Get = "GET",
+    Put = "PUT",
+    Head = "HEAD",
+    Post = "POST",
+    Delete = "DELETE",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    fn config() -> CorsConfiguration {
+        CorsConfiguration::new()
+            .rule(
+                CorsRule::new()
+                    .id("app")
+                    .allowed_origin("https://*.example.com")
+                    .allowed_method(CorsMethod::Get)
+                    .allowed_method(CorsMethod::Put)
+                    .allowed_header("x-amz-*")
+                    .allowed_header("Content-Type")
+                    .expose_header("ETag")
+                    .max_age_seconds(3000),
+            )
+            .rule(
+                CorsRule::new()
+                    .allowed_origin("*")
+                    .allowed_method(CorsMethod::Get),
+            )
+    }
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = config();
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<CORSConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<CORSRule><ID>app</ID><AllowedHeader>x-amz-*</AllowedHeader>",
+                "<AllowedHeader>Content-Type</AllowedHeader>",
+                "<AllowedMethod>GET</AllowedMethod><AllowedMethod>PUT</AllowedMethod>",
+                "<AllowedOrigin>https://*.example.com</AllowedOrigin>",
+                "<ExposeHeader>ETag</ExposeHeader><MaxAgeSeconds>3000</MaxAgeSeconds></CORSRule>",
+                "<CORSRule><AllowedMethod>GET</AllowedMethod><AllowedOrigin>*</AllowedOrigin></CORSRule>",
+                "</CORSConfiguration>"
+            )
+        );
+
+        let parsed: CorsConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn evaluate_matches_wildcards() {
+        let config = config();
+
+        let response = config
+            .evaluate_preflight(
+                "https://app.example.com",
+                "PUT",
+                &["X-Amz-Date", "content-type"],
+            )
+            .unwrap();
+        assert_eq!(response.rule.id.as_deref(), Some("app"));
+        assert_eq!(
+            response.header("Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            response.header("Access-Control-Allow-Credentials"),
+            Some("true")
+        );
+        assert_eq!(
+            response.header("Access-Control-Allow-Methods"),
+            Some("GET, PUT")
+        );
+        assert_eq!(
+            response.header("Access-Control-Allow-Headers"),
+            Some("x-amz-date, content-type")
+        );
+        assert_eq!(response.header("Access-Control-Max-Age"), Some("3000"));
+
+        // Falls through to the rule allowing every origin
+        let response = config.evaluate("https://example.org", "GET").unwrap();
+        assert_eq!(response.rule.id, None);
+        assert_eq!(response.header("Access-Control-Allow-Origin"), Some("*"));
+        assert_eq!(response.header("Access-Control-Allow-Credentials"), None);
+
+        assert!(config.evaluate("https://example.org", "PUT").is_none());
+        assert!(config
+            .evaluate_preflight("https://app.example.com", "PUT", &["authorization"])
+            .is_none());
+        assert!(config.evaluate("https://example.com", "PUT").is_none());
+    }
+
+    #[test]
+    fn only_preflight_requests_check_headers() {
+        let config = CorsConfiguration::new().rule(
+            CorsRule::new()
+                .allowed_origin("*")
+                .allowed_method(CorsMethod::Get),
+        );
+
+        // The rule allows no headers, so any preflight asking for one fails
+        assert!(config
+            .evaluate_preflight("https://example.com", "GET", &["range"])
+            .is_none());
+
+        let response = config
+            .evaluate_preflight("https://example.com", "GET", &[])
+            .unwrap();
+        assert_eq!(response.header("Access-Control-Allow-Headers"), None);
+
+        // Simple requests send headers without a preflight, which S3 allows
+        let response = config.evaluate("https://example.com", "GET").unwrap();
+        assert_eq!(response.header("Access-Control-Allow-Origin"), Some("*"));
+        assert_eq!(response.header("Access-Control-Allow-Headers"), None);
+    }
 }
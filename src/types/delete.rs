@@ -0,0 +1,137 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// The body of a `DeleteObjects` request.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+#[serde(rename = "Delete")]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct Delete {
+    pub(crate) quiet: Option<bool>,
+
+    #[serde(rename = "Object")]
+    pub(crate) objects: Vec<ObjectIdentifier>,
+}
+
+/// An object to delete, or a specific version of it.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ObjectIdentifier {
+    pub key: String,
+    pub version_id: Option<String>,
+}
+
+/// The outcome of a `DeleteObjects` request. In quiet mode only the
+/// objects which couldn't be deleted are listed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize)]
+#[serde(from = "DeleteResultElement")]
+pub struct DeleteResult {
+    pub deleted: Vec<DeletedObject>,
+    pub errors: Vec<DeleteError>,
+}
+
+// `Deleted` and `Error` elements may be interleaved, which can't be read
+// into two separate lists directly.
+#[derive(Deserialize)]
+struct DeleteResultElement {
+    #[serde(rename = "$value", default)]
+    entries: Vec<DeleteResultEntry>,
+}
+
+#[derive(Deserialize)]
+enum DeleteResultEntry {
+    Deleted(DeletedObject),
+    Error(DeleteError),
+}
+
+impl From<DeleteResultElement> for DeleteResult {
+    fn from(element: DeleteResultElement) -> Self {
+        let mut result = DeleteResult::default();
+
+        for entry in element.entries {
+            match entry {
+                DeleteResultEntry::Deleted(deleted) => result.deleted.push(deleted),
+                DeleteResultEntry::Error(error) => result.errors.push(error),
+            }
+        }
+
+        result
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeletedObject {
+    pub key: String,
+
+    /// The version deleted, if a version was given.
+    pub version_id: Option<String>,
+
+    /// Whether a delete marker was created, or deleted if a delete marker
+    /// version was given.
+    #[serde(default)]
+    pub delete_marker: bool,
+
+    pub delete_marker_version_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeleteError {
+    pub key: String,
+    pub version_id: Option<String>,
+
+    /// The error code, e.g. `AccessDenied`.
+    pub code: String,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn delete_round_trip() {
+        let delete = Delete {
+            quiet: Some(true),
+            objects: vec![
+                ObjectIdentifier {
+                    key: "a".to_owned(),
+                    version_id: None,
+                },
+                ObjectIdentifier {
+                    key: "b".to_owned(),
+                    version_id: Some("v1".to_owned()),
+                },
+            ],
+        };
+
+        assert_eq!(
+            xml::to_string(&delete).unwrap(),
+            concat!(
+                r#"<Delete xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Quiet>true</Quiet>"#,
+                "<Object><Key>a</Key></Object>",
+                "<Object><Key>b</Key><VersionId>v1</VersionId></Object></Delete>"
+            )
+        );
+
+        let result: DeleteResult = quick_xml::de::from_str(concat!(
+            "<DeleteResult>",
+            "<Deleted><Key>a</Key><DeleteMarker>true</DeleteMarker>",
+            "<DeleteMarkerVersionId>v2</DeleteMarkerVersionId></Deleted>",
+            "<Error><Key>b</Key><VersionId>v1</VersionId><Code>AccessDenied</Code>",
+            "<Message>Access Denied</Message></Error>",
+            "<Deleted><Key>c</Key></Deleted>",
+            "</DeleteResult>"
+        ))
+        .unwrap();
+        assert_eq!(result.deleted.len(), 2);
+        assert!(result.deleted[0].delete_marker);
+        assert_eq!(result.errors[0].code, "AccessDenied");
+
+        let quiet: DeleteResult = quick_xml::de::from_str("<DeleteResult></DeleteResult>").unwrap();
+        assert_eq!(quiet, DeleteResult::default());
+    }
+}
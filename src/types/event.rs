@@ -0,0 +1,202 @@
+// The json messages S3 delivers to SNS, SQS and Lambda destinations when a
+// configured event occurs.
+use crate::{
+    types::NotificationEvent,
+    uri_encode,
+};
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use std::collections::HashMap;
+
+/// A notification message. The `s3:TestEvent` message sent when a
+/// configuration is saved has no records.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EventMessage {
+    #[serde(rename = "Records", default)]
+    pub records: Vec<EventRecord>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventRecord {
+    pub event_version: String,
+    pub event_source: String,
+    pub aws_region: String,
+    pub event_time: DateTime<Utc>,
+
+    /// The event type without the `s3:` prefix, e.g. `ObjectCreated:Put`.
+    pub event_name: String,
+
+    pub user_identity: Option<UserIdentity>,
+    pub request_parameters: Option<RequestParameters>,
+
+    /// Contains `x-amz-request-id` and `x-amz-id-2` of the request that
+    /// caused the event.
+    #[serde(default)]
+    pub response_elements: HashMap<String, String>,
+
+    pub s3: EventEntity,
+    pub glacier_event_data: Option<GlacierEventData>,
+}
+
+impl EventRecord {
+    /// The typed event type, `None` for event types this crate doesn't know.
+    pub fn event(&self) -> Option<NotificationEvent> {
+        format!("s3:{}", self.event_name).parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserIdentity {
+    pub principal_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestParameters {
+    #[serde(rename = "sourceIPAddress")]
+    pub source_ip_address: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventEntity {
+    pub s3_schema_version: String,
+
+    /// The id of the notification configuration that matched the event.
+    pub configuration_id: String,
+
+    pub bucket: EventBucket,
+    pub object: EventObject,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventBucket {
+    pub name: String,
+    pub owner_identity: UserIdentity,
+    pub arn: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventObject {
+    /// The object key, already url-decoded. It is encoded again when
+    /// serialized, so messages can be passed on as they were received.
+    #[serde(deserialize_with = "decode_key", serialize_with = "encode_key")]
+    pub key: String,
+
+    /// The object size, missing for delete events.
+    pub size: Option<u64>,
+
+    pub e_tag: Option<String>,
+    pub version_id: Option<String>,
+
+    /// Orders events for the same key, a greater sequencer is a later event.
+    pub sequencer: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlacierEventData {
+    pub restore_event_data: RestoreEventData,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreEventData {
+    pub lifecycle_restoration_expiry_time: DateTime<Utc>,
+    pub lifecycle_restore_storage_class: String,
+}
+
+fn decode_key<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    uri_encode::decode_form(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+}
+
+fn encode_key<S: Serializer>(key: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&uri_encode::encode_form(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_event_message() {
+        let message: EventMessage = serde_json::from_str(
+            r#"{
+                "Records": [{
+                    "eventVersion": "2.1",
+                    "eventSource": "aws:s3",
+                    "awsRegion": "us-west-2",
+                    "eventTime": "2020-01-01T12:00:00.000Z",
+                    "eventName": "ObjectCreated:Put",
+                    "userIdentity": {"principalId": "AWS:AIDAJDPLRKLG7UEXAMPLE"},
+                    "requestParameters": {"sourceIPAddress": "127.0.0.1"},
+                    "responseElements": {
+                        "x-amz-request-id": "C3D13FE58DE4C810",
+                        "x-amz-id-2": "FMyUVURIY8/IgAtTv8xRjskZQpcIZ9KG4V5Wp6S7S/JRWeUWerMUE5JgHvANOjpD"
+                    },
+                    "s3": {
+                        "s3SchemaVersion": "1.0",
+                        "configurationId": "uploads",
+                        "bucket": {
+                            "name": "bucket",
+                            "ownerIdentity": {"principalId": "A3NL1KOZZKExample"},
+                            "arn": "arn:aws:s3:::bucket"
+                        },
+                        "object": {
+                            "key": "photos/Happy+Face%C3%A9%2B.jpg",
+                            "size": 1024,
+                            "eTag": "d41d8cd98f00b204e9800998ecf8427e",
+                            "sequencer": "0055AED6DCD90281E5"
+                        }
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let record = &message.records[0];
+        assert_eq!(record.s3.object.key, "photos/Happy Faceé+.jpg");
+        assert_eq!(record.s3.object.size, Some(1024));
+        assert_eq!(record.event(), Some(NotificationEvent::ObjectCreatedPut));
+        assert_eq!(
+            record
+                .response_elements
+                .get("x-amz-request-id")
+                .map(String::as_str),
+            Some("C3D13FE58DE4C810")
+        );
+
+        let test: EventMessage =
+            serde_json::from_str(r#"{"Service":"Amazon S3","Event":"s3:TestEvent"}"#).unwrap();
+        assert!(test.records.is_empty());
+    }
+
+    #[test]
+    fn keys_survive_a_round_trip() {
+        for encoded in &["a+b.jpg", "%2541", "photos/Happy+Face%C3%A9%2B.jpg"] {
+            let json = format!(r#"{{"key":"{}","sequencer":"0"}}"#, encoded);
+
+            let object: EventObject = serde_json::from_str(&json).unwrap();
+            let serialized = serde_json::to_string(&object).unwrap();
+            let parsed: EventObject = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(parsed.key, object.key);
+            assert!(serialized.contains(encoded));
+        }
+
+        let object: EventObject =
+            serde_json::from_str(r#"{"key":"a+b%41","sequencer":"0"}"#).unwrap();
+        assert_eq!(object.key, "a bA");
+    }
+}
@@ -1,47 +1,245 @@
-use serde::Deserialize;
+use crate::Error;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// An inventory configuration of a bucket, which periodically writes a
+/// list of the objects of the bucket to a destination bucket.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "InventoryConfiguration")]
 #[serde(rename_all = "PascalCase")]
-pub struct InventoryConfig {
-    inventory_destination: InventoryDestination,
-    inventory_filter: InventoryFilter,
+pub struct InventoryConfiguration {
+    pub destination: InventoryDestination,
+    pub is_enabled: bool,
+    pub filter: Option<InventoryFilter>,
+    pub id: String,
+    pub included_object_versions: IncludedObjectVersions,
+    #[serde(default)]
+    pub optional_fields: OptionalFields,
+    pub schedule: InventorySchedule,
+}
+
+impl InventoryConfiguration {
+    /// Create a new enabled configuration writing a daily report of the
+    /// current object versions to `destination`.
+    pub fn new<T: Into<String>>(id: T, destination: InventoryS3BucketDestination) -> Self {
+        InventoryConfiguration {
+            destination: InventoryDestination {
+                s3_bucket_destination: destination,
+            },
+            is_enabled: true,
+            filter: None,
+            id: id.into(),
+            included_object_versions: IncludedObjectVersions::Current,
+            optional_fields: OptionalFields::default(),
+            schedule: InventorySchedule {
+                frequency: InventoryFrequency::Daily,
+            },
+        }
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.is_enabled = false;
+        self
+    }
+
+    /// Only list objects with keys starting with `prefix`.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.filter = Some(InventoryFilter {
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    /// List every version of the objects rather than only the current one.
+    pub fn all_versions(mut self) -> Self {
+        self.included_object_versions = IncludedObjectVersions::All;
+        self
+    }
+
+    pub fn frequency(mut self, frequency: InventoryFrequency) -> Self {
+        self.schedule.frequency = frequency;
+        self
+    }
+
+    /// Add a column to the report. The bucket, key and, when listing every
+    /// version, the version columns are always included.
+    pub fn field(mut self, field: InventoryField) -> Self {
+        if !self.optional_fields.fields.contains(&field) {
+            self.optional_fields.fields.push(field);
+        }
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketInventoryConfiguration` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !super::is_valid_configuration_id(&self.id) {
+            return Err(Error::InvalidInventory(format!(
+                "id {:?} must be between 1 and {} letters, numbers, `-`, `_` or `.`",
+                self.id,
+                super::MAX_CONFIGURATION_ID_LENGTH
+            )));
+        }
+
+        let destination = &self.destination.s3_bucket_destination;
 
-    #[serde(rename = "ID")]
-    id: String,
+        if !destination.bucket.starts_with("arn:") {
+            return Err(Error::InvalidInventory(format!(
+                "destination must be a bucket arn, found {:?}",
+                destination.bucket
+            )));
+        }
 
-    included_object_versions: String,
-    is_enabled: bool,
-    optional_fields: OptionalFields,
-    schedule: InventorySchedule,
+        if let Some(ref encryption) = destination.encryption {
+            if encryption.sse_s3.is_some() == encryption.sse_kms.is_some() {
+                return Err(Error::InvalidInventory(
+                    "destination encryption must be either SSE-S3 or SSE-KMS".to_owned(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct InventoryDestination {
     #[serde(rename = "S3BucketDestination")]
-    s3_bucket_destination: InventoryS3BucketDestination,
+    pub s3_bucket_destination: InventoryS3BucketDestination,
 }
 
-#[derive(Debug, Deserialize)]
+/// The bucket inventory reports are written to.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct InventoryS3BucketDestination {
-    account_id: Option<String>,
-    bucket: String,
-    inventory_encryption: Option<InventoryEncryption>,
-    format: String,
-    prefix: Option<String>,
+    pub account_id: Option<String>,
+
+    /// The arn of the destination bucket.
+    pub bucket: String,
+
+    pub format: InventoryFormat,
+    pub prefix: Option<String>,
+    pub encryption: Option<InventoryEncryption>,
+}
+
+impl InventoryS3BucketDestination {
+    /// Create a new destination for the bucket with the given arn, e.g.
+    /// `arn:aws:s3:::reports`.
+    pub fn new<T: Into<String>>(bucket_arn: T, format: InventoryFormat) -> Self {
+        InventoryS3BucketDestination {
+            account_id: None,
+            bucket: bucket_arn.into(),
+            format,
+            prefix: None,
+            encryption: None,
+        }
+    }
+
+    /// The account owning the destination bucket.
+    pub fn account_id<T: Into<String>>(mut self, account_id: T) -> Self {
+        self.account_id = Some(account_id.into());
+        self
+    }
+
+    /// The prefix of every report key.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Encrypt reports with keys managed by S3.
+    pub fn sse_s3(mut self) -> Self {
+        self.encryption = Some(InventoryEncryption {
+            sse_s3: Some(SseS3 {}),
+            sse_kms: None,
+        });
+        self
+    }
+
+    /// Encrypt reports with a KMS key.
+    pub fn sse_kms<T: Into<String>>(mut self, key_id: T) -> Self {
+        self.encryption = Some(InventoryEncryption {
+            sse_s3: None,
+            sse_kms: Some(SseKms {
+                key_id: key_id.into(),
+            }),
+        });
+        self
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct InventoryEncryption {
+    #[serde(rename = "SSE-S3")]
+    pub sse_s3: Option<SseS3>,
+
+    #[serde(rename = "SSE-KMS")]
+    pub sse_kms: Option<SseKms>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct SseS3 {}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct OptionalFields {
-    #[serde(rename = "Field")]
-    fields: Vec<InventoryFields>,
+pub struct SseKms {
+    pub key_id: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub enum InventoryFields {
+pub struct InventoryFilter {
+    pub prefix: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InventorySchedule {
+    pub frequency: InventoryFrequency,
+}
+
+/// The optional columns of an inventory report.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OptionalFields {
+    #[serde(rename = "Field", default)]
+    pub fields: Vec<InventoryField>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InventoryFormat {
+    Csv,
+    Orc,
+    Parquet,
+}
+
+impl_text_enum!(InventoryFormat, "inventory format", {
+    Csv = "CSV",
+    Orc = "ORC",
+    Parquet,
+});
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InventoryFrequency {
+    Daily,
+    Weekly,
+}
+
+impl_text_enum!(InventoryFrequency, "inventory frequency", { Daily, Weekly });
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IncludedObjectVersions {
+    All,
+    Current,
+}
+
+impl_text_enum!(IncludedObjectVersions, "included object versions", { All, Current });
+
+/// An optional column of an inventory report.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InventoryField {
     Size,
     LastModifiedDate,
     StorageClass,
@@ -53,41 +251,91 @@ pub enum InventoryFields {
     ObjectLockMode,
     ObjectLockLegalHoldStatus,
     IntelligentTieringAccessTier,
+    BucketKeyStatus,
+    ChecksumAlgorithm,
 }
 
-#[derive(Debug, Deserialize)]
+impl_text_enum!(InventoryField, "inventory field", {
+    Size,
+    LastModifiedDate,
+    StorageClass,
+    ETag,
+    IsMultipartUploaded,
+    ReplicationStatus,
+    EncryptionStatus,
+    ObjectLockRetainUntilDate,
+    ObjectLockMode,
+    ObjectLockLegalHoldStatus,
+    IntelligentTieringAccessTier,
+    BucketKeyStatus,
+    ChecksumAlgorithm,
+});
+
+/// A page of `ListBucketInventoryConfigurations`.
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct InventoryEncryption {
-    #[serde(rename = "SSEKMS")]
-    sse_kms: Option<SseKms>,
+pub struct ListInventoryConfigurationsResult {
+    pub continuation_token: Option<String>,
 
-    #[serde(rename = "SSES3")]
-    sse_s3: Option<String>,
-}
+    #[serde(rename = "InventoryConfiguration", default)]
+    pub inventory_configurations: Vec<InventoryConfiguration>,
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct SseKms {
-    key_id: String,
-}
+    pub is_truncated: bool,
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct InventoryFilter {
-    prefix: String,
+    /// The token to request the next page with, if the list is truncated.
+    pub next_continuation_token: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct InventorySchedule {
-    frequency: String,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct InventoryConfigs {
-    continuation_token: String,
-    inventory_configuration: Vec<InventoryConfig>,
-    is_truncated: bool,
-    next_continuation_token: String,
+    #[test]
+    fn configuration_round_trip() {
+        let config = InventoryConfiguration::new(
+            "audit",
+            InventoryS3BucketDestination::new("arn:aws:s3:::reports", InventoryFormat::Csv)
+                .prefix("inventory")
+                .sse_s3(),
+        )
+        .prefix("data/")
+        .all_versions()
+        .frequency(InventoryFrequency::Weekly)
+        .field(InventoryField::Size)
+        .field(InventoryField::ETag);
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<InventoryConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Destination><S3BucketDestination><Bucket>arn:aws:s3:::reports</Bucket>",
+                "<Format>CSV</Format><Prefix>inventory</Prefix>",
+                "<Encryption><SSE-S3></SSE-S3></Encryption></S3BucketDestination></Destination>",
+                "<IsEnabled>true</IsEnabled><Filter><Prefix>data/</Prefix></Filter><Id>audit</Id>",
+                "<IncludedObjectVersions>All</IncludedObjectVersions>",
+                "<OptionalFields><Field>Size</Field><Field>ETag</Field></OptionalFields>",
+                "<Schedule><Frequency>Weekly</Frequency></Schedule>",
+                "</InventoryConfiguration>"
+            )
+        );
+
+        let parsed: InventoryConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(config.validate().is_ok());
+
+        let list: ListInventoryConfigurationsResult = quick_xml::de::from_str(&format!(
+            concat!(
+                "<ListInventoryConfigurationsResult>{}",
+                "<IsTruncated>true</IsTruncated><NextContinuationToken>next</NextContinuationToken>",
+                "</ListInventoryConfigurationsResult>"
+            ),
+            string.replace(r#" xmlns="http://s3.amazonaws.com/doc/2006-03-01/""#, "")
+                .replace("<SSE-S3></SSE-S3>", "<SSE-S3/>")
+        ))
+        .unwrap();
+        assert_eq!(list.inventory_configurations, vec![config]);
+        assert_eq!(list.next_continuation_token.as_deref(), Some("next"));
+    }
 }
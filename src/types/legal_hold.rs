@@ -1,7 +1,13 @@
-use serde::Deserialize;
+use crate::LegalHoldStatus;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// The request and response body of the legal-hold sub-resource.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "LegalHold")]
 #[serde(rename_all = "PascalCase")]
 pub struct LegalHold {
-    status: String,
+    pub status: LegalHoldStatus,
 }
@@ -1,22 +1,122 @@
-use serde::Deserialize;
+use crate::ObjectLockMode;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// The object lock configuration of a bucket.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "ObjectLockConfiguration")]
 #[serde(rename_all = "PascalCase")]
 pub struct ObjectLockConfiguration {
-    object_lock_enabled: String,
-    rule: ObjectLockRule,
+    pub object_lock_enabled: Option<ObjectLockEnabled>,
+
+    /// The retention applied to new objects placed in the bucket.
+    pub rule: Option<ObjectLockRule>,
 }
 
-#[derive(Debug, Deserialize)]
+impl ObjectLockConfiguration {
+    /// Create a configuration which enables object lock without a default retention
+    pub fn enabled() -> Self {
+        ObjectLockConfiguration {
+            object_lock_enabled: Some(ObjectLockEnabled::Enabled),
+            rule: None,
+        }
+    }
+
+    /// The retention applied to new objects that don't specify their own.
+    pub fn default_retention(mut self, default_retention: DefaultRetention) -> Self {
+        self.rule = Some(ObjectLockRule { default_retention });
+        self
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ObjectLockEnabled {
+    Enabled,
+}
+
+impl_text_enum!(ObjectLockEnabled, "object lock status", { Enabled });
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ObjectLockRule {
-    default_retention: Option<DefaultRetention>,
+    pub default_retention: DefaultRetention,
 }
 
-#[derive(Debug, Deserialize)]
+/// A retention period given in either days or years, never both.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DefaultRetention {
-    days: Option<i32>,
-    mode: Option<String>,
-    years: Option<i32>,
+    pub days: Option<u32>,
+    pub mode: ObjectLockMode,
+    pub years: Option<u32>,
+}
+
+impl DefaultRetention {
+    /// Retain new objects for the given number of days
+    pub fn days(mode: ObjectLockMode, days: u32) -> Self {
+        DefaultRetention {
+            days: Some(days),
+            mode,
+            years: None,
+        }
+    }
+
+    /// Retain new objects for the given number of years
+    pub fn years(mode: ObjectLockMode, years: u32) -> Self {
+        DefaultRetention {
+            days: None,
+            mode,
+            years: Some(years),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        types::Retention,
+        xml,
+    };
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = ObjectLockConfiguration::enabled()
+            .default_retention(DefaultRetention::days(ObjectLockMode::Governance, 30));
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<ObjectLockConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<ObjectLockEnabled>Enabled</ObjectLockEnabled>",
+                "<Rule><DefaultRetention><Days>30</Days><Mode>GOVERNANCE</Mode></DefaultRetention></Rule>",
+                "</ObjectLockConfiguration>"
+            )
+        );
+
+        let parsed: ObjectLockConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn retention_round_trip() {
+        let retention: Retention = quick_xml::de::from_str(concat!(
+            "<Retention><Mode>COMPLIANCE</Mode>",
+            "<RetainUntilDate>2030-01-31T12:00:00.000Z</RetainUntilDate></Retention>"
+        ))
+        .unwrap();
+
+        assert_eq!(retention.mode, ObjectLockMode::Compliance);
+        assert_eq!(
+            crate::object_lock::to_iso8601(retention.retain_until_date),
+            "2030-01-31T12:00:00.000Z"
+        );
+
+        let string = xml::to_string(&retention).unwrap();
+        let parsed: Retention = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, retention);
+    }
 }
@@ -1,67 +1,867 @@
-use serde::Deserialize;
+use crate::{
+    types::Tag,
+    Error,
+    StorageClass,
+    TagSet,
+};
+use chrono::{
+    DateTime,
+    Timelike,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::collections::HashSet;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketLifecycleOutput")]
-pub struct BucketLifecycle {
-    #[serde(rename = "Rule")]
-    pub rule: Vec<LifecycleRule>,
-}
+// Limits enforced by S3 on a lifecycle configuration
+const MAX_RULES: usize = 1000;
+const MAX_ID_LENGTH: usize = 255;
+
+// Objects must be stored for at least 30 days before they can be
+// transitioned to one of the infrequent access storage classes.
+const MIN_IA_TRANSITION_DAYS: u32 = 30;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketLifecycleConfigurationOutput")]
-pub struct BucketLifecycleConfig {
-    #[serde(rename = "Rule")]
+/// The lifecycle configuration of a bucket, a list of rules each applying
+/// actions to the objects matching its filter.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "LifecycleConfiguration")]
+pub struct LifecycleConfiguration {
+    #[serde(rename = "Rule", default)]
     pub rules: Vec<LifecycleRule>,
 }
 
-#[derive(Debug, Deserialize)]
+impl LifecycleConfiguration {
+    /// Create a new empty lifecycle configuration
+    pub fn new() -> Self {
+        LifecycleConfiguration::default()
+    }
+
+    /// Add a rule to the configuration.
+    pub fn rule(mut self, rule: LifecycleRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject, and for rules
+    /// that conflict with each other. This is done by
+    /// `PutBucketLifecycleConfiguration` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.rules.is_empty() {
+            return Err(invalid(
+                "a configuration needs at least one rule, \
+                 use `DeleteBucketLifecycle` to remove every rule"
+                    .to_owned(),
+            ));
+        }
+
+        if self.rules.len() > MAX_RULES {
+            return Err(invalid(format!(
+                "at most {} rules are allowed, found {}",
+                MAX_RULES,
+                self.rules.len()
+            )));
+        }
+
+        let mut ids = HashSet::new();
+        for rule in self.rules.iter() {
+            if let Some(ref id) = rule.id {
+                if !ids.insert(id.as_str()) {
+                    return Err(invalid(format!("rule id {:?} is used more than once", id)));
+                }
+            }
+
+            rule.validate()?;
+        }
+
+        // Two enabled rules selecting the same objects can't both take the
+        // same kind of action, as it would be ambiguous which one applies.
+        let enabled = self
+            .rules
+            .iter()
+            .filter(|rule| rule.status == LifecycleStatus::Enabled)
+            .collect::<Vec<_>>();
+
+        for (index, rule) in enabled.iter().enumerate() {
+            for other in enabled[index + 1..].iter() {
+                if rule.selector() != other.selector() {
+                    continue;
+                }
+
+                if let Some(action) = rule
+                    .actions()
+                    .into_iter()
+                    .find(|action| other.actions().contains(action))
+                {
+                    return Err(invalid(format!(
+                        "rules {} and {} have the same filter and both {}",
+                        rule.name(),
+                        other.name(),
+                        action
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::InvalidLifecycle(message)
+}
+
+/// A single lifecycle rule. Rules are enabled and apply to every object of
+/// the bucket unless a filter is given.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct LifecycleRule {
-    pub abort_incomplete_multipart_upload: Option<AbortIncompleteMultipartUpload>,
-    pub lifecycle_expiration: Option<LifecyleExpiration>,
+    pub expiration: Option<LifecycleExpiration>,
 
     #[serde(rename = "ID")]
     pub id: Option<String>,
 
+    /// The prefix of legacy rules without a filter. It is only read from
+    /// existing configurations, new rules should use `filter` instead.
+    pub prefix: Option<String>,
+
+    pub filter: Option<LifecycleFilter>,
+
+    pub status: LifecycleStatus,
+
+    #[serde(rename = "Transition", default)]
+    pub transitions: Vec<Transition>,
+
+    #[serde(rename = "NoncurrentVersionTransition", default)]
+    pub noncurrent_version_transitions: Vec<NoncurrentVersionTransition>,
+
     pub noncurrent_version_expiration: Option<NoncurrentVersionExpiration>,
-    pub noncurrent_version_transition: Option<NoncurrentVersionTransition>,
-    pub prefix: String,
-    pub status: String,
 
-    pub transition: Option<Transition>,
+    pub abort_incomplete_multipart_upload: Option<AbortIncompleteMultipartUpload>,
 }
 
-#[derive(Debug, Deserialize)]
+impl LifecycleRule {
+    /// Create a new enabled rule that applies to every object in the bucket
+    pub fn new<T: Into<String>>(id: T) -> Self {
+        LifecycleRule {
+            expiration: None,
+            id: Some(id.into()),
+            prefix: None,
+            filter: Some(LifecycleFilter::new()),
+            status: LifecycleStatus::Enabled,
+            transitions: Vec::new(),
+            noncurrent_version_transitions: Vec::new(),
+            noncurrent_version_expiration: None,
+            abort_incomplete_multipart_upload: None,
+        }
+    }
+
+    /// Keep the rule in the configuration without applying it.
+    pub fn disabled(mut self) -> Self {
+        self.status = LifecycleStatus::Disabled;
+        self
+    }
+
+    /// Only apply the rule to objects matching the filter.
+    pub fn filter(mut self, filter: LifecycleFilter) -> Self {
+        self.prefix = None;
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Expire objects the given number of days after their creation.
+    pub fn expire_after_days(mut self, days: u32) -> Self {
+        self.expiration = Some(LifecycleExpiration {
+            days: Some(days),
+            ..LifecycleExpiration::default()
+        });
+        self
+    }
+
+    /// Expire objects at the given date, which must be at midnight UTC.
+    pub fn expire_at(mut self, date: DateTime<Utc>) -> Self {
+        self.expiration = Some(LifecycleExpiration {
+            date: Some(date),
+            ..LifecycleExpiration::default()
+        });
+        self
+    }
+
+    /// Remove delete markers that no longer have any noncurrent versions
+    /// behind them. Only valid in versioned buckets.
+    pub fn expire_object_delete_markers(mut self) -> Self {
+        self.expiration = Some(LifecycleExpiration {
+            expired_object_delete_marker: Some(true),
+            ..LifecycleExpiration::default()
+        });
+        self
+    }
+
+    /// Move objects to another storage class the given number of days after
+    /// their creation. Can be called multiple times to add several transitions.
+    pub fn transition_after_days(mut self, days: u32, storage_class: StorageClass) -> Self {
+        self.transitions.push(Transition {
+            date: None,
+            days: Some(days),
+            storage_class,
+        });
+        self
+    }
+
+    /// Move objects to another storage class at the given date, which must
+    /// be at midnight UTC.
+    pub fn transition_at(mut self, date: DateTime<Utc>, storage_class: StorageClass) -> Self {
+        self.transitions.push(Transition {
+            date: Some(date),
+            days: None,
+            storage_class,
+        });
+        self
+    }
+
+    /// Move versions to another storage class the given number of days after
+    /// they became noncurrent.
+    pub fn noncurrent_version_transition(
+        mut self,
+        noncurrent_days: u32,
+        storage_class: StorageClass,
+    ) -> Self {
+        self.noncurrent_version_transitions
+            .push(NoncurrentVersionTransition {
+                noncurrent_days,
+                storage_class,
+                newer_noncurrent_versions: None,
+            });
+        self
+    }
+
+    /// Permanently delete versions the given number of days after they
+    /// became noncurrent.
+    pub fn noncurrent_version_expiration(mut self, noncurrent_days: u32) -> Self {
+        self.noncurrent_version_expiration_mut().noncurrent_days = noncurrent_days;
+        self
+    }
+
+    /// The number of newest noncurrent versions kept by the noncurrent
+    /// version expiration, regardless of their age. The rule is only valid
+    /// once `noncurrent_version_expiration()` is given as well.
+    pub fn newer_noncurrent_versions(mut self, versions: u32) -> Self {
+        self.noncurrent_version_expiration_mut()
+            .newer_noncurrent_versions = Some(versions);
+        self
+    }
+
+    // Zero days are rejected by `validate()` until the days are given
+    fn noncurrent_version_expiration_mut(&mut self) -> &mut NoncurrentVersionExpiration {
+        self.noncurrent_version_expiration
+            .get_or_insert(NoncurrentVersionExpiration {
+                noncurrent_days: 0,
+                newer_noncurrent_versions: None,
+            })
+    }
+
+    /// Abort multipart uploads that didn't complete within the given number
+    /// of days after they were started.
+    pub fn abort_incomplete_multipart_upload(mut self, days_after_initiation: u32) -> Self {
+        self.abort_incomplete_multipart_upload = Some(AbortIncompleteMultipartUpload {
+            days_after_initiation,
+        });
+        self
+    }
+
+    // The objects the rule applies to, by its filter or else by the prefix
+    // of a legacy rule. An empty prefix applies to every object, just like
+    // no filter at all.
+    fn selector(&self) -> (Option<&LifecycleFilter>, Option<&str>) {
+        match self.filter {
+            Some(ref filter) => (Some(filter), None),
+            None => (
+                None,
+                self.prefix.as_deref().filter(|prefix| !prefix.is_empty()),
+            ),
+        }
+    }
+
+    /// The prefix of the objects the rule applies to.
+    pub fn effective_prefix(&self) -> &str {
+        self.filter
+            .as_ref()
+            .and_then(|filter| filter.prefix.as_deref())
+            .or(self.prefix.as_deref())
+            .unwrap_or("")
+    }
+
+    fn name(&self) -> String {
+        self.id
+            .as_ref()
+            .map(|id| format!("{:?}", id))
+            .unwrap_or_else(|| "without an id".to_owned())
+    }
+
+    // The kinds of actions taken by the rule, used to find conflicting rules
+    fn actions(&self) -> Vec<&'static str> {
+        let mut actions = Vec::new();
+
+        if let Some(ref expiration) = self.expiration {
+            if expiration.expired_object_delete_marker == Some(true) {
+                actions.push("remove expired delete markers");
+            } else {
+                actions.push("expire objects");
+            }
+        }
+        if !self.transitions.is_empty() {
+            actions.push("transition objects");
+        }
+        if !self.noncurrent_version_transitions.is_empty() {
+            actions.push("transition noncurrent versions");
+        }
+        if self.noncurrent_version_expiration.is_some() {
+            actions.push("expire noncurrent versions");
+        }
+        if self.abort_incomplete_multipart_upload.is_some() {
+            actions.push("abort incomplete multipart uploads");
+        }
+
+        actions
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let name = self.name();
+
+        if let Some(ref id) = self.id {
+            if id.chars().count() > MAX_ID_LENGTH {
+                return Err(invalid(format!(
+                    "rule id {:?} may not be longer than {} characters",
+                    id, MAX_ID_LENGTH
+                )));
+            }
+        }
+
+        if self.prefix.is_some() && self.filter.is_some() {
+            return Err(invalid(format!(
+                "rule {} has both a prefix and a filter",
+                name
+            )));
+        }
+
+        if self.actions().is_empty() {
+            return Err(invalid(format!("rule {} has no actions", name)));
+        }
+
+        let filter = self.filter.clone().unwrap_or_default();
+        filter.validate(&name)?;
+
+        // Tag and size filters only apply to objects, not to delete markers
+        // or multipart uploads
+        let object_filter = !filter.tags.is_empty()
+            || filter.object_size_greater_than.is_some()
+            || filter.object_size_less_than.is_some();
+
+        let mut expiration_days = None;
+        let mut expiration_date = None;
+
+        if let Some(ref expiration) = self.expiration {
+            let actions = expiration.days.is_some() as u8
+                + expiration.date.is_some() as u8
+                + expiration.expired_object_delete_marker.is_some() as u8;
+            if actions != 1 {
+                return Err(invalid(format!(
+                    "the expiration of rule {} needs exactly one of a date, days \
+                     or expired object delete marker",
+                    name
+                )));
+            }
+
+            if expiration.days == Some(0) {
+                return Err(invalid(format!(
+                    "rule {} must expire objects after at least one day",
+                    name
+                )));
+            }
+
+            if let Some(date) = expiration.date {
+                check_midnight(&name, date)?;
+            }
+
+            if expiration.expired_object_delete_marker.is_some() && object_filter {
+                return Err(invalid(format!(
+                    "rule {} can't remove expired delete markers with a tag or size filter",
+                    name
+                )));
+            }
+
+            expiration_days = expiration.days;
+            expiration_date = expiration.date;
+        }
+
+        let mut storage_classes = HashSet::new();
+        for transition in self.transitions.iter() {
+            check_transition_class(&name, transition.storage_class)?;
+
+            if !storage_classes.insert(<&'static str>::from(transition.storage_class)) {
+                return Err(invalid(format!(
+                    "rule {} has more than one transition to {}",
+                    name,
+                    <&'static str>::from(transition.storage_class)
+                )));
+            }
+
+            match (transition.days, transition.date) {
+                (Some(days), None) => {
+                    if is_infrequent_access(transition.storage_class)
+                        && days < MIN_IA_TRANSITION_DAYS
+                    {
+                        return Err(invalid(format!(
+                            "rule {} can't transition objects to {} before {} days",
+                            name,
+                            <&'static str>::from(transition.storage_class),
+                            MIN_IA_TRANSITION_DAYS
+                        )));
+                    }
+
+                    if matches!(expiration_days, Some(expire) if expire <= days) {
+                        return Err(invalid(format!(
+                            "rule {} expires objects before they are transitioned",
+                            name
+                        )));
+                    }
+                }
+                (None, Some(date)) => {
+                    check_midnight(&name, date)?;
+
+                    if matches!(expiration_date, Some(expire) if expire <= date) {
+                        return Err(invalid(format!(
+                            "rule {} expires objects before they are transitioned",
+                            name
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "the transitions of rule {} need exactly one of a date or days",
+                        name
+                    )))
+                }
+            }
+        }
+
+        // S3 doesn't allow mixing days and dates in the transitions of a rule
+        let days = self.transitions.iter().filter(|t| t.days.is_some()).count();
+        if days != 0 && days != self.transitions.len() {
+            return Err(invalid(format!(
+                "the transitions of rule {} mix dates and days",
+                name
+            )));
+        }
+
+        let mut storage_classes = HashSet::new();
+        for transition in self.noncurrent_version_transitions.iter() {
+            check_transition_class(&name, transition.storage_class)?;
+
+            if !storage_classes.insert(<&'static str>::from(transition.storage_class)) {
+                return Err(invalid(format!(
+                    "rule {} has more than one noncurrent version transition to {}",
+                    name,
+                    <&'static str>::from(transition.storage_class)
+                )));
+            }
+
+            if is_infrequent_access(transition.storage_class)
+                && transition.noncurrent_days < MIN_IA_TRANSITION_DAYS
+            {
+                return Err(invalid(format!(
+                    "rule {} can't transition noncurrent versions to {} before {} days",
+                    name,
+                    <&'static str>::from(transition.storage_class),
+                    MIN_IA_TRANSITION_DAYS
+                )));
+            }
+
+            if let Some(ref expiration) = self.noncurrent_version_expiration {
+                if expiration.noncurrent_days <= transition.noncurrent_days {
+                    return Err(invalid(format!(
+                        "rule {} expires noncurrent versions before they are transitioned",
+                        name
+                    )));
+                }
+            }
+        }
+
+        if let Some(ref expiration) = self.noncurrent_version_expiration {
+            if expiration.noncurrent_days == 0 {
+                return Err(invalid(format!(
+                    "rule {} must expire noncurrent versions after at least one day",
+                    name
+                )));
+            }
+        }
+
+        if let Some(ref abort) = self.abort_incomplete_multipart_upload {
+            if object_filter {
+                return Err(invalid(format!(
+                    "rule {} can't abort incomplete multipart uploads with a tag or size filter",
+                    name
+                )));
+            }
+
+            if abort.days_after_initiation == 0 {
+                return Err(invalid(format!(
+                    "rule {} must abort incomplete multipart uploads after at least one day",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_midnight(name: &str, date: DateTime<Utc>) -> Result<(), Error> {
+    if date.num_seconds_from_midnight() != 0 || date.nanosecond() != 0 {
+        Err(invalid(format!(
+            "the dates of rule {} must be at midnight UTC",
+            name
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_transition_class(name: &str, storage_class: StorageClass) -> Result<(), Error> {
+    match storage_class {
+        StorageClass::Standard | StorageClass::ReducedRedundancy => Err(invalid(format!(
+            "rule {} can't transition objects to {}",
+            name,
+            <&'static str>::from(storage_class)
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn is_infrequent_access(storage_class: StorageClass) -> bool {
+    storage_class == StorageClass::StandardIa || storage_class == StorageClass::OnezoneIa
+}
+
+/// Whether a rule is applied.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LifecycleStatus {
+    Enabled,
+    Disabled,
+}
+
+impl_text_enum!(LifecycleStatus, "lifecycle rule status", { Enabled, Disabled });
+
+/// The objects a rule applies to. Every condition must match, an empty
+/// filter matches every object of the bucket.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "FilterElement", into = "FilterElement")]
+pub struct LifecycleFilter {
+    pub prefix: Option<String>,
+    pub tags: TagSet,
+
+    /// Only match objects larger than this many bytes.
+    pub object_size_greater_than: Option<u64>,
+
+    /// Only match objects smaller than this many bytes.
+    pub object_size_less_than: Option<u64>,
+}
+
+impl LifecycleFilter {
+    /// Create a new filter which matches every object
+    pub fn new() -> Self {
+        LifecycleFilter::default()
+    }
+
+    /// Only match objects whose key starts with `prefix`.
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only match objects with the given tag. Can be called multiple times,
+    /// in which case objects must have every tag.
+    pub fn tag<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Result<Self, Error> {
+        self.tags.insert(key, value)?;
+        Ok(self)
+    }
+
+    /// Only match objects larger than `size` bytes.
+    pub fn object_size_greater_than(mut self, size: u64) -> Self {
+        self.object_size_greater_than = Some(size);
+        self
+    }
+
+    /// Only match objects smaller than `size` bytes.
+    pub fn object_size_less_than(mut self, size: u64) -> Self {
+        self.object_size_less_than = Some(size);
+        self
+    }
+
+    /// Whether an object with the given key, size and tags matches the filter.
+    pub fn matches(&self, key: &str, size: u64, tags: &TagSet) -> bool {
+        self.prefix
+            .iter()
+            .all(|prefix| key.starts_with(prefix.as_str()))
+            && self.object_size_greater_than.iter().all(|&min| size > min)
+            && self.object_size_less_than.iter().all(|&max| size < max)
+            && self
+                .tags
+                .iter()
+                .all(|tag| tags.get(&tag.key) == Some(tag.value.as_str()))
+    }
+
+    fn validate(&self, name: &str) -> Result<(), Error> {
+        if let (Some(min), Some(max)) = (self.object_size_greater_than, self.object_size_less_than)
+        {
+            if min >= max {
+                return Err(invalid(format!(
+                    "the size filter of rule {} can never match",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The layout of the filter on the wire, which wraps the conditions in an
+// `And` element once there is more than one.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct AbortIncompleteMultipartUpload {
-    pub days_after_initiation: Option<i64>,
+struct FilterElement {
+    prefix: Option<String>,
+    tag: Option<Tag>,
+    object_size_greater_than: Option<u64>,
+    object_size_less_than: Option<u64>,
+    and: Option<FilterAnd>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct FilterAnd {
+    prefix: Option<String>,
+    #[serde(rename = "Tag", default)]
+    tags: Vec<Tag>,
+    object_size_greater_than: Option<u64>,
+    object_size_less_than: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+impl From<FilterElement> for LifecycleFilter {
+    fn from(element: FilterElement) -> Self {
+        let and = element.and.unwrap_or_default();
+
+        LifecycleFilter {
+            prefix: element.prefix.or(and.prefix),
+            tags: TagSet {
+                tags: element.tag.into_iter().chain(and.tags).collect(),
+            },
+            object_size_greater_than: element
+                .object_size_greater_than
+                .or(and.object_size_greater_than),
+            object_size_less_than: element.object_size_less_than.or(and.object_size_less_than),
+        }
+    }
+}
+
+impl From<LifecycleFilter> for FilterElement {
+    fn from(filter: LifecycleFilter) -> Self {
+        let conditions = filter.prefix.is_some() as usize
+            + filter.tags.len()
+            + filter.object_size_greater_than.is_some() as usize
+            + filter.object_size_less_than.is_some() as usize;
+
+        if conditions > 1 {
+            FilterElement {
+                and: Some(FilterAnd {
+                    prefix: filter.prefix,
+                    tags: filter.tags.tags,
+                    object_size_greater_than: filter.object_size_greater_than,
+                    object_size_less_than: filter.object_size_less_than,
+                }),
+                ..FilterElement::default()
+            }
+        } else {
+            FilterElement {
+                prefix: filter.prefix,
+                tag: filter.tags.tags.into_iter().next(),
+                object_size_greater_than: filter.object_size_greater_than,
+                object_size_less_than: filter.object_size_less_than,
+                and: None,
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct LifecyleExpiration {
-    pub date: Option<String>,
-    pub days: Option<i64>,
+pub struct LifecycleExpiration {
+    pub date: Option<DateTime<Utc>>,
+    pub days: Option<u32>,
     pub expired_object_delete_marker: Option<bool>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct NoncurrentVersionExpiration {
-    pub noncurrent_days: Option<i64>,
+pub struct Transition {
+    pub date: Option<DateTime<Utc>>,
+    pub days: Option<u32>,
+    pub storage_class: StorageClass,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct NoncurrentVersionTransition {
-    pub noncurrent_days: Option<i64>,
-    pub storage_class: Option<String>,
+    pub noncurrent_days: u32,
+    pub storage_class: StorageClass,
+    pub newer_noncurrent_versions: Option<u32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct Transition {
-    date: Option<String>,
-    days: Option<u32>,
-    storage_class: Option<String>,
+pub struct NoncurrentVersionExpiration {
+    pub noncurrent_days: u32,
+    pub newer_noncurrent_versions: Option<u32>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AbortIncompleteMultipartUpload {
+    pub days_after_initiation: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+    use chrono::TimeZone;
+
+    fn archive_rule() -> LifecycleRule {
+        LifecycleRule::new("archive")
+            .filter(
+                LifecycleFilter::new()
+                    .prefix("logs/")
+                    .tag("retain", "short")
+                    .unwrap()
+                    .object_size_greater_than(1024),
+            )
+            .transition_after_days(30, StorageClass::StandardIa)
+            .transition_after_days(90, StorageClass::Glacier)
+            .expire_after_days(365)
+    }
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = LifecycleConfiguration::new().rule(archive_rule()).rule(
+            LifecycleRule::new("cleanup")
+                .filter(LifecycleFilter::new().prefix("tmp/"))
+                .noncurrent_version_expiration(7)
+                .abort_incomplete_multipart_upload(1),
+        );
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<LifecycleConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Rule><Expiration><Days>365</Days></Expiration><ID>archive</ID>",
+                "<Filter><And><Prefix>logs/</Prefix><Tag><Key>retain</Key><Value>short</Value></Tag>",
+                "<ObjectSizeGreaterThan>1024</ObjectSizeGreaterThan></And></Filter>",
+                "<Status>Enabled</Status>",
+                "<Transition><Days>30</Days><StorageClass>STANDARD_IA</StorageClass></Transition>",
+                "<Transition><Days>90</Days><StorageClass>GLACIER</StorageClass></Transition></Rule>",
+                "<Rule><ID>cleanup</ID><Filter><Prefix>tmp/</Prefix></Filter><Status>Enabled</Status>",
+                "<NoncurrentVersionExpiration><NoncurrentDays>7</NoncurrentDays></NoncurrentVersionExpiration>",
+                "<AbortIncompleteMultipartUpload><DaysAfterInitiation>1</DaysAfterInitiation>",
+                "</AbortIncompleteMultipartUpload></Rule>",
+                "</LifecycleConfiguration>"
+            )
+        );
+
+        let parsed: LifecycleConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validation_catches_conflicts() {
+        let duplicate = LifecycleConfiguration::new()
+            .rule(archive_rule())
+            .rule(archive_rule());
+        assert!(duplicate.validate().is_err());
+
+        let same_filter = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("a").expire_after_days(10))
+            .rule(LifecycleRule::new("b").expire_after_days(20));
+        assert!(same_filter.validate().is_err());
+
+        let early_ia = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("a").transition_after_days(10, StorageClass::OnezoneIa));
+        assert!(early_ia.validate().is_err());
+
+        let expires_first = LifecycleConfiguration::new().rule(
+            LifecycleRule::new("a")
+                .transition_after_days(60, StorageClass::Glacier)
+                .expire_after_days(30),
+        );
+        assert!(expires_first.validate().is_err());
+
+        let tagged_abort = LifecycleConfiguration::new().rule(
+            LifecycleRule::new("a")
+                .filter(LifecycleFilter::new().tag("a", "b").unwrap())
+                .abort_incomplete_multipart_upload(7),
+        );
+        assert!(tagged_abort.validate().is_err());
+
+        let not_midnight = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("a").expire_at(Utc.ymd(2030, 1, 1).and_hms(12, 0, 0)));
+        assert!(not_midnight.validate().is_err());
+
+        // Legacy rules are told apart by their prefix
+        let legacy = |id: &str, prefix: &str| LifecycleRule {
+            prefix: Some(prefix.to_owned()),
+            filter: None,
+            ..LifecycleRule::new(id).expire_after_days(10)
+        };
+        let prefixes = LifecycleConfiguration::new()
+            .rule(legacy("a", "logs/"))
+            .rule(legacy("b", "tmp/"));
+        assert!(prefixes.validate().is_ok());
+
+        let same_prefix = LifecycleConfiguration::new()
+            .rule(legacy("a", "logs/"))
+            .rule(legacy("b", "logs/"));
+        assert!(same_prefix.validate().is_err());
+
+        // The number of versions to keep may be given before the days
+        let keep = LifecycleRule::new("a")
+            .newer_noncurrent_versions(3)
+            .noncurrent_version_expiration(30);
+        assert_eq!(
+            keep,
+            LifecycleRule::new("a")
+                .noncurrent_version_expiration(30)
+                .newer_noncurrent_versions(3)
+        );
+        assert_eq!(
+            keep.noncurrent_version_expiration
+                .as_ref()
+                .and_then(|expiration| expiration.newer_noncurrent_versions),
+            Some(3)
+        );
+
+        let without_days = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("a").newer_noncurrent_versions(3));
+        assert!(without_days.validate().is_err());
+
+        let disabled = LifecycleConfiguration::new()
+            .rule(LifecycleRule::new("a").expire_after_days(10))
+            .rule(LifecycleRule::new("b").expire_after_days(20).disabled());
+        assert!(disabled.validate().is_ok());
+    }
 }
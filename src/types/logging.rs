@@ -1,27 +1,252 @@
-use super::Grantee;
-use serde::Deserialize;
+use crate::Error;
+use serde::{
+    ser::SerializeStruct,
+    Deserialize,
+    Serialize,
+    Serializer,
+};
 
-#[derive(Debug, Deserialize)]
+/// The server access logging status of a bucket. Logging is disabled when
+/// `logging_enabled` is `None`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "BucketLoggingStatus")]
 #[serde(rename_all = "PascalCase")]
-#[serde(rename = "GetBucketLoggingOutput")]
-pub struct BucketLogging {
-    logging_enabled: LoggingEnabled,
+pub struct BucketLoggingStatus {
+    pub logging_enabled: Option<LoggingEnabled>,
 }
 
-#[derive(Debug, Deserialize)]
+impl BucketLoggingStatus {
+    /// A status which disables logging.
+    pub fn disabled() -> Self {
+        BucketLoggingStatus::default()
+    }
+
+    /// A status which enables logging to the given target.
+    pub fn enabled(logging: LoggingEnabled) -> Self {
+        BucketLoggingStatus {
+            logging_enabled: Some(logging),
+        }
+    }
+
+    /// Check the status for mistakes S3 would reject. This is done by
+    /// `PutBucketLogging` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        let logging = match self.logging_enabled {
+            Some(ref logging) => logging,
+            None => return Ok(()),
+        };
+
+        if logging.target_bucket.is_empty() {
+            return Err(Error::InvalidLogging(
+                "target bucket may not be empty".to_owned(),
+            ));
+        }
+
+        for grant in logging.target_grants.grants.iter() {
+            let grantee = &grant.grantee;
+            let value = match grantee.grantee_type {
+                GranteeType::CanonicalUser => &grantee.id,
+                GranteeType::AmazonCustomerByEmail => &grantee.email_address,
+                GranteeType::Group => &grantee.uri,
+            };
+
+            if value.is_none() {
+                return Err(Error::InvalidLogging(format!(
+                    "{} grantee is missing its {}",
+                    <&'static str>::from(grantee.grantee_type),
+                    match grantee.grantee_type {
+                        GranteeType::CanonicalUser => "id",
+                        GranteeType::AmazonCustomerByEmail => "email address",
+                        GranteeType::Group => "uri",
+                    }
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Where the access logs of a bucket are written and who is given access
+/// to them.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct LoggingEnabled {
-    target_bucket: String,
+    pub target_bucket: String,
 
-    #[serde(rename = "TargetGrant")]
-    target_grants: Vec<TargetGrant>,
+    #[serde(default)]
+    pub target_grants: TargetGrants,
 
-    target_prefix: String,
+    /// The prefix of every log object, e.g. `logs/source-bucket/`.
+    pub target_prefix: String,
 }
 
-#[derive(Debug, Deserialize)]
+impl LoggingEnabled {
+    /// Write logs to `target_bucket`, which must be owned by the same
+    /// account and be in the same region as the source bucket.
+    pub fn new<B: Into<String>, P: Into<String>>(target_bucket: B, target_prefix: P) -> Self {
+        LoggingEnabled {
+            target_bucket: target_bucket.into(),
+            target_grants: TargetGrants::default(),
+            target_prefix: target_prefix.into(),
+        }
+    }
+
+    /// Grant access to the log objects written.
+    pub fn grant(mut self, grantee: TargetGrantee, permission: BucketLogsPermission) -> Self {
+        self.target_grants.grants.push(TargetGrant {
+            grantee,
+            permission,
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct TargetGrants {
+    #[serde(rename = "Grant", default)]
+    pub grants: Vec<TargetGrant>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct TargetGrant {
-    grantee: Option<Grantee>,
-    permission: Option<String>,
+    pub grantee: TargetGrantee,
+    pub permission: BucketLogsPermission,
+}
+
+/// A grantee of the log objects, identified by the field matching its
+/// type.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct TargetGrantee {
+    #[serde(rename = "xsi:type")]
+    pub grantee_type: GranteeType,
+
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+
+    #[serde(rename = "DisplayName")]
+    pub display_name: Option<String>,
+
+    #[serde(rename = "EmailAddress")]
+    pub email_address: Option<String>,
+
+    #[serde(rename = "URI")]
+    pub uri: Option<String>,
+}
+
+impl TargetGrantee {
+    /// The account or user with the given canonical id.
+    pub fn canonical_user<T: Into<String>>(id: T) -> Self {
+        TargetGrantee {
+            id: Some(id.into()),
+            ..TargetGrantee::empty(GranteeType::CanonicalUser)
+        }
+    }
+
+    /// The account with the given email address. This is only supported
+    /// in some of the older regions.
+    pub fn email<T: Into<String>>(email_address: T) -> Self {
+        TargetGrantee {
+            email_address: Some(email_address.into()),
+            ..TargetGrantee::empty(GranteeType::AmazonCustomerByEmail)
+        }
+    }
+
+    /// A predefined group, e.g.
+    /// `http://acs.amazonaws.com/groups/global/AuthenticatedUsers`.
+    pub fn group<T: Into<String>>(uri: T) -> Self {
+        TargetGrantee {
+            uri: Some(uri.into()),
+            ..TargetGrantee::empty(GranteeType::Group)
+        }
+    }
+
+    fn empty(grantee_type: GranteeType) -> Self {
+        TargetGrantee {
+            grantee_type,
+            id: None,
+            display_name: None,
+            email_address: None,
+            uri: None,
+        }
+    }
+}
+
+// The type is written as an `xsi:type` attribute, which needs the namespace
+// declared on the element itself.
+impl Serialize for TargetGrantee {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut grantee = serializer.serialize_struct("Grantee", 6)?;
+        grantee.serialize_field("@xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance")?;
+        grantee.serialize_field("@xsi:type", &self.grantee_type)?;
+        grantee.serialize_field("ID", &self.id)?;
+        grantee.serialize_field("DisplayName", &self.display_name)?;
+        grantee.serialize_field("EmailAddress", &self.email_address)?;
+        grantee.serialize_field("URI", &self.uri)?;
+        grantee.end()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GranteeType {
+    CanonicalUser,
+    AmazonCustomerByEmail,
+    Group,
+}
+
+impl_text_enum!(GranteeType, "grantee type", {
+    CanonicalUser,
+    AmazonCustomerByEmail,
+    Group,
+});
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BucketLogsPermission {
+    FullControl,
+    Read,
+    Write,
+}
+
+impl_text_enum!(BucketLogsPermission, "bucket logs permission", {
+    FullControl = "FULL_CONTROL",
+    Read = "READ",
+    Write = "WRITE",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn status_round_trip() {
+        let status = BucketLoggingStatus::enabled(LoggingEnabled::new("logs", "source/").grant(
+            TargetGrantee::group("http://acs.amazonaws.com/groups/global/AuthenticatedUsers"),
+            BucketLogsPermission::Read,
+        ));
+
+        let string = xml::to_string(&status).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<BucketLoggingStatus xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<LoggingEnabled><TargetBucket>logs</TargetBucket><TargetGrants><Grant>",
+                r#"<Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Group">"#,
+                "<URI>http://acs.amazonaws.com/groups/global/AuthenticatedUsers</URI></Grantee>",
+                "<Permission>READ</Permission></Grant></TargetGrants>",
+                "<TargetPrefix>source/</TargetPrefix></LoggingEnabled></BucketLoggingStatus>"
+            )
+        );
+
+        let parsed: BucketLoggingStatus = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, status);
+        assert!(parsed.validate().is_ok());
+
+        let disabled = xml::to_string(&BucketLoggingStatus::disabled()).unwrap();
+        assert_eq!(
+            disabled,
+            r#"<BucketLoggingStatus xmlns="http://s3.amazonaws.com/doc/2006-03-01/"></BucketLoggingStatus>"#
+        );
+    }
 }
@@ -1,38 +1,177 @@
-use super::Tag;
-use serde::Deserialize;
+use super::{
+    FilterElement,
+    TagSet,
+};
+use crate::Error;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+/// A request metrics configuration of a bucket, which publishes CloudWatch
+/// request metrics for the filtered objects.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename = "MetricsConfiguration")]
-pub struct BucketMetrics {
-    #[serde(rename = "ID")]
-    id: String,
-    filter: MetricsFilter,
+#[serde(rename_all = "PascalCase")]
+pub struct MetricsConfiguration {
+    pub id: String,
+    pub filter: Option<MetricsFilter>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+impl MetricsConfiguration {
+    /// Create a new configuration publishing metrics for every object of
+    /// the bucket.
+    pub fn new<T: Into<String>>(id: T) -> Self {
+        MetricsConfiguration {
+            id: id.into(),
+            filter: None,
+        }
+    }
+
+    /// Only publish metrics for the objects matching `filter`.
+    pub fn filter(mut self, filter: MetricsFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketMetricsConfiguration` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !super::is_valid_configuration_id(&self.id) {
+            return Err(Error::InvalidMetrics(format!(
+                "id {:?} must be between 1 and {} letters, numbers, `-`, `_` or `.`",
+                self.id,
+                super::MAX_CONFIGURATION_ID_LENGTH
+            )));
+        }
+
+        if let Some(ref filter) = self.filter {
+            if let Some(ref arn) = filter.access_point_arn {
+                if !arn.starts_with("arn:") {
+                    return Err(Error::InvalidMetrics(format!(
+                        "access point must be an arn, found {:?}",
+                        arn
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Selects the objects request metrics are published for. Objects must
+/// match the prefix, every tag and be requested through the access point.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "FilterElement", into = "FilterElement")]
 pub struct MetricsFilter {
-    and: Option<MetricsAndOperator>,
-    prefix: Option<String>,
-    tag: Option<Tag>,
+    pub prefix: Option<String>,
+    pub tags: TagSet,
+    pub access_point_arn: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct MetricsAndOperator {
-    prefix: Option<String>,
+impl MetricsFilter {
+    /// Create a new filter which matches every object
+    pub fn new() -> Self {
+        MetricsFilter::default()
+    }
+
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only match objects with this tag.
+    pub fn tag<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Result<Self, Error> {
+        self.tags.insert(key, value)?;
+        Ok(self)
+    }
+
+    /// Only match requests made through the access point with this arn.
+    pub fn access_point_arn<T: Into<String>>(mut self, arn: T) -> Self {
+        self.access_point_arn = Some(arn.into());
+        self
+    }
+}
+
+impl From<FilterElement> for MetricsFilter {
+    fn from(element: FilterElement) -> Self {
+        let (prefix, tags, access_point_arn) = element.into_parts();
+
+        MetricsFilter {
+            prefix,
+            tags,
+            access_point_arn,
+        }
+    }
+}
 
-    #[serde(rename = "Tag")]
-    tags: Vec<Tag>,
+impl From<MetricsFilter> for FilterElement {
+    fn from(filter: MetricsFilter) -> Self {
+        FilterElement::new(filter.prefix, filter.tags, filter.access_point_arn)
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// A page of `ListBucketMetricsConfigurations`.
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "PascalCase")]
-#[serde(rename = "ListBucketMetricsConfigurationsOutput")]
-pub struct BucketMetricsConfigs {
-    metrics_configuration: Vec<BucketMetrics>,
-    continuation_token: String,
-    is_truncated: bool,
-    next_continuation_token: String,
+pub struct ListMetricsConfigurationsResult {
+    pub continuation_token: Option<String>,
+
+    #[serde(rename = "MetricsConfiguration", default)]
+    pub metrics_configurations: Vec<MetricsConfiguration>,
+
+    pub is_truncated: bool,
+
+    /// The token to request the next page with, if the list is truncated.
+    pub next_continuation_token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn filter_round_trip() {
+        let single = MetricsConfiguration::new("documents")
+            .filter(MetricsFilter::new().tag("class", "blue").unwrap());
+
+        let string = xml::to_string(&single).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<MetricsConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Id>documents</Id><Filter><Tag><Key>class</Key><Value>blue</Value></Tag></Filter>",
+                "</MetricsConfiguration>"
+            )
+        );
+        assert_eq!(
+            quick_xml::de::from_str::<MetricsConfiguration>(&string).unwrap(),
+            single
+        );
+
+        let combined = MetricsConfiguration::new("documents").filter(
+            MetricsFilter::new()
+                .prefix("docs/")
+                .access_point_arn("arn:aws:s3:us-west-2:123456789012:accesspoint/reader"),
+        );
+
+        let string = xml::to_string(&combined).unwrap();
+        assert!(string.contains(concat!(
+            "<Filter><And><Prefix>docs/</Prefix>",
+            "<AccessPointArn>arn:aws:s3:us-west-2:123456789012:accesspoint/reader</AccessPointArn>",
+            "</And></Filter>"
+        )));
+        assert_eq!(
+            quick_xml::de::from_str::<MetricsConfiguration>(&string).unwrap(),
+            combined
+        );
+        assert!(combined.validate().is_ok());
+        assert!(MetricsConfiguration::new("has space").validate().is_err());
+    }
 }
@@ -1,8 +1,63 @@
+// Implements `FromStr`, `From<$name> for &'static str` and the text based
+// serde implementations for an enum written as the variant name unless
+// given otherwise. Any aliases after the text are only accepted when
+// parsing.
+macro_rules! impl_text_enum {
+    ($name: ident, $description: literal, { $($variant: ident $(= $text: literal $(| $alias: literal)*)?),* $(,)? }) => {
+        impl From<$name> for &'static str {
+            fn from(value: $name) -> &'static str {
+                match value {
+                    $($name::$variant => impl_text_enum!(@text $variant $($text)?),)*
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = crate::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(
+                    if s == impl_text_enum!(@text $variant $($text)?) $($(|| s == $alias)*)? {
+                        return Ok($name::$variant);
+                    }
+                )*
+
+                Err(crate::Error::from(crate::error::Internal::Message(format!(
+                    concat!("unknown ", $description, " {:?}"),
+                    s
+                ))))
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str((*self).into())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                crate::xml::from_text(deserializer)
+            }
+        }
+    };
+    (@text $variant: ident $text: literal) => {
+        $text
+    };
+    (@text $variant: ident) => {
+        stringify!($variant)
+    };
+}
+
+pub mod accelerate;
 pub mod acl;
 pub mod analytics;
 pub mod bucket;
+pub mod copy;
 pub mod cors;
+pub mod delete;
 pub mod encryption;
+pub mod event;
 pub mod grant;
 pub mod inventory;
 pub mod legal_hold;
@@ -23,11 +78,15 @@ pub mod tag;
 pub mod version;
 pub mod website;
 
+pub use accelerate::*;
 pub use acl::*;
 pub use analytics::*;
 pub use bucket::*;
+pub use copy::*;
 pub use cors::*;
+pub use delete::*;
 pub use encryption::*;
+pub use event::*;
 pub use grant::*;
 pub use inventory::*;
 pub use legal_hold::*;
@@ -47,3 +106,78 @@ pub use retention::*;
 pub use tag::*;
 pub use version::*;
 pub use website::*;
+
+// Limits enforced by S3 on the id of an inventory, analytics or metrics
+// configuration
+pub(crate) const MAX_CONFIGURATION_ID_LENGTH: usize = 64;
+
+pub(crate) fn is_valid_configuration_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_CONFIGURATION_ID_LENGTH
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-_.".contains(&b))
+}
+
+// The layout of the analytics, metrics and replication filters on the wire.
+// A filter is written as a single condition, or as conditions wrapped in an
+// `And` element once there is more than one.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct FilterElement {
+    prefix: Option<String>,
+    tag: Option<Tag>,
+    access_point_arn: Option<String>,
+    and: Option<FilterAnd>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct FilterAnd {
+    prefix: Option<String>,
+    #[serde(rename = "Tag", default)]
+    tags: Vec<Tag>,
+    access_point_arn: Option<String>,
+}
+
+impl FilterElement {
+    pub(crate) fn new(
+        prefix: Option<String>,
+        tags: TagSet,
+        access_point_arn: Option<String>,
+    ) -> Self {
+        let conditions =
+            prefix.is_some() as usize + tags.len() + access_point_arn.is_some() as usize;
+
+        if conditions > 1 {
+            FilterElement {
+                and: Some(FilterAnd {
+                    prefix,
+                    tags: tags.tags,
+                    access_point_arn,
+                }),
+                ..FilterElement::default()
+            }
+        } else {
+            FilterElement {
+                prefix,
+                tag: tags.tags.into_iter().next(),
+                access_point_arn,
+                and: None,
+            }
+        }
+    }
+
+    /// The prefix, tags and access point arn of the filter.
+    pub(crate) fn into_parts(self) -> (Option<String>, TagSet, Option<String>) {
+        let and = self.and.unwrap_or_default();
+
+        (
+            self.prefix.or(and.prefix),
+            TagSet {
+                tags: self.tag.into_iter().chain(and.tags).collect(),
+            },
+            self.access_point_arn.or(and.access_point_arn),
+        )
+    }
+}
@@ -1,5 +1,8 @@
 use super::Owner;
-use serde::Deserialize;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename = "ListMultipartUploadsOutput")]
@@ -21,7 +24,7 @@ pub struct MultipartUploads {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CommonPrefix {
-    prefix: Option<String>,
+    pub prefix: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -43,3 +46,37 @@ pub struct Initiator {
 
     display_name: Option<String>,
 }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct InitiateMultipartUploadResult {
+    pub bucket: String,
+    pub key: String,
+    pub upload_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompleteMultipartUpload {
+    #[serde(rename = "Part")]
+    pub parts: Vec<CompletedPart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompletedPart {
+    pub part_number: u64,
+
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadResult {
+    pub location: Option<String>,
+    pub bucket: String,
+    pub key: String,
+
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
@@ -1,54 +1,338 @@
-use serde::Deserialize;
+use crate::Error;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+/// The notification configuration of a bucket, which publishes events to
+/// SNS topics, SQS queues and Lambda functions. An empty configuration
+/// disables notifications.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "NotificationConfiguration")]
 pub struct NotificationConfiguration {
-    cloud_function_config: Vec<LamdaFunctionConfiguration>,
-    queue_config: Vec<QueueConfiguration>,
-    topic_config: Vec<TopicConfiguration>,
+    #[serde(rename = "TopicConfiguration", default)]
+    pub topics: Vec<TopicConfiguration>,
+
+    #[serde(rename = "QueueConfiguration", default)]
+    pub queues: Vec<QueueConfiguration>,
+
+    #[serde(rename = "CloudFunctionConfiguration", default)]
+    pub lambda_functions: Vec<LambdaFunctionConfiguration>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct TopicConfiguration {
-    events: Vec<String>,
-    filter: Option<NotificationConfigFilter>,
-    id: Option<String>,
-    topic_arn: String,
+impl NotificationConfiguration {
+    /// Create a new empty notification configuration
+    pub fn new() -> Self {
+        NotificationConfiguration::default()
+    }
+
+    pub fn topic(mut self, topic: TopicConfiguration) -> Self {
+        self.topics.push(topic);
+        self
+    }
+
+    pub fn queue(mut self, queue: QueueConfiguration) -> Self {
+        self.queues.push(queue);
+        self
+    }
+
+    pub fn lambda_function(mut self, function: LambdaFunctionConfiguration) -> Self {
+        self.lambda_functions.push(function);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketNotificationConfiguration` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        let destinations = self
+            .topics
+            .iter()
+            .map(|topic| (&topic.id, &topic.events, &topic.filter))
+            .chain(
+                self.queues
+                    .iter()
+                    .map(|queue| (&queue.id, &queue.events, &queue.filter)),
+            )
+            .chain(
+                self.lambda_functions
+                    .iter()
+                    .map(|function| (&function.id, &function.events, &function.filter)),
+            );
+
+        for (index, (id, events, filter)) in destinations.enumerate() {
+            let name = id.clone().unwrap_or_else(|| index.to_string());
+
+            if events.is_empty() {
+                return Err(Error::InvalidNotification(format!(
+                    "configuration {} needs at least one event",
+                    name
+                )));
+            }
+
+            if let Some(filter) = filter {
+                for rule_name in &[FilterRuleName::Prefix, FilterRuleName::Suffix] {
+                    let count = filter
+                        .key
+                        .filter_rules
+                        .iter()
+                        .filter(|rule| rule.name == *rule_name)
+                        .count();
+                    if count > 1 {
+                        return Err(Error::InvalidNotification(format!(
+                            "configuration {} may have at most one {} filter rule",
+                            name,
+                            <&'static str>::from(*rule_name)
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct QueueConfiguration {
-    events: Vec<String>,
-    filter: Option<NotificationConfigFilter>,
-    id: Option<String>,
-    queue_arn: String,
+// The three destinations only differ in the element holding their arn
+macro_rules! impl_notification_destination {
+    ($name: ident, $arn: ident, $element: literal) => {
+        #[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+        pub struct $name {
+            #[serde(rename = "Id")]
+            pub id: Option<String>,
+
+            #[serde(rename = $element)]
+            pub $arn: String,
+
+            #[serde(rename = "Event", default)]
+            pub events: Vec<NotificationEvent>,
+
+            #[serde(rename = "Filter")]
+            pub filter: Option<NotificationFilter>,
+        }
+
+        impl $name {
+            /// Create a new configuration publishing to the given arn. At
+            /// least one event must be added.
+            pub fn new<T: Into<String>>($arn: T) -> Self {
+                $name {
+                    id: None,
+                    $arn: $arn.into(),
+                    events: Vec::new(),
+                    filter: None,
+                }
+            }
+
+            /// A unique identifier for the configuration.
+            pub fn id<T: Into<String>>(mut self, id: T) -> Self {
+                self.id = Some(id.into());
+                self
+            }
+
+            pub fn event(mut self, event: NotificationEvent) -> Self {
+                self.events.push(event);
+                self
+            }
+
+            /// Only publish events for keys starting with `prefix`.
+            pub fn prefix<T: Into<String>>(self, prefix: T) -> Self {
+                self.filter_rule(FilterRuleName::Prefix, prefix.into())
+            }
+
+            /// Only publish events for keys ending with `suffix`.
+            pub fn suffix<T: Into<String>>(self, suffix: T) -> Self {
+                self.filter_rule(FilterRuleName::Suffix, suffix.into())
+            }
+
+            fn filter_rule(mut self, name: FilterRuleName, value: String) -> Self {
+                self.filter
+                    .get_or_insert_with(NotificationFilter::default)
+                    .key
+                    .filter_rules
+                    .push(FilterRule { name, value });
+                self
+            }
+
+            /// Whether an event for `key` is published to this destination.
+            pub fn matches(&self, event: NotificationEvent, key: &str) -> bool {
+                self.events.iter().any(|pattern| pattern.covers(event))
+                    && self.filter.iter().all(|filter| filter.matches(key))
+            }
+        }
+    };
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct LamdaFunctionConfiguration {
-    events: Vec<String>,
-    filter: Option<NotificationConfigFilter>,
-    id: Option<String>,
-    lambda_function_arn: String,
+impl_notification_destination!(TopicConfiguration, topic_arn, "Topic");
+impl_notification_destination!(QueueConfiguration, queue_arn, "Queue");
+impl_notification_destination!(
+    LambdaFunctionConfiguration,
+    lambda_function_arn,
+    "CloudFunction"
+);
+
+/// Limits the keys a destination is notified about.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct NotificationFilter {
+    #[serde(rename = "S3Key", default)]
+    pub key: S3KeyFilter,
 }
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct NotificationConfigFilter {
-    key: Option<S3KeyFilter>,
+
+impl NotificationFilter {
+    pub fn matches(&self, key: &str) -> bool {
+        self.key.filter_rules.iter().all(|rule| match rule.name {
+            FilterRuleName::Prefix => key.starts_with(rule.value.as_str()),
+            FilterRuleName::Suffix => key.ends_with(rule.value.as_str()),
+        })
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub struct S3KeyFilter {
-    filter_rules: Vec<FilterRule>,
+    #[serde(rename = "FilterRule", default)]
+    pub filter_rules: Vec<FilterRule>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct FilterRule {
-    name: Option<String>,
-    value: Option<String>,
+    pub name: FilterRuleName,
+    pub value: String,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FilterRuleName {
+    Prefix,
+    Suffix,
+}
+
+// S3 returns the names capitalized even though they are sent in lowercase
+impl_text_enum!(FilterRuleName, "filter rule name", {
+    Prefix = "prefix" | "Prefix",
+    Suffix = "suffix" | "Suffix",
+});
+
+/// The event types a destination can be notified about. The `*All`
+/// variants cover every event of their kind.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NotificationEvent {
+    ObjectCreatedAll,
+    ObjectCreatedPut,
+    ObjectCreatedPost,
+    ObjectCreatedCopy,
+    ObjectCreatedCompleteMultipartUpload,
+    ObjectRemovedAll,
+    ObjectRemovedDelete,
+    ObjectRemovedDeleteMarkerCreated,
+    ObjectRestoreAll,
+    ObjectRestorePost,
+    ObjectRestoreCompleted,
+    ObjectRestoreDelete,
+    ReducedRedundancyLostObject,
+    ReplicationAll,
+    ReplicationOperationFailedReplication,
+    ReplicationOperationMissedThreshold,
+    ReplicationOperationReplicatedAfterThreshold,
+    ReplicationOperationNotTracked,
+    LifecycleExpirationAll,
+    LifecycleExpirationDelete,
+    LifecycleExpirationDeleteMarkerCreated,
+    LifecycleTransition,
+    IntelligentTiering,
+    ObjectTaggingAll,
+    ObjectTaggingPut,
+    ObjectTaggingDelete,
+    ObjectAclPut,
+}
+
+// Written as text in both the xml configuration and json event records
+impl_text_enum!(NotificationEvent, "notification event", {
+    ObjectCreatedAll = "s3:ObjectCreated:*",
+    ObjectCreatedPut = "s3:ObjectCreated:Put",
+    ObjectCreatedPost = "s3:ObjectCreated:Post",
+    ObjectCreatedCopy = "s3:ObjectCreated:Copy",
+    ObjectCreatedCompleteMultipartUpload = "s3:ObjectCreated:CompleteMultipartUpload",
+    ObjectRemovedAll = "s3:ObjectRemoved:*",
+    ObjectRemovedDelete = "s3:ObjectRemoved:Delete",
+    ObjectRemovedDeleteMarkerCreated = "s3:ObjectRemoved:DeleteMarkerCreated",
+    ObjectRestoreAll = "s3:ObjectRestore:*",
+    ObjectRestorePost = "s3:ObjectRestore:Post",
+    ObjectRestoreCompleted = "s3:ObjectRestore:Completed",
+    ObjectRestoreDelete = "s3:ObjectRestore:Delete",
+    ReducedRedundancyLostObject = "s3:ReducedRedundancyLostObject",
+    ReplicationAll = "s3:Replication:*",
+    ReplicationOperationFailedReplication = "s3:Replication:OperationFailedReplication",
+    ReplicationOperationMissedThreshold = "s3:Replication:OperationMissedThreshold",
+    ReplicationOperationReplicatedAfterThreshold = "s3:Replication:OperationReplicatedAfterThreshold",
+    ReplicationOperationNotTracked = "s3:Replication:OperationNotTracked",
+    LifecycleExpirationAll = "s3:LifecycleExpiration:*",
+    LifecycleExpirationDelete = "s3:LifecycleExpiration:Delete",
+    LifecycleExpirationDeleteMarkerCreated = "s3:LifecycleExpiration:DeleteMarkerCreated",
+    LifecycleTransition = "s3:LifecycleTransition",
+    IntelligentTiering = "s3:IntelligentTiering",
+    ObjectTaggingAll = "s3:ObjectTagging:*",
+    ObjectTaggingPut = "s3:ObjectTagging:Put",
+    ObjectTaggingDelete = "s3:ObjectTagging:Delete",
+    ObjectAclPut = "s3:ObjectAcl:Put",
+});
+
+impl NotificationEvent {
+    /// Whether a destination configured for `self` is notified of `event`,
+    /// e.g. `s3:ObjectCreated:*` covers `s3:ObjectCreated:Put`.
+    pub fn covers(self, event: NotificationEvent) -> bool {
+        let pattern = <&'static str>::from(self);
+        match pattern.strip_suffix('*') {
+            Some(prefix) => <&'static str>::from(event).starts_with(prefix),
+            None => self == event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = NotificationConfiguration::new()
+            .queue(
+                QueueConfiguration::new("arn:aws:sqs:us-east-1:123456789012:uploads")
+                    .id("images")
+                    .event(NotificationEvent::ObjectCreatedAll)
+                    .prefix("images/")
+                    .suffix(".jpg"),
+            )
+            .lambda_function(
+                LambdaFunctionConfiguration::new(
+                    "arn:aws:lambda:us-east-1:123456789012:function:cleanup",
+                )
+                .event(NotificationEvent::ObjectRemovedDelete),
+            );
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<NotificationConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<QueueConfiguration><Id>images</Id>",
+                "<Queue>arn:aws:sqs:us-east-1:123456789012:uploads</Queue>",
+                "<Event>s3:ObjectCreated:*</Event><Filter><S3Key>",
+                "<FilterRule><Name>prefix</Name><Value>images/</Value></FilterRule>",
+                "<FilterRule><Name>suffix</Name><Value>.jpg</Value></FilterRule>",
+                "</S3Key></Filter></QueueConfiguration>",
+                "<CloudFunctionConfiguration>",
+                "<CloudFunction>arn:aws:lambda:us-east-1:123456789012:function:cleanup</CloudFunction>",
+                "<Event>s3:ObjectRemoved:Delete</Event></CloudFunctionConfiguration>",
+                "</NotificationConfiguration>"
+            )
+        );
+
+        let parsed: NotificationConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(config.validate().is_ok());
+
+        let queue = &config.queues[0];
+        assert!(queue.matches(NotificationEvent::ObjectCreatedPut, "images/cat.jpg"));
+        assert!(!queue.matches(NotificationEvent::ObjectCreatedPut, "images/cat.png"));
+        assert!(!queue.matches(NotificationEvent::ObjectRemovedDelete, "images/cat.jpg"));
+    }
 }
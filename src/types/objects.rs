@@ -2,34 +2,51 @@ use super::{
     CommonPrefix,
     Owner,
 };
+use crate::StorageClass;
+use chrono::{
+    DateTime,
+    Utc,
+};
 use serde::Deserialize;
 
+/// A page of `ListObjectsV2`.
 #[derive(Debug, Deserialize)]
-#[serde(rename = "ListObjectsV2Output")]
+#[serde(rename = "ListBucketResult")]
 #[serde(rename_all = "PascalCase")]
-pub struct Objects {
-    common_prefixes: Vec<CommonPrefix>,
-    contents: Vec<Object>,
-    continuation_token: String,
-    delimiter: String,
-    encoding_type: String,
-    is_truncated: bool,
-    key_count: u32,
-    max_keys: i32,
-    name: String,
-    next_continuation_token: String,
-    prefix: String,
-    start_after: String,
+pub struct ListBucketResult {
+    pub name: String,
+    pub prefix: Option<String>,
+    pub delimiter: Option<String>,
+    pub start_after: Option<String>,
+    pub key_count: u32,
+    pub max_keys: u32,
+    pub is_truncated: bool,
+    pub continuation_token: Option<String>,
+
+    /// The token to request the next page with, if the list is truncated.
+    pub next_continuation_token: Option<String>,
+
+    #[serde(default)]
+    pub contents: Vec<Object>,
+
+    #[serde(default)]
+    pub common_prefixes: Vec<CommonPrefix>,
 }
 
 #[derive(Debug, Deserialize)]
-#[serde(rename = "ListObjectsResponse")]
 #[serde(rename_all = "PascalCase")]
 pub struct Object {
-    etag: Option<String>,
-    key: Option<String>,
-    last_modified: Option<String>,
-    owner: Option<Owner>,
-    size: Option<u32>,
-    storage_class: Option<String>,
+    pub key: String,
+    pub last_modified: DateTime<Utc>,
+    #[serde(rename = "ETag")]
+    pub etag: Option<String>,
+    pub size: u64,
+    #[serde(default = "standard")]
+    pub storage_class: StorageClass,
+    pub owner: Option<Owner>,
+}
+
+// Objects listed without a storage class are stored as `STANDARD`
+pub(super) fn standard() -> StorageClass {
+    StorageClass::Standard
 }
@@ -1,7 +1,454 @@
-use serde::Deserialize;
+use serde::{
+    de::{
+        self,
+        MapAccess,
+        Visitor,
+    },
+    ser::SerializeMap,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+};
 
-#[derive(Debug, Deserialize)]
+/// The current version of the policy language
+pub const POLICY_VERSION: &str = "2012-10-17";
+
+/// Whether S3 considers a bucket public, based on its policy and acls.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename = "PolicyStatus")]
 pub struct PolicyStatus {
     #[serde(rename = "IsPublic")]
-    is_public: bool,
+    pub is_public: bool,
+}
+
+/// A bucket policy written in the IAM policy language. Policies are read
+/// and written as json, keeping the layout of the original document: a
+/// single value stays a single value, and objects keep their key order.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Policy {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    pub statement: OneOrMany<Statement>,
+}
+
+impl Policy {
+    /// Create a new policy without any statements using the current version
+    /// of the policy language
+    pub fn new() -> Self {
+        Policy {
+            version: Some(POLICY_VERSION.to_owned()),
+            id: None,
+            statement: OneOrMany::Many(Vec::new()),
+        }
+    }
+
+    /// Add a statement to the policy.
+    pub fn statement(mut self, statement: Statement) -> Self {
+        self.statement.push(statement);
+        self
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::new()
+    }
+}
+
+/// A single permission of a policy.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Statement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+
+    pub effect: Effect,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub principal: Option<Principal>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_principal: Option<Principal>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<OneOrMany<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_action: Option<OneOrMany<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<OneOrMany<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not_resource: Option<OneOrMany<String>>,
+
+    /// Condition operators, e.g. `StringLike`, mapped to the condition keys
+    /// they test and the values to test against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<PolicyMap<PolicyMap<OneOrMany<ConditionValue>>>>,
+}
+
+impl Statement {
+    /// Create a new statement allowing nothing yet
+    pub fn allow() -> Self {
+        Statement::new(Effect::Allow)
+    }
+
+    /// Create a new statement denying nothing yet
+    pub fn deny() -> Self {
+        Statement::new(Effect::Deny)
+    }
+
+    fn new(effect: Effect) -> Self {
+        Statement {
+            sid: None,
+            effect,
+            principal: None,
+            not_principal: None,
+            action: None,
+            not_action: None,
+            resource: None,
+            not_resource: None,
+            condition: None,
+        }
+    }
+
+    pub fn sid<T: Into<String>>(mut self, sid: T) -> Self {
+        self.sid = Some(sid.into());
+        self
+    }
+
+    /// Add a principal the statement applies to, e.g. `("AWS", "arn:aws:iam::123456789012:root")`.
+    /// A statement applying to every principal is left as is.
+    pub fn principal<K: Into<String>, V: Into<String>>(mut self, kind: K, principal: V) -> Self {
+        // `Principal::Any` already includes every principal, so it is kept
+        // rather than narrowed down to the given one
+        match self.principal {
+            Some(Principal::Any) => {}
+            Some(Principal::Map(ref mut principals)) => {
+                principals.entry(kind.into()).push(principal.into());
+            }
+            None => {
+                let mut principals = PolicyMap::<OneOrMany<String>>::new();
+                principals.entry(kind.into()).push(principal.into());
+                self.principal = Some(Principal::Map(principals));
+            }
+        }
+        self
+    }
+
+    /// Apply the statement to every principal, including anonymous users.
+    pub fn any_principal(mut self) -> Self {
+        self.principal = Some(Principal::Any);
+        self
+    }
+
+    /// Add an action, e.g. `s3:GetObject`. Actions may contain wildcards.
+    pub fn action<T: Into<String>>(mut self, action: T) -> Self {
+        self.action
+            .get_or_insert_with(OneOrMany::default)
+            .push(action.into());
+        self
+    }
+
+    /// Add a resource, e.g. `arn:aws:s3:::bucket/*`. Resources may contain wildcards.
+    pub fn resource<T: Into<String>>(mut self, resource: T) -> Self {
+        self.resource
+            .get_or_insert_with(OneOrMany::default)
+            .push(resource.into());
+        self
+    }
+
+    /// Add a condition, e.g. `("Bool", "aws:SecureTransport", false)`.
+    pub fn condition<O, K, V>(mut self, operator: O, key: K, value: V) -> Self
+    where
+        O: Into<String>,
+        K: Into<String>,
+        V: Into<ConditionValue>,
+    {
+        self.condition
+            .get_or_insert_with(PolicyMap::new)
+            .entry(operator.into())
+            .entry(key.into())
+            .push(value.into());
+        self
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+/// The principals a statement applies to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Principal {
+    /// `"*"`, every principal including anonymous users.
+    Any,
+
+    /// Principals by kind, e.g. `AWS`, `Service` or `CanonicalUser`.
+    Map(PolicyMap<OneOrMany<String>>),
+}
+
+impl Serialize for Principal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Principal::Any => serializer.serialize_str("*"),
+            Principal::Map(principals) => principals.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Principal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Map(PolicyMap<OneOrMany<String>>),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::String(ref any) if any == "*" => Ok(Principal::Any),
+            Repr::String(other) => Err(de::Error::custom(format!(
+                "principal must be \"*\" or an object, found {:?}",
+                other
+            ))),
+            Repr::Map(principals) => Ok(Principal::Map(principals)),
+        }
+    }
+}
+
+/// A value of a condition. Policies may give booleans and numbers either
+/// as json values or as strings, both are kept as written.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ConditionValue {
+    String(String),
+    Bool(bool),
+    Number(serde_json::Number),
+}
+
+impl ConditionValue {
+    /// The value as the string it is compared as.
+    pub fn to_string_value(&self) -> String {
+        match self {
+            ConditionValue::String(value) => value.clone(),
+            ConditionValue::Bool(value) => value.to_string(),
+            ConditionValue::Number(value) => value.to_string(),
+        }
+    }
+}
+
+impl From<&str> for ConditionValue {
+    fn from(value: &str) -> Self {
+        ConditionValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for ConditionValue {
+    fn from(value: String) -> Self {
+        ConditionValue::String(value)
+    }
+}
+
+impl From<bool> for ConditionValue {
+    fn from(value: bool) -> Self {
+        ConditionValue::Bool(value)
+    }
+}
+
+/// Either a single value or a list of values, which the policy language
+/// treats the same.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum OneOrMany<T> {
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> OneOrMany<T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        match self {
+            OneOrMany::One(value) => std::slice::from_ref(value).iter(),
+            OneOrMany::Many(values) => values.iter(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add a value, turning a single value into a list.
+    pub fn push(&mut self, value: T) {
+        match self {
+            OneOrMany::Many(values) => values.push(value),
+            OneOrMany::One(_) => {
+                if let OneOrMany::One(first) = std::mem::take(self) {
+                    *self = OneOrMany::Many(vec![first, value]);
+                }
+            }
+        }
+    }
+}
+
+impl<T> Default for OneOrMany<T> {
+    fn default() -> Self {
+        OneOrMany::Many(Vec::new())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a OneOrMany<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A json object which keeps its keys in the order they were written.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyMap<V>(pub Vec<(String, V)>);
+
+impl<V> PolicyMap<V> {
+    pub fn new() -> Self {
+        PolicyMap(Vec::new())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    fn entry(&mut self, key: String) -> &mut V
+    where
+        V: Default,
+    {
+        let index = match self.0.iter().position(|(k, _)| *k == key) {
+            Some(index) => index,
+            None => {
+                self.0.push((key, V::default()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[index].1
+    }
+}
+
+impl<V> Default for PolicyMap<V> {
+    fn default() -> Self {
+        PolicyMap::new()
+    }
+}
+
+impl<V: Serialize> Serialize for PolicyMap<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in self.0.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for PolicyMap<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for MapVisitor<V> {
+            type Value = PolicyMap<V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a json object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = access.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(PolicyMap(entries))
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_round_trip() {
+        let json = concat!(
+            r#"{"Version":"2012-10-17","Id":"example","Statement":["#,
+            r#"{"Sid":"PublicRead","Effect":"Allow","Principal":"*","Action":"s3:GetObject","#,
+            r#""Resource":["arn:aws:s3:::bucket/public/*","arn:aws:s3:::bucket/shared/*"]},"#,
+            r#"{"Effect":"Deny","NotPrincipal":{"AWS":["arn:aws:iam::123456789012:root"],"#,
+            r#""Service":"logging.s3.amazonaws.com"},"NotAction":["s3:Get*"],"#,
+            r#""NotResource":"arn:aws:s3:::bucket/logs/*","#,
+            r#""Condition":{"StringNotLike":{"aws:Referer":"https://*.example.com/*"},"#,
+            r#""Bool":{"aws:SecureTransport":false},"NumericLessThan":{"s3:max-keys":"10"}}}]}"#
+        );
+
+        let policy: Policy = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&policy).unwrap(), json);
+
+        let statements = policy.statement.iter().collect::<Vec<_>>();
+        assert_eq!(statements[0].principal, Some(Principal::Any));
+        assert_eq!(statements[1].effect, Effect::Deny);
+
+        let built = Policy::new().statement(
+            Statement::allow()
+                .sid("PublicRead")
+                .any_principal()
+                .action("s3:GetObject")
+                .resource("arn:aws:s3:::bucket/*")
+                .condition("Bool", "aws:SecureTransport", true),
+        );
+        assert_eq!(
+            serde_json::to_string(&built).unwrap(),
+            concat!(
+                r#"{"Version":"2012-10-17","Statement":[{"Sid":"PublicRead","Effect":"Allow","#,
+                r#""Principal":"*","Action":["s3:GetObject"],"Resource":["arn:aws:s3:::bucket/*"],"#,
+                r#""Condition":{"Bool":{"aws:SecureTransport":[true]}}}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn principals_dont_narrow_any_principal() {
+        let statement = Statement::allow()
+            .any_principal()
+            .principal("AWS", "arn:aws:iam::123456789012:root");
+        assert_eq!(statement.principal, Some(Principal::Any));
+
+        let statement = Statement::allow()
+            .principal("AWS", "arn:aws:iam::123456789012:root")
+            .principal("AWS", "arn:aws:iam::210987654321:root");
+        assert_eq!(
+            serde_json::to_string(&statement.principal).unwrap(),
+            r#"{"AWS":["arn:aws:iam::123456789012:root","arn:aws:iam::210987654321:root"]}"#
+        );
+    }
 }
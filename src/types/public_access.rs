@@ -1,10 +1,77 @@
-use serde::Deserialize;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// The public access block of a bucket. Each setting is off unless set.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "PublicAccessBlockConfiguration")]
 #[serde(rename_all = "PascalCase")]
-pub struct PublicAccessBookConfiguration {
-    block_public_acls: bool,
-    block_public_policy: bool,
-    ignore_public_acls: bool,
-    restrict_public_buckets: bool,
+pub struct PublicAccessBlockConfiguration {
+    /// Reject requests setting public acls on the bucket or its objects.
+    #[serde(default)]
+    pub block_public_acls: bool,
+
+    /// Ignore public acls on the bucket and its objects.
+    #[serde(default)]
+    pub ignore_public_acls: bool,
+
+    /// Reject bucket policies granting public access.
+    #[serde(default)]
+    pub block_public_policy: bool,
+
+    /// Only allow AWS services and users of the owning account access to
+    /// the bucket if its policy is public.
+    #[serde(default)]
+    pub restrict_public_buckets: bool,
+}
+
+impl PublicAccessBlockConfiguration {
+    /// A configuration with every setting turned on.
+    pub fn block_all() -> Self {
+        PublicAccessBlockConfiguration {
+            block_public_acls: true,
+            ignore_public_acls: true,
+            block_public_policy: true,
+            restrict_public_buckets: true,
+        }
+    }
+
+    /// Whether every setting is turned on.
+    pub fn blocks_all(&self) -> bool {
+        *self == PublicAccessBlockConfiguration::block_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = PublicAccessBlockConfiguration::block_all();
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<PublicAccessBlockConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<BlockPublicAcls>true</BlockPublicAcls><IgnorePublicAcls>true</IgnorePublicAcls>",
+                "<BlockPublicPolicy>true</BlockPublicPolicy>",
+                "<RestrictPublicBuckets>true</RestrictPublicBuckets>",
+                "</PublicAccessBlockConfiguration>"
+            )
+        );
+
+        let parsed: PublicAccessBlockConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert!(parsed.blocks_all());
+
+        let partial: PublicAccessBlockConfiguration = quick_xml::de::from_str(
+            "<PublicAccessBlockConfiguration><BlockPublicAcls>true</BlockPublicAcls></PublicAccessBlockConfiguration>",
+        )
+        .unwrap();
+        assert!(partial.block_public_acls);
+        assert!(!partial.blocks_all());
+    }
 }
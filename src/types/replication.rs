@@ -1,108 +1,564 @@
-use super::Tag;
-use serde::Deserialize;
+use crate::{
+    types::{
+        FilterElement,
+        TagSet,
+    },
+    Error,
+    StorageClass,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use std::collections::HashSet;
 
-#[derive(Debug, Deserialize)]
+// Limits enforced by S3 on a replication configuration
+const MAX_RULES: usize = 1000;
+
+// Replication time control only supports a 15 minute threshold
+const REPLICATION_TIME_MINUTES: u32 = 15;
+
+/// The replication configuration of a bucket. Objects matching a rule are
+/// copied to the rule's destination bucket using the IAM role.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "ReplicationConfiguration")]
 #[serde(rename_all = "PascalCase")]
 pub struct ReplicationConfiguration {
-    role: String,
-    rule: Vec<ReplicationRule>,
+    /// The arn of the IAM role S3 assumes to replicate objects.
+    pub role: String,
+
+    #[serde(rename = "Rule", default)]
+    pub rules: Vec<ReplicationRule>,
 }
 
-#[derive(Debug, Deserialize)]
+impl ReplicationConfiguration {
+    /// Create a new configuration without rules replicating as `role`
+    pub fn new<T: Into<String>>(role: T) -> Self {
+        ReplicationConfiguration {
+            role: role.into(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Add a rule to the configuration. Rules without a priority are given
+    /// a lower priority than the rules added before them, which are raised
+    /// when there is no lower priority left.
+    pub fn rule(mut self, mut rule: ReplicationRule) -> Self {
+        if rule.priority.is_none() {
+            let lowest = self.rules.iter().filter_map(|rule| rule.priority).min();
+            rule.priority = Some(match lowest {
+                Some(0) => {
+                    for rule in self.rules.iter_mut() {
+                        if let Some(ref mut priority) = rule.priority {
+                            *priority += 1;
+                        }
+                    }
+                    0
+                }
+                Some(lowest) => lowest - 1,
+                None => 0,
+            });
+        }
+
+        self.rules.push(rule);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketReplication` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.role.is_empty() {
+            return Err(Error::InvalidReplication(
+                "the configuration needs a role".to_owned(),
+            ));
+        }
+
+        if self.rules.is_empty() || self.rules.len() > MAX_RULES {
+            return Err(Error::InvalidReplication(format!(
+                "a configuration needs between 1 and {} rules, found {}",
+                MAX_RULES,
+                self.rules.len()
+            )));
+        }
+
+        let mut ids = HashSet::new();
+        let mut priorities = HashSet::new();
+
+        for (index, rule) in self.rules.iter().enumerate() {
+            let name = rule.id.clone().unwrap_or_else(|| index.to_string());
+
+            if let Some(ref id) = rule.id {
+                if !ids.insert(id) {
+                    return Err(Error::InvalidReplication(format!(
+                        "rule id {:?} is used more than once",
+                        id
+                    )));
+                }
+            }
+
+            if let Some(priority) = rule.priority {
+                if !priorities.insert(priority) {
+                    return Err(Error::InvalidReplication(format!(
+                        "priority {} of rule {} is used more than once",
+                        priority, name
+                    )));
+                }
+            }
+
+            rule.validate(&name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Which objects to replicate and where to.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReplicationRule {
-    delete_marker_replication: Option<DeleteMarkerReplication>,
-    destination: ReplicationDestination,
-    existing_object_replication: Option<ExistingObjectReplication>,
-    filter: Option<ReplicationFilter>,
-    id: Option<String>,
-    prefix: Option<String>,
-    priority: Option<i64>,
-    source_selection_criteria: Option<SourceSelectionCriteria>,
-    status: String,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct ReplicationDestination {
-    access_control_translation: Option<AccessControlTranslation>,
-    account: Option<String>,
-    bucket: String,
-    encryption_configuration: Option<EncryptionConfiguration>,
-    metrics: Option<ReplicationMetrics>,
-    replication_time: Option<ReplicationTime>,
-    storage_class: Option<String>,
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+
+    /// Decides which rule applies when several rules replicate an object to
+    /// the same bucket, the highest priority wins.
+    pub priority: Option<u32>,
+
+    /// Only set by configurations written before filters existed.
+    pub prefix: Option<String>,
+
+    pub filter: Option<ReplicationFilter>,
+    pub status: ReplicationRuleStatus,
+    pub source_selection_criteria: Option<SourceSelectionCriteria>,
+    pub existing_object_replication: Option<ExistingObjectReplication>,
+    pub destination: ReplicationDestination,
+    pub delete_marker_replication: Option<DeleteMarkerReplication>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct ExistingObjectReplication {
-    status: String,
+impl ReplicationRule {
+    /// Create a new enabled rule replicating every object to `destination`,
+    /// without replicating delete markers.
+    pub fn new<T: Into<String>>(id: T, destination: ReplicationDestination) -> Self {
+        ReplicationRule {
+            id: Some(id.into()),
+            priority: None,
+            prefix: None,
+            filter: Some(ReplicationFilter::default()),
+            status: ReplicationRuleStatus::Enabled,
+            source_selection_criteria: None,
+            existing_object_replication: None,
+            destination,
+            delete_marker_replication: Some(DeleteMarkerReplication {
+                status: ReplicationRuleStatus::Disabled,
+            }),
+        }
+    }
+
+    pub fn disabled(mut self) -> Self {
+        self.status = ReplicationRuleStatus::Disabled;
+        self
+    }
+
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Only replicate objects matching the filter.
+    pub fn filter(mut self, filter: ReplicationFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Replicate delete markers created without a version id. Not
+    /// supported by rules filtering on tags.
+    pub fn replicate_delete_markers(mut self) -> Self {
+        self.delete_marker_replication = Some(DeleteMarkerReplication {
+            status: ReplicationRuleStatus::Enabled,
+        });
+        self
+    }
+
+    /// Also replicate objects that existed before the rule was added.
+    pub fn replicate_existing_objects(mut self) -> Self {
+        self.existing_object_replication = Some(ExistingObjectReplication {
+            status: ReplicationRuleStatus::Enabled,
+        });
+        self
+    }
+
+    /// Also replicate objects encrypted with SSE-KMS, which requires the
+    /// destination to set `replica_kms_key_id`.
+    pub fn replicate_sse_kms_encrypted_objects(mut self) -> Self {
+        self.source_selection_criteria = Some(SourceSelectionCriteria {
+            sse_kms_encrypted_objects: Some(SseKmsEncryptedObjects {
+                status: ReplicationRuleStatus::Enabled,
+            }),
+        });
+        self
+    }
+
+    fn validate(&self, name: &str) -> Result<(), Error> {
+        let destination = &self.destination;
+
+        if !destination.bucket.starts_with("arn:") {
+            return Err(Error::InvalidReplication(format!(
+                "destination of rule {} must be a bucket arn, found {:?}",
+                name, destination.bucket
+            )));
+        }
+
+        if self.filter.is_some() && self.prefix.is_some() {
+            return Err(Error::InvalidReplication(format!(
+                "rule {} can't have both a filter and a prefix",
+                name
+            )));
+        }
+
+        let replicates_delete_markers = self
+            .delete_marker_replication
+            .iter()
+            .any(|replication| replication.status == ReplicationRuleStatus::Enabled);
+        let filters_on_tags = self.filter.iter().any(|filter| !filter.tags.is_empty());
+        if replicates_delete_markers && filters_on_tags {
+            return Err(Error::InvalidReplication(format!(
+                "rule {} can't replicate delete markers while filtering on tags",
+                name
+            )));
+        }
+
+        let sse_kms = self
+            .source_selection_criteria
+            .as_ref()
+            .and_then(|criteria| criteria.sse_kms_encrypted_objects.as_ref())
+            .iter()
+            .any(|objects| objects.status == ReplicationRuleStatus::Enabled);
+        if sse_kms && destination.encryption_configuration.is_none() {
+            return Err(Error::InvalidReplication(format!(
+                "rule {} replicates SSE-KMS encrypted objects without a replica kms key",
+                name
+            )));
+        }
+
+        if destination.access_control_translation.is_some() && destination.account.is_none() {
+            return Err(Error::InvalidReplication(format!(
+                "rule {} can only change the replica owner when the destination account is set",
+                name
+            )));
+        }
+
+        let replication_time = destination
+            .replication_time
+            .iter()
+            .any(|time| time.status == ReplicationRuleStatus::Enabled);
+        let metrics = destination
+            .metrics
+            .iter()
+            .any(|metrics| metrics.status == ReplicationRuleStatus::Enabled);
+        if replication_time && !metrics {
+            return Err(Error::InvalidReplication(format!(
+                "rule {} enables replication time control without metrics",
+                name
+            )));
+        }
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
+/// Whether a rule, or a feature of a rule, is enabled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReplicationRuleStatus {
+    Enabled,
+    Disabled,
+}
+
+impl_text_enum!(ReplicationRuleStatus, "replication rule status", { Enabled, Disabled });
+
+/// The objects a rule applies to. Every condition must match, an empty
+/// filter matches every object of the bucket.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(from = "FilterElement", into = "FilterElement")]
 pub struct ReplicationFilter {
-    and: Option<ReplicationRuleAndOperator>,
-    prefix: Option<String>,
-    tag: Option<Tag>,
+    pub prefix: Option<String>,
+    pub tags: TagSet,
+}
+
+impl ReplicationFilter {
+    /// Create a new filter which matches every object
+    pub fn new() -> Self {
+        ReplicationFilter::default()
+    }
+
+    pub fn prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only match objects with this tag.
+    pub fn tag<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Result<Self, Error> {
+        self.tags.insert(key, value)?;
+        Ok(self)
+    }
+}
+
+impl From<FilterElement> for ReplicationFilter {
+    fn from(element: FilterElement) -> Self {
+        let (prefix, tags, _) = element.into_parts();
+        ReplicationFilter { prefix, tags }
+    }
+}
+
+impl From<ReplicationFilter> for FilterElement {
+    fn from(filter: ReplicationFilter) -> Self {
+        FilterElement::new(filter.prefix, filter.tags, None)
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// The bucket objects are replicated to.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct ReplicationRuleAndOperator {
-    prefix: Option<String>,
+pub struct ReplicationDestination {
+    /// The arn of the destination bucket.
+    pub bucket: String,
+    pub account: Option<String>,
+    pub storage_class: Option<StorageClass>,
+    pub access_control_translation: Option<AccessControlTranslation>,
+    pub encryption_configuration: Option<EncryptionConfiguration>,
+    pub replication_time: Option<ReplicationTime>,
+    pub metrics: Option<ReplicationMetrics>,
+}
+
+impl ReplicationDestination {
+    /// Create a new destination for the bucket with the given arn, e.g.
+    /// `arn:aws:s3:::destination`.
+    pub fn new<T: Into<String>>(bucket_arn: T) -> Self {
+        ReplicationDestination {
+            bucket: bucket_arn.into(),
+            account: None,
+            storage_class: None,
+            access_control_translation: None,
+            encryption_configuration: None,
+            replication_time: None,
+            metrics: None,
+        }
+    }
+
+    /// The account owning the destination bucket.
+    pub fn account<T: Into<String>>(mut self, account: T) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// The storage class of the replicas, the storage class of the source
+    /// object by default.
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = Some(storage_class);
+        self
+    }
+
+    /// Make the destination account the owner of the replicas.
+    pub fn owner_override(mut self) -> Self {
+        self.access_control_translation = Some(AccessControlTranslation {
+            owner: "Destination".to_owned(),
+        });
+        self
+    }
+
+    /// The KMS key used to encrypt replicas of SSE-KMS encrypted objects.
+    pub fn replica_kms_key_id<T: Into<String>>(mut self, key_id: T) -> Self {
+        self.encryption_configuration = Some(EncryptionConfiguration {
+            replica_kms_key_id: Some(key_id.into()),
+        });
+        self
+    }
+
+    /// Publish replication metrics to CloudWatch.
+    pub fn metrics(mut self) -> Self {
+        self.metrics = Some(ReplicationMetrics {
+            status: ReplicationRuleStatus::Enabled,
+            event_threshold: None,
+        });
+        self
+    }
+
+    /// Replicate objects within 15 minutes, which also enables metrics.
+    pub fn replication_time_control(mut self) -> Self {
+        let minutes = ReplicationTimeValue {
+            minutes: REPLICATION_TIME_MINUTES,
+        };
 
-    #[serde(rename = "Tag")]
-    tags: Vec<Tag>,
+        self.replication_time = Some(ReplicationTime {
+            status: ReplicationRuleStatus::Enabled,
+            time: minutes.clone(),
+        });
+        self.metrics = Some(ReplicationMetrics {
+            status: ReplicationRuleStatus::Enabled,
+            event_threshold: Some(minutes),
+        });
+        self
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ExistingObjectReplication {
+    pub status: ReplicationRuleStatus,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct AccessControlTranslation {
-    owner: String,
+    pub owner: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct EncryptionConfiguration {
     #[serde(rename = "ReplicaKmsKeyID")]
-    replica_kms_key_id: Option<String>,
+    pub replica_kms_key_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReplicationMetrics {
-    event_threshold: ReplicationTimeValue,
-    status: String,
+    pub status: ReplicationRuleStatus,
+    pub event_threshold: Option<ReplicationTimeValue>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReplicationTimeValue {
-    minutes: Option<i64>,
+    pub minutes: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ReplicationTime {
-    status: String,
-    time: ReplicationTimeValue,
+    pub status: ReplicationRuleStatus,
+    pub time: ReplicationTimeValue,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct DeleteMarkerReplication {
-    status: Option<String>,
+    pub status: ReplicationRuleStatus,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct SourceSelectionCriteria {
-    sse_kms_encrypted_objects: Option<SseKmsEncryptedObjects>,
+    pub sse_kms_encrypted_objects: Option<SseKmsEncryptedObjects>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct SseKmsEncryptedObjects {
-    status: String,
+    pub status: ReplicationRuleStatus,
+}
+
+/// The replication state of an object, sent as `x-amz-replication-status`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReplicationStatus {
+    /// The object is waiting to be replicated.
+    Pending,
+
+    /// The object was replicated to every destination.
+    Completed,
+
+    /// Replicating the object failed.
+    Failed,
+
+    /// The object is itself a replica.
+    Replica,
+}
+
+// Objects replicated to a single destination report `COMPLETE`
+impl_text_enum!(ReplicationStatus, "replication status", {
+    Pending = "PENDING",
+    Completed = "COMPLETED" | "COMPLETE",
+    Failed = "FAILED",
+    Replica = "REPLICA",
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let config = ReplicationConfiguration::new("arn:aws:iam::123456789012:role/replication")
+            .rule(
+                ReplicationRule::new(
+                    "logs",
+                    ReplicationDestination::new("arn:aws:s3:::backup")
+                        .storage_class(StorageClass::StandardIa)
+                        .replica_kms_key_id("key")
+                        .replication_time_control(),
+                )
+                .filter(ReplicationFilter::new().prefix("logs/"))
+                .replicate_delete_markers()
+                .replicate_sse_kms_encrypted_objects(),
+            )
+            .rule(ReplicationRule::new(
+                "all",
+                ReplicationDestination::new("arn:aws:s3:::archive"),
+            ));
+
+        // The catch-all rule added last has the lowest priority
+        assert_eq!(config.rules[0].priority, Some(1));
+        assert_eq!(config.rules[1].priority, Some(0));
+
+        let explicit = ReplicationConfiguration::new("role")
+            .rule(ReplicationRule::new("a", ReplicationDestination::new("bucket")).priority(5))
+            .rule(ReplicationRule::new(
+                "b",
+                ReplicationDestination::new("bucket"),
+            ))
+            .rule(ReplicationRule::new(
+                "c",
+                ReplicationDestination::new("bucket"),
+            ));
+        let priorities = explicit
+            .rules
+            .iter()
+            .map(|rule| rule.priority)
+            .collect::<Vec<_>>();
+        assert_eq!(priorities, vec![Some(5), Some(4), Some(3)]);
+
+        let string = xml::to_string(&config).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<ReplicationConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Role>arn:aws:iam::123456789012:role/replication</Role>",
+                "<Rule><ID>logs</ID><Priority>1</Priority><Filter><Prefix>logs/</Prefix></Filter>",
+                "<Status>Enabled</Status><SourceSelectionCriteria><SseKmsEncryptedObjects>",
+                "<Status>Enabled</Status></SseKmsEncryptedObjects></SourceSelectionCriteria>",
+                "<Destination><Bucket>arn:aws:s3:::backup</Bucket><StorageClass>STANDARD_IA</StorageClass>",
+                "<EncryptionConfiguration><ReplicaKmsKeyID>key</ReplicaKmsKeyID></EncryptionConfiguration>",
+                "<ReplicationTime><Status>Enabled</Status><Time><Minutes>15</Minutes></Time></ReplicationTime>",
+                "<Metrics><Status>Enabled</Status><EventThreshold><Minutes>15</Minutes></EventThreshold></Metrics>",
+                "</Destination><DeleteMarkerReplication><Status>Enabled</Status></DeleteMarkerReplication></Rule>",
+                "<Rule><ID>all</ID><Priority>0</Priority><Filter></Filter><Status>Enabled</Status>",
+                "<Destination><Bucket>arn:aws:s3:::archive</Bucket></Destination>",
+                "<DeleteMarkerReplication><Status>Disabled</Status></DeleteMarkerReplication></Rule>",
+                "</ReplicationConfiguration>"
+            )
+        );
+
+        let parsed: ReplicationConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, config);
+        assert!(config.validate().is_ok());
+
+        let tagged = ReplicationConfiguration::new("role").rule(
+            ReplicationRule::new("tagged", ReplicationDestination::new("arn:aws:s3:::backup"))
+                .filter(ReplicationFilter::new().tag("replicate", "yes").unwrap())
+                .replicate_delete_markers(),
+        );
+        assert!(tagged.validate().is_err());
+    }
 }
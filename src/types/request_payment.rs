@@ -1,7 +1,59 @@
-use serde::Deserialize;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// Who pays for requests to and downloads from a bucket.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "RequestPaymentConfiguration")]
 #[serde(rename_all = "PascalCase")]
-pub struct RequestPayment {
-    payer: String,
+pub struct RequestPaymentConfiguration {
+    pub payer: Payer,
+}
+
+impl RequestPaymentConfiguration {
+    /// The bucket owner pays, which is the default for every bucket.
+    pub fn bucket_owner() -> Self {
+        RequestPaymentConfiguration {
+            payer: Payer::BucketOwner,
+        }
+    }
+
+    /// The requester pays. Requests must then acknowledge the charge with
+    /// `RequesterPays` or `Client::set_requester_pays()`, except for those
+    /// made by the bucket owner.
+    pub fn requester() -> Self {
+        RequestPaymentConfiguration {
+            payer: Payer::Requester,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Payer {
+    Requester,
+    BucketOwner,
+}
+
+impl_text_enum!(Payer, "payer", { Requester, BucketOwner });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let string = xml::to_string(&RequestPaymentConfiguration::requester()).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<RequestPaymentConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Payer>Requester</Payer></RequestPaymentConfiguration>"
+            )
+        );
+
+        let parsed: RequestPaymentConfiguration = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed, RequestPaymentConfiguration::requester());
+    }
 }
@@ -1,8 +1,29 @@
-use serde::Deserialize;
+use crate::ObjectLockMode;
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
+/// The retention settings of an object version.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "Retention")]
 #[serde(rename_all = "PascalCase")]
 pub struct Retention {
-    mode: String,
-    retain_until_date: String,
+    pub mode: ObjectLockMode,
+
+    /// The date until which the object version is protected.
+    pub retain_until_date: DateTime<Utc>,
+}
+
+impl Retention {
+    pub fn new(mode: ObjectLockMode, retain_until_date: DateTime<Utc>) -> Self {
+        Retention {
+            mode,
+            retain_until_date,
+        }
+    }
 }
@@ -1,29 +1,340 @@
-use serde::Deserialize;
+use crate::{
+    Error,
+    UriEncode,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct Tag {
-    key: String,
-    value: String,
-}
+// Tag keys may be up to 128 and values up to 256 unicode characters long
+const MAX_KEY_LENGTH: usize = 128;
+const MAX_VALUE_LENGTH: usize = 256;
+
+/// The maximum number of tags an object can have.
+pub(crate) const MAX_OBJECT_TAGS: usize = 10;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketTaggingOutput")]
+/// The maximum number of tags a bucket can have.
+pub(crate) const MAX_BUCKET_TAGS: usize = 50;
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
-pub struct BucketTagging {
-    tag_set: TagSet,
+pub struct Tag {
+    pub key: String,
+    pub value: String,
 }
 
-#[derive(Debug, Deserialize)]
+/// A set of tags with unique keys.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct TagSet {
-    #[serde(rename = "Tag")]
+    #[serde(rename = "Tag", default)]
     pub tags: Vec<Tag>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetObjectTagging")]
+impl TagSet {
+    /// Create a new empty tag set
+    pub fn new() -> Self {
+        TagSet::default()
+    }
+
+    /// Insert a tag, returning the previous value for `key` if any. Keys and
+    /// values may only contain letters, numbers, spaces and `+ - = . _ : / @`,
+    /// keys may be at most 128 and values 256 characters long, and keys may
+    /// not start with `aws:`.
+    pub fn insert<K: Into<String>, V: Into<String>>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<Option<String>, Error> {
+        let key = key.into();
+        let value = value.into();
+
+        if key.is_empty() || key.chars().count() > MAX_KEY_LENGTH {
+            return Err(Error::InvalidTagging(format!(
+                "tag key {:?} must be between 1 and {} characters long",
+                key, MAX_KEY_LENGTH
+            )));
+        }
+
+        // The `aws:` prefix is reserved for tags created by AWS
+        if key.starts_with("aws:") {
+            return Err(Error::InvalidTagging(format!(
+                "tag key {:?} uses the reserved `aws:` prefix",
+                key
+            )));
+        }
+
+        if value.chars().count() > MAX_VALUE_LENGTH {
+            return Err(Error::InvalidTagging(format!(
+                "value of tag {:?} may not be longer than {} characters",
+                key, MAX_VALUE_LENGTH
+            )));
+        }
+
+        if !key.chars().chain(value.chars()).all(is_allowed) {
+            return Err(Error::InvalidTagging(format!(
+                "tag {:?} contains characters that are not allowed",
+                key
+            )));
+        }
+
+        if let Some(tag) = self.tags.iter_mut().find(|tag| tag.key == key) {
+            Ok(Some(std::mem::replace(&mut tag.value, value)))
+        } else {
+            self.tags.push(Tag { key, value });
+            Ok(None)
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|tag| tag.key == key)
+            .map(|tag| tag.value.as_str())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let index = self.tags.iter().position(|tag| tag.key == key)?;
+        Some(self.tags.remove(index).value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Tag> {
+        self.tags.iter()
+    }
+
+    /// Fails if the set has more tags than the given limit.
+    pub(crate) fn check_limit(&self, limit: usize) -> Result<(), Error> {
+        if self.tags.len() > limit {
+            Err(Error::InvalidTagging(format!(
+                "at most {} tags are allowed, found {}",
+                limit,
+                self.tags.len()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The value of the `x-amz-tagging` header, which is the tag set
+    /// encoded as url query parameters.
+    pub(crate) fn header_value(&self) -> String {
+        self.tags
+            .iter()
+            .map(|tag| format!("{}={}", tag.key.uri_encode(), tag.value.uri_encode()))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+}
+
+fn is_allowed(c: char) -> bool {
+    c.is_alphanumeric() || c == ' ' || "+-=._:/@".contains(c)
+}
+
+/// Changes to make to an existing tag-set, applied with a read-modify-write
+/// so that tags set by others are kept.
+#[derive(Debug, Clone, Default)]
+pub struct TagSetChanges {
+    insert: TagSet,
+    remove: Vec<String>,
+}
+
+impl TagSetChanges {
+    pub fn new() -> Self {
+        TagSetChanges::default()
+    }
+
+    /// Add a tag. This conflicts if the tag already exists with a different value.
+    pub fn insert<K: Into<String>, V: Into<String>>(
+        mut self,
+        key: K,
+        value: V,
+    ) -> Result<Self, Error> {
+        self.insert.insert(key, value)?;
+        Ok(self)
+    }
+
+    /// Remove a tag. This conflicts if the tag doesn't exist.
+    pub fn remove<K: Into<String>>(mut self, key: K) -> Self {
+        self.remove.push(key.into());
+        self
+    }
+
+    /// Apply the changes to `current`, reporting every change that conflicts
+    /// with it. Conflicting changes are still applied to the merged tag-set.
+    pub fn merge(&self, current: &TagSet) -> TagSetMerge {
+        let mut tag_set = current.clone();
+        let mut conflicts = Vec::new();
+
+        for key in self.remove.iter() {
+            if tag_set.remove(key).is_none() {
+                conflicts.push(TagConflict::Missing { key: key.clone() });
+            }
+        }
+
+        for tag in self.insert.iter() {
+            match current.get(&tag.key) {
+                Some(value) if value != tag.value => conflicts.push(TagConflict::Changed {
+                    key: tag.key.clone(),
+                    current: value.to_owned(),
+                    requested: tag.value.clone(),
+                }),
+                _ => {}
+            }
+
+            if let Some(existing) = tag_set.tags.iter_mut().find(|t| t.key == tag.key) {
+                existing.value = tag.value.clone();
+            } else {
+                tag_set.tags.push(tag.clone());
+            }
+        }
+
+        let changed = tag_set != *current;
+
+        TagSetMerge {
+            tag_set,
+            conflicts,
+            changed,
+        }
+    }
+}
+
+/// A change that didn't match the tag-set it was applied to.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TagConflict {
+    /// The tag to add already exists with a different value.
+    Changed {
+        key: String,
+        current: String,
+        requested: String,
+    },
+
+    /// The tag to remove doesn't exist.
+    Missing { key: String },
+}
+
+/// The result of merging `TagSetChanges` into a tag-set.
+#[derive(Debug, Clone)]
+pub struct TagSetMerge {
+    /// The tag-set with every change applied.
+    pub tag_set: TagSet,
+
+    /// The changes that conflicted with the existing tag-set.
+    pub conflicts: Vec<TagConflict>,
+
+    /// Whether the merged tag-set differs from the existing one.
+    pub changed: bool,
+}
+
+/// The request and response body of the tagging sub-resource.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename = "Tagging")]
 #[serde(rename_all = "PascalCase")]
-pub struct ObjectTagging {
-    tag_set: TagSet,
+pub struct Tagging {
+    pub tag_set: TagSet,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn tags_are_validated() {
+        let mut tags = TagSet::new();
+
+        assert!(tags.insert("", "value").is_err());
+        assert!(tags.insert("k".repeat(129), "value").is_err());
+        assert!(tags.insert("key", "v".repeat(257)).is_err());
+        assert!(tags.insert("key", "semi;colon").is_err());
+        assert!(tags.insert("aws:reserved", "value").is_err());
+        assert!(tags.is_empty());
+
+        for index in 0..=MAX_OBJECT_TAGS {
+            tags.insert(format!("key{}", index), "value").unwrap();
+        }
+        assert!(tags.check_limit(MAX_OBJECT_TAGS).is_err());
+    }
+
+    #[test]
+    fn header_value_is_encoded() {
+        let mut tags = TagSet::new();
+        tags.insert("project", "s3 client").unwrap();
+        tags.insert("path", "a/b=c").unwrap();
+
+        assert_eq!(tags.header_value(), "project=s3%20client&path=a%2Fb%3Dc");
+    }
+
+    #[test]
+    fn tagging_round_trip() {
+        let mut tagging = Tagging::default();
+        tagging.tag_set.insert("key", "value").unwrap();
+
+        let string = xml::to_string(&tagging).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<TagSet><Tag><Key>key</Key><Value>value</Value></Tag></TagSet>",
+                "</Tagging>"
+            )
+        );
+
+        let parsed: Tagging = quick_xml::de::from_str(&string).unwrap();
+        assert_eq!(parsed.tag_set, tagging.tag_set);
+
+        let empty: Tagging =
+            quick_xml::de::from_str("<Tagging><TagSet></TagSet></Tagging>").unwrap();
+        assert!(empty.tag_set.is_empty());
+    }
+
+    #[test]
+    fn merge_reports_conflicts() {
+        let mut current = TagSet::new();
+        current.insert("team", "storage").unwrap();
+        current.insert("cost-center", "42").unwrap();
+
+        let merge = TagSetChanges::new()
+            .insert("team", "storage")
+            .unwrap()
+            .insert("cost-center", "7")
+            .unwrap()
+            .insert("env", "prod")
+            .unwrap()
+            .remove("owner")
+            .merge(&current);
+
+        assert!(merge.changed);
+        assert_eq!(merge.tag_set.get("team"), Some("storage"));
+        assert_eq!(merge.tag_set.get("cost-center"), Some("7"));
+        assert_eq!(merge.tag_set.get("env"), Some("prod"));
+        assert_eq!(
+            merge.conflicts,
+            vec![
+                TagConflict::Missing {
+                    key: "owner".to_owned()
+                },
+                TagConflict::Changed {
+                    key: "cost-center".to_owned(),
+                    current: "42".to_owned(),
+                    requested: "7".to_owned(),
+                },
+            ]
+        );
+
+        let merge = TagSetChanges::new()
+            .insert("team", "storage")
+            .unwrap()
+            .merge(&current);
+        assert!(!merge.changed);
+        assert!(merge.conflicts.is_empty());
+    }
 }
@@ -2,55 +2,261 @@ use super::{
     CommonPrefix,
     Owner,
 };
-use serde::Deserialize;
+use crate::StorageClass;
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketVersioningOutput")]
+/// The versioning state of a bucket. Both fields are `None` for a bucket
+/// which never had versioning enabled, and versioning can only be suspended
+/// once it was enabled.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "VersioningConfiguration")]
 #[serde(rename_all = "PascalCase")]
 pub struct BucketVersioning {
-    #[serde(rename = "MFADelete")]
-    mfa_delete: String,
-    status: String,
+    pub status: Option<BucketVersioningStatus>,
+
+    /// Whether deleting object versions and changing the versioning state
+    /// requires an `Mfa`. Changing it requires an `Mfa` as well, and is
+    /// only possible with the credentials of the root account.
+    pub mfa_delete: Option<MfaDelete>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "ListObjectVersionsOutput")]
-#[serde(rename_all = "PascalCase")]
-pub struct ObjectVersions {
-    common_prefixes: Vec<CommonPrefix>,
-    delete_marker: Vec<DeleteMarkerEntry>,
-    delimiter: String,
-    encoding_type: String,
-    is_truncated: bool,
-    key_marker: String,
-    max_keys: i32,
-    name: String,
-    next_key_marker: String,
-    next_version_id_marker: String,
-    prefix: String,
-    version: Vec<ObjectVersion>,
-    version_id_marker: String,
+impl BucketVersioning {
+    pub fn enabled() -> Self {
+        BucketVersioning {
+            status: Some(BucketVersioningStatus::Enabled),
+            mfa_delete: None,
+        }
+    }
+
+    pub fn suspended() -> Self {
+        BucketVersioning {
+            status: Some(BucketVersioningStatus::Suspended),
+            mfa_delete: None,
+        }
+    }
+
+    /// Enable or disable MFA delete along with the status.
+    pub fn mfa_delete(mut self, enabled: bool) -> Self {
+        self.mfa_delete = Some(if enabled {
+            MfaDelete::Enabled
+        } else {
+            MfaDelete::Disabled
+        });
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.status == Some(BucketVersioningStatus::Enabled)
+    }
+
+    pub fn is_mfa_delete_enabled(&self) -> bool {
+        self.mfa_delete == Some(MfaDelete::Enabled)
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "PascalCase")]
-pub struct DeleteMarkerEntry {
-    is_latest: Option<bool>,
-    key: Option<String>,
-    last_modified: Option<String>,
-    owner: Owner,
-    version_id: String,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BucketVersioningStatus {
+    Enabled,
+    Suspended,
 }
 
+impl_text_enum!(BucketVersioningStatus, "versioning status", { Enabled, Suspended });
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MfaDelete {
+    Enabled,
+    Disabled,
+}
+
+impl_text_enum!(MfaDelete, "mfa delete status", { Enabled, Disabled });
+
+/// A page of `ListObjectVersions`. Versions and delete markers are listed
+/// together in key order, the newest version of each key first.
+#[derive(Debug, Default, Deserialize)]
+#[serde(from = "ListVersionsResultElement")]
+pub struct ListVersionsResult {
+    pub name: String,
+    pub prefix: Option<String>,
+    pub delimiter: Option<String>,
+    pub key_marker: Option<String>,
+    pub version_id_marker: Option<String>,
+    pub max_keys: u32,
+    pub is_truncated: bool,
+
+    /// The markers to request the next page with, if the list is truncated.
+    pub next_key_marker: Option<String>,
+    pub next_version_id_marker: Option<String>,
+
+    pub versions: Vec<ObjectVersion>,
+    pub common_prefixes: Vec<CommonPrefix>,
+}
+
+/// A version of an object, or a delete marker.
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ObjectVersion {
-    etag: Option<String>,
-    is_latest: Option<String>,
-    key: Option<String>,
-    last_modified: Option<String>,
-    owner: Option<Owner>,
-    size: Option<u32>,
-    storage_class: Option<String>,
-    version_id: Option<String>,
+    pub key: String,
+
+    /// The version id, `null` for objects stored before versioning was
+    /// enabled.
+    pub version_id: String,
+
+    /// Whether this is the current version of the object.
+    pub is_latest: bool,
+    pub last_modified: DateTime<Utc>,
+
+    /// Whether this version is a delete marker, which has no content.
+    #[serde(skip)]
+    pub delete_marker: bool,
+
+    #[serde(rename = "ETag")]
+    pub etag: Option<String>,
+
+    #[serde(default)]
+    pub size: u64,
+
+    #[serde(default = "super::objects::standard")]
+    pub storage_class: StorageClass,
+
+    pub owner: Option<Owner>,
+}
+
+// `Version` and `DeleteMarker` elements are interleaved to keep the versions
+// of a key in order, which can't be read into separate fields directly.
+#[derive(Deserialize)]
+struct ListVersionsResultElement {
+    #[serde(rename = "$value", default)]
+    entries: Vec<ListVersionsResultEntry>,
+}
+
+#[derive(Deserialize)]
+enum ListVersionsResultEntry {
+    Name(String),
+    Prefix(String),
+    Delimiter(String),
+    KeyMarker(String),
+    VersionIdMarker(String),
+    MaxKeys(u32),
+    IsTruncated(bool),
+    NextKeyMarker(String),
+    NextVersionIdMarker(String),
+    Version(ObjectVersion),
+    DeleteMarker(ObjectVersion),
+    CommonPrefixes(CommonPrefix),
+    #[serde(other)]
+    Other,
+}
+
+impl From<ListVersionsResultElement> for ListVersionsResult {
+    fn from(element: ListVersionsResultElement) -> Self {
+        // Empty markers are sent as empty elements
+        let non_empty = |value: String| if value.is_empty() { None } else { Some(value) };
+        let mut result = ListVersionsResult::default();
+
+        for entry in element.entries {
+            match entry {
+                ListVersionsResultEntry::Name(name) => result.name = name,
+                ListVersionsResultEntry::Prefix(prefix) => result.prefix = non_empty(prefix),
+                ListVersionsResultEntry::Delimiter(delimiter) => {
+                    result.delimiter = non_empty(delimiter)
+                }
+                ListVersionsResultEntry::KeyMarker(marker) => result.key_marker = non_empty(marker),
+                ListVersionsResultEntry::VersionIdMarker(marker) => {
+                    result.version_id_marker = non_empty(marker)
+                }
+                ListVersionsResultEntry::MaxKeys(max_keys) => result.max_keys = max_keys,
+                ListVersionsResultEntry::IsTruncated(truncated) => result.is_truncated = truncated,
+                ListVersionsResultEntry::NextKeyMarker(marker) => {
+                    result.next_key_marker = non_empty(marker)
+                }
+                ListVersionsResultEntry::NextVersionIdMarker(marker) => {
+                    result.next_version_id_marker = non_empty(marker)
+                }
+                ListVersionsResultEntry::Version(version) => result.versions.push(version),
+                ListVersionsResultEntry::DeleteMarker(mut marker) => {
+                    marker.delete_marker = true;
+                    result.versions.push(marker);
+                }
+                ListVersionsResultEntry::CommonPrefixes(prefix) => {
+                    result.common_prefixes.push(prefix)
+                }
+                ListVersionsResultEntry::Other => {}
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn versioning_round_trip() {
+        let versioning = BucketVersioning::enabled().mfa_delete(true);
+
+        let string = xml::to_string(&versioning).unwrap();
+        assert_eq!(
+            string,
+            concat!(
+                r#"<VersioningConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Status>Enabled</Status><MfaDelete>Enabled</MfaDelete></VersioningConfiguration>"
+            )
+        );
+
+        let parsed: BucketVersioning = quick_xml::de::from_str(&string).unwrap();
+        assert!(parsed.is_enabled());
+        assert!(parsed.is_mfa_delete_enabled());
+
+        let never: BucketVersioning = quick_xml::de::from_str(
+            r#"<VersioningConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/"/>"#,
+        )
+        .unwrap();
+        assert_eq!(never, BucketVersioning::default());
+    }
+
+    #[test]
+    fn versions_and_delete_markers_keep_their_order() {
+        let result: ListVersionsResult = quick_xml::de::from_str(concat!(
+            r#"<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<Name>bucket</Name><Prefix></Prefix><KeyMarker></KeyMarker>",
+            "<VersionIdMarker></VersionIdMarker><MaxKeys>1000</MaxKeys>",
+            "<IsTruncated>false</IsTruncated>",
+            "<DeleteMarker><Key>a</Key><VersionId>3</VersionId><IsLatest>true</IsLatest>",
+            "<LastModified>2020-01-03T00:00:00.000Z</LastModified></DeleteMarker>",
+            "<Version><Key>a</Key><VersionId>2</VersionId><IsLatest>false</IsLatest>",
+            "<LastModified>2020-01-02T00:00:00.000Z</LastModified><ETag>\"x\"</ETag>",
+            "<Size>10</Size><StorageClass>GLACIER</StorageClass></Version>",
+            "<DeleteMarker><Key>a</Key><VersionId>1</VersionId><IsLatest>false</IsLatest>",
+            "<LastModified>2020-01-01T00:00:00.000Z</LastModified></DeleteMarker>",
+            "</ListVersionsResult>"
+        ))
+        .unwrap();
+
+        assert_eq!(result.name, "bucket");
+        assert_eq!(result.key_marker, None);
+        assert_eq!(result.max_keys, 1000);
+        assert!(!result.is_truncated);
+
+        let versions = result
+            .versions
+            .iter()
+            .map(|version| (version.version_id.as_str(), version.delete_marker))
+            .collect::<Vec<_>>();
+        assert_eq!(versions, vec![("3", true), ("2", false), ("1", true)]);
+
+        assert!(result.versions[0].is_latest);
+        assert_eq!(result.versions[0].storage_class, StorageClass::Standard);
+        assert_eq!(result.versions[1].size, 10);
+        assert_eq!(result.versions[1].storage_class, StorageClass::Glacier);
+    }
 }
@@ -1,55 +1,473 @@
-use serde::Deserialize;
+use crate::{
+    Error,
+    UriEncode,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
-#[derive(Debug, Deserialize)]
-#[serde(rename = "GetBucketWebsiteOutput")]
+// Limits enforced by S3 on a website configuration
+const MAX_ROUTING_RULES: usize = 50;
+
+// Status code of a redirect without an `HttpRedirectCode`
+const DEFAULT_REDIRECT_CODE: u16 = 301;
+
+/// The static website configuration of a bucket. Either redirects every
+/// request to another host, or serves an index document and optionally an
+/// error document and routing rules.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename = "WebsiteConfiguration")]
 #[serde(rename_all = "PascalCase")]
 pub struct BucketWebsite {
-    error_document: ErrorDocument,
-    index_document: IndexDocument,
-    redirect_all_requests_to: RedirectAllRequestsTo,
-    #[serde(rename = "RoutingRule")]
-    routing_rules: Vec<RoutingRule>,
+    pub error_document: Option<ErrorDocument>,
+    pub index_document: Option<IndexDocument>,
+    pub redirect_all_requests_to: Option<RedirectAllRequestsTo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "routing_rules")]
+    pub routing_rules: Vec<RoutingRule>,
+}
+
+impl BucketWebsite {
+    /// Create a new website configuration serving `suffix` for requests to
+    /// a directory, e.g. `index.html`.
+    pub fn new<T: Into<String>>(suffix: T) -> Self {
+        BucketWebsite {
+            index_document: Some(IndexDocument {
+                suffix: suffix.into(),
+            }),
+            ..BucketWebsite::default()
+        }
+    }
+
+    /// Create a new website configuration redirecting every request to
+    /// another host. The protocol of the original request is kept when no
+    /// protocol is given.
+    pub fn redirect_all<T: Into<String>>(host_name: T, protocol: Option<Protocol>) -> Self {
+        BucketWebsite {
+            redirect_all_requests_to: Some(RedirectAllRequestsTo {
+                host_name: host_name.into(),
+                protocol,
+            }),
+            ..BucketWebsite::default()
+        }
+    }
+
+    /// The object to return when an error occurs.
+    pub fn error_document<T: Into<String>>(mut self, key: T) -> Self {
+        self.error_document = Some(ErrorDocument { key: key.into() });
+        self
+    }
+
+    /// Add a routing rule. Rules are evaluated in the order they are added.
+    pub fn routing_rule(mut self, rule: RoutingRule) -> Self {
+        self.routing_rules.push(rule);
+        self
+    }
+
+    /// Check the configuration for mistakes S3 would reject. This is done
+    /// by `PutBucketWebsite` before the request is sent.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.redirect_all_requests_to.is_some() {
+            if self.index_document.is_some()
+                || self.error_document.is_some()
+                || !self.routing_rules.is_empty()
+            {
+                return Err(Error::InvalidWebsite(
+                    "redirecting all requests can't be combined with other settings".to_owned(),
+                ));
+            }
+
+            return Ok(());
+        }
+
+        match self.index_document {
+            Some(ref index) if index.suffix.is_empty() || index.suffix.contains('/') => {
+                return Err(Error::InvalidWebsite(format!(
+                    "index document suffix {:?} must be non-empty and may not contain a slash",
+                    index.suffix
+                )))
+            }
+            Some(_) => {}
+            None => {
+                return Err(Error::InvalidWebsite(
+                    "an index document is required unless all requests are redirected".to_owned(),
+                ))
+            }
+        }
+
+        if self.routing_rules.len() > MAX_ROUTING_RULES {
+            return Err(Error::InvalidWebsite(format!(
+                "at most {} routing rules are allowed, found {}",
+                MAX_ROUTING_RULES,
+                self.routing_rules.len()
+            )));
+        }
+
+        for (index, rule) in self.routing_rules.iter().enumerate() {
+            let redirect = &rule.redirect;
+
+            if redirect.replace_key_with.is_some() && redirect.replace_key_prefix_with.is_some() {
+                return Err(Error::InvalidWebsite(format!(
+                    "rule {} can't replace both the key and the key prefix",
+                    index
+                )));
+            }
+
+            if let Some(code) = redirect.http_redirect_code {
+                if !(300..400).contains(&code) {
+                    return Err(Error::InvalidWebsite(format!(
+                        "rule {} has redirect code {} which is not a 3xx code",
+                        index, code
+                    )));
+                }
+            }
+
+            if let Some(code) = rule
+                .condition
+                .as_ref()
+                .and_then(|condition| condition.http_error_code_returned_equals)
+            {
+                if !(400..600).contains(&code) {
+                    return Err(Error::InvalidWebsite(format!(
+                        "rule {} matches error code {} which is not a 4xx or 5xx code",
+                        index, code
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute the redirect S3 would respond with to a request for `key`
+    /// on the website endpoint `host`. `error_code` is the status code of
+    /// the response without any redirect, e.g. `Some(404)` for a missing
+    /// object, and `None` if it succeeds. `None` means no redirect applies.
+    pub fn route<'w>(
+        &'w self,
+        host: &str,
+        key: &str,
+        error_code: Option<u16>,
+    ) -> Option<WebsiteRedirect<'w>> {
+        // Website endpoints only serve http, so that is the protocol of
+        // every original request
+        if let Some(ref redirect) = self.redirect_all_requests_to {
+            return Some(WebsiteRedirect {
+                rule: None,
+                location: location(
+                    redirect.protocol.unwrap_or(Protocol::Http),
+                    &redirect.host_name,
+                    key,
+                ),
+                status: DEFAULT_REDIRECT_CODE,
+            });
+        }
+
+        let rule = self
+            .routing_rules
+            .iter()
+            .find(|rule| rule.matches(key, error_code))?;
+        let redirect = &rule.redirect;
+
+        let key = match (
+            &redirect.replace_key_with,
+            &redirect.replace_key_prefix_with,
+        ) {
+            (Some(replacement), _) => replacement.clone(),
+            (None, Some(replacement)) => {
+                let prefix = rule
+                    .condition
+                    .as_ref()
+                    .and_then(|condition| condition.key_prefix_equals.as_deref())
+                    .unwrap_or("");
+                format!("{}{}", replacement, &key[prefix.len()..])
+            }
+            (None, None) => key.to_owned(),
+        };
+
+        Some(WebsiteRedirect {
+            rule: Some(rule),
+            location: location(
+                redirect.protocol.unwrap_or(Protocol::Http),
+                redirect.host_name.as_deref().unwrap_or(host),
+                &key,
+            ),
+            status: redirect.http_redirect_code.unwrap_or(DEFAULT_REDIRECT_CODE),
+        })
+    }
+}
+
+fn location(protocol: Protocol, host: &str, key: &str) -> String {
+    format!(
+        "{}://{}/{}",
+        <&'static str>::from(protocol),
+        host,
+        key.uri_encode_path()
+    )
+}
+
+/// The outcome of `BucketWebsite::route()`.
+#[derive(Debug, Clone)]
+pub struct WebsiteRedirect<'w> {
+    /// The routing rule that applies, `None` when all requests are redirected.
+    pub rule: Option<&'w RoutingRule>,
+
+    /// The value of the `Location` header.
+    pub location: String,
+
+    /// The status code of the response.
+    pub status: u16,
+}
+
+// Routing rules are wrapped in a `RoutingRules` element
+mod routing_rules {
+    use super::RoutingRule;
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+
+    #[derive(Serialize)]
+    struct RoutingRulesRef<'a> {
+        #[serde(rename = "RoutingRule")]
+        rules: &'a [RoutingRule],
+    }
+
+    #[derive(Deserialize)]
+    struct RoutingRules {
+        #[serde(rename = "RoutingRule", default)]
+        rules: Vec<RoutingRule>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        rules: &[RoutingRule],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        RoutingRulesRef { rules }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<RoutingRule>, D::Error> {
+        RoutingRules::deserialize(deserializer).map(|rules| rules.rules)
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ErrorDocument {
-    key: String,
+    pub key: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct IndexDocument {
-    suffix: String,
+    pub suffix: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct RedirectAllRequestsTo {
-    host_name: String,
-    protocol: Option<String>,
+    pub host_name: String,
+    pub protocol: Option<Protocol>,
+}
+
+/// The protocol to use when redirecting requests.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Protocol {
+    Http,
+    Https,
 }
 
-#[derive(Debug, Deserialize)]
+impl_text_enum!(Protocol, "protocol", { Http = "http", Https = "https" });
+
+/// When a routing rule applies. Every condition that is set must match.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Condition {
-    http_error_code_returned_equals: Option<String>,
-    key_prefix_equals: Option<String>,
+    pub http_error_code_returned_equals: Option<u16>,
+    pub key_prefix_equals: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Where a routing rule redirects to. Anything not set is kept from the
+/// original request.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct Redirect {
-    host_name: Option<String>,
-    http_redirect_code: Option<String>,
-    protocol: Option<String>,
-    replace_key_prefix_with: Option<String>,
-    replace_key_with: Option<String>,
+    pub host_name: Option<String>,
+    pub http_redirect_code: Option<u16>,
+    pub protocol: Option<Protocol>,
+    pub replace_key_prefix_with: Option<String>,
+    pub replace_key_with: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+impl Redirect {
+    /// Create a new redirect which keeps the host, protocol and key
+    pub fn new() -> Self {
+        Redirect::default()
+    }
+
+    pub fn host_name<T: Into<String>>(mut self, host_name: T) -> Self {
+        self.host_name = Some(host_name.into());
+        self
+    }
+
+    /// The 3xx status code to respond with, 301 by default.
+    pub fn http_redirect_code(mut self, code: u16) -> Self {
+        self.http_redirect_code = Some(code);
+        self
+    }
+
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    /// Replace the prefix matched by the rule's condition, or prepend to
+    /// the key if the condition has no prefix.
+    pub fn replace_key_prefix_with<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.replace_key_prefix_with = Some(prefix.into());
+        self
+    }
+
+    /// Replace the whole key.
+    pub fn replace_key_with<T: Into<String>>(mut self, key: T) -> Self {
+        self.replace_key_with = Some(key.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct RoutingRule {
-    condition: Option<Condition>,
-    redirect: Redirect,
+    pub condition: Option<Condition>,
+    pub redirect: Redirect,
+}
+
+impl RoutingRule {
+    /// Create a new rule which applies the redirect to every request
+    pub fn new(redirect: Redirect) -> Self {
+        RoutingRule {
+            condition: None,
+            redirect,
+        }
+    }
+
+    /// Only apply the rule to keys starting with `prefix`.
+    pub fn key_prefix_equals<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.condition
+            .get_or_insert_with(Condition::default)
+            .key_prefix_equals = Some(prefix.into());
+        self
+    }
+
+    /// Only apply the rule when the request fails with `code`, e.g. 404.
+    pub fn http_error_code_returned_equals(mut self, code: u16) -> Self {
+        self.condition
+            .get_or_insert_with(Condition::default)
+            .http_error_code_returned_equals = Some(code);
+        self
+    }
+
+    fn matches(&self, key: &str, error_code: Option<u16>) -> bool {
+        let condition = match self.condition {
+            Some(ref condition) => condition,
+            None => return true,
+        };
+
+        condition
+            .key_prefix_equals
+            .iter()
+            .all(|prefix| key.starts_with(prefix.as_str()))
+            && condition
+                .http_error_code_returned_equals
+                .iter()
+                .all(|&code| error_code == Some(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml;
+
+    #[test]
+    fn configuration_round_trip() {
+        let response = concat!(
+            r#"<WebsiteConfiguration xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+            "<ErrorDocument><Key>error.html</Key></ErrorDocument>",
+            "<IndexDocument><Suffix>index.html</Suffix></IndexDocument>",
+            "<RoutingRules><RoutingRule>",
+            "<Condition><KeyPrefixEquals>docs/</KeyPrefixEquals></Condition>",
+            "<Redirect><ReplaceKeyPrefixWith>documents/</ReplaceKeyPrefixWith></Redirect>",
+            "</RoutingRule></RoutingRules>",
+            "</WebsiteConfiguration>"
+        );
+
+        let parsed: BucketWebsite = quick_xml::de::from_str(response).unwrap();
+        let website = BucketWebsite::new("index.html")
+            .error_document("error.html")
+            .routing_rule(
+                RoutingRule::new(Redirect::new().replace_key_prefix_with("documents/"))
+                    .key_prefix_equals("docs/"),
+            );
+        assert_eq!(parsed, website);
+        assert_eq!(xml::to_string(&website).unwrap(), response);
+        assert!(website.validate().is_ok());
+
+        let redirect: BucketWebsite = quick_xml::de::from_str(concat!(
+            "<WebsiteConfiguration><RedirectAllRequestsTo>",
+            "<HostName>example.com</HostName><Protocol>https</Protocol>",
+            "</RedirectAllRequestsTo></WebsiteConfiguration>"
+        ))
+        .unwrap();
+        assert_eq!(
+            redirect,
+            BucketWebsite::redirect_all("example.com", Some(Protocol::Https))
+        );
+    }
+
+    #[test]
+    fn route_applies_first_matching_rule() {
+        let host = "bucket.s3-website-us-east-1.amazonaws.com";
+        let website = BucketWebsite::new("index.html")
+            .routing_rule(
+                RoutingRule::new(Redirect::new().replace_key_prefix_with("documents/"))
+                    .key_prefix_equals("docs/"),
+            )
+            .routing_rule(
+                RoutingRule::new(
+                    Redirect::new()
+                        .host_name("fallback.example.com")
+                        .protocol(Protocol::Https)
+                        .replace_key_prefix_with("report-404/")
+                        .http_redirect_code(302),
+                )
+                .http_error_code_returned_equals(404),
+            );
+
+        let redirect = website.route(host, "docs/a b.html", None).unwrap();
+        assert_eq!(
+            redirect.location,
+            format!("http://{}/documents/a%20b.html", host)
+        );
+        assert_eq!(redirect.status, 301);
+
+        let redirect = website.route(host, "missing.html", Some(404)).unwrap();
+        assert_eq!(
+            redirect.location,
+            "https://fallback.example.com/report-404/missing.html"
+        );
+        assert_eq!(redirect.status, 302);
+
+        assert!(website.route(host, "missing.html", None).is_none());
+        assert!(website.route(host, "missing.html", Some(403)).is_none());
+
+        let website = BucketWebsite::redirect_all("example.com", None);
+        let redirect = website.route(host, "a/b", None).unwrap();
+        assert!(redirect.rule.is_none());
+        assert_eq!(redirect.location, "http://example.com/a/b");
+    }
 }
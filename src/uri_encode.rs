@@ -0,0 +1,52 @@
+use percent_encoding::{
+    percent_decode_str,
+    utf8_percent_encode,
+    AsciiSet,
+    NON_ALPHANUMERIC,
+};
+use std::str::Utf8Error;
+
+// Every character except the unreserved characters 'A'-'Z', 'a'-'z', '0'-'9',
+// '-', '.', '_', and '~' must be percent encoded when signing a request.
+const URI_ENCODE: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+// Object keys keep their forward slashes when used as the uri path
+const URI_ENCODE_PATH: &AsciiSet = &URI_ENCODE.remove(b'/');
+
+// Spaces are left for `encode_form` to write as `+`
+const FORM_ENCODE: &AsciiSet = &URI_ENCODE_PATH.remove(b' ');
+
+pub trait UriEncode {
+    fn uri_encode(&self) -> String;
+    fn uri_encode_path(&self) -> String;
+}
+
+impl<T: AsRef<str>> UriEncode for T {
+    fn uri_encode(&self) -> String {
+        utf8_percent_encode(self.as_ref(), URI_ENCODE).to_string()
+    }
+
+    fn uri_encode_path(&self) -> String {
+        utf8_percent_encode(self.as_ref(), URI_ENCODE_PATH).to_string()
+    }
+}
+
+/// Decode a value encoded like a html form, with spaces written as `+`.
+/// S3 encodes keys this way in event notifications and inventory reports.
+pub(crate) fn decode_form(value: &str) -> Result<String, Utf8Error> {
+    percent_decode_str(&value.replace('+', " "))
+        .decode_utf8()
+        .map(|value| value.into_owned())
+}
+
+/// Encode a value the way `decode_form` expects, keeping forward slashes as
+/// S3 does.
+pub(crate) fn encode_form(value: &str) -> String {
+    utf8_percent_encode(value, FORM_ENCODE)
+        .to_string()
+        .replace(' ', "+")
+}
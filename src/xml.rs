@@ -0,0 +1,426 @@
+// The serializer provided by quick-xml wraps nested structs and sequences
+// in an extra element named after the type and does not support unit
+// variants, neither of which S3 will accept. This is a small serializer
+// that writes the layout S3 expects: struct fields become elements, `None`
+// fields are skipped, sequences repeat the field element for every item,
+// and unit variants are written as text. Fields renamed with a leading `@`
+// are written as attributes of the struct element.
+use quick_xml::DeError;
+use serde::{
+    de::{
+        self,
+        Deserialize,
+        Deserializer,
+    },
+    ser::{
+        self,
+        Impossible,
+        Serialize,
+    },
+};
+use std::str::FromStr;
+
+const NAMESPACE: &str = "http://s3.amazonaws.com/doc/2006-03-01/";
+
+/// Serialize a struct into an S3 xml document. The root element is named
+/// after the struct (or its `#[serde(rename)]`).
+pub(crate) fn to_string<T: Serialize>(value: &T) -> Result<String, DeError> {
+    let mut output = String::new();
+    value.serialize(Element {
+        output: &mut output,
+        name: None,
+    })?;
+    Ok(output)
+}
+
+/// quick-xml deserializes enums from element names rather than from text,
+/// so enums written as text are parsed with their `FromStr` implementation.
+pub(crate) fn from_text<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+{
+    let text = String::deserialize(deserializer)?;
+    text.parse()
+        .map_err(|_| de::Error::custom(format!("unexpected value {:?}", text)))
+}
+
+fn escape(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            c => output.push(c),
+        }
+    }
+}
+
+/// Writes a single value as an element. `name` is only `None` for the
+/// root element, in which case the struct name is used.
+struct Element<'o> {
+    output: &'o mut String,
+    name: Option<&'static str>,
+}
+
+impl<'o> Element<'o> {
+    fn text(self, value: &str) -> Result<(), DeError> {
+        let name = self
+            .name
+            .ok_or(DeError::Unsupported("primitive as root element"))?;
+        self.output.push('<');
+        self.output.push_str(name);
+        self.output.push('>');
+        escape(self.output, value);
+        self.output.push_str("</");
+        self.output.push_str(name);
+        self.output.push('>');
+        Ok(())
+    }
+
+    fn open(self, name: &'static str) -> Fields<'o> {
+        let (name, root) = match self.name {
+            Some(name) => (name, false),
+            None => (name, true),
+        };
+
+        self.output.push('<');
+        self.output.push_str(name);
+        if root {
+            self.output.push_str(" xmlns=\"");
+            self.output.push_str(NAMESPACE);
+            self.output.push('"');
+        }
+
+        Fields {
+            output: self.output,
+            name,
+            started: false,
+        }
+    }
+}
+
+/// Writes the fields of a struct and closes the parent element. The start
+/// tag is left open until the first element so attributes can be added.
+struct Fields<'o> {
+    output: &'o mut String,
+    name: &'static str,
+    started: bool,
+}
+
+impl<'o> Fields<'o> {
+    fn field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), DeError> {
+        if let Some(attribute) = key.strip_prefix('@') {
+            return self.attribute(attribute, value);
+        }
+
+        self.start();
+        value.serialize(Element {
+            output: self.output,
+            name: Some(key),
+        })
+    }
+
+    // The value is written as an element named after the attribute first,
+    // which takes care of escaping, and `None` values write nothing.
+    fn attribute<T: ?Sized + Serialize>(
+        &mut self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), DeError> {
+        if self.started {
+            return Err(DeError::Unsupported("attribute after an element"));
+        }
+
+        let mut element = String::new();
+        value.serialize(Element {
+            output: &mut element,
+            name: Some(name),
+        })?;
+
+        if element.is_empty() {
+            return Ok(());
+        }
+
+        let text = element
+            .strip_prefix(&format!("<{}>", name))
+            .and_then(|text| text.strip_suffix(&format!("</{}>", name)))
+            .ok_or(DeError::Unsupported("attribute that isn't text"))?;
+
+        self.output.push(' ');
+        self.output.push_str(name);
+        self.output.push_str("=\"");
+        self.output.push_str(text);
+        self.output.push('"');
+        Ok(())
+    }
+
+    fn start(&mut self) {
+        if !self.started {
+            self.output.push('>');
+            self.started = true;
+        }
+    }
+
+    fn close(mut self) -> Result<(), DeError> {
+        self.start();
+        self.output.push_str("</");
+        self.output.push_str(self.name);
+        self.output.push('>');
+        Ok(())
+    }
+}
+
+/// Repeats the parent element for every item of a sequence.
+struct Items<'o> {
+    output: &'o mut String,
+    name: Option<&'static str>,
+}
+
+macro_rules! serialize_display {
+    ($($fn: ident => $ty: ty),*) => {
+        $(
+            fn $fn(self, value: $ty) -> Result<(), DeError> {
+                self.text(&value.to_string())
+            }
+        )*
+    };
+}
+
+impl<'o> ser::Serializer for Element<'o> {
+    type Ok = ();
+    type Error = DeError;
+    type SerializeSeq = Items<'o>;
+    type SerializeTuple = Impossible<(), DeError>;
+    type SerializeTupleStruct = Impossible<(), DeError>;
+    type SerializeTupleVariant = Impossible<(), DeError>;
+    type SerializeMap = Impossible<(), DeError>;
+    type SerializeStruct = Fields<'o>;
+    type SerializeStructVariant = Impossible<(), DeError>;
+
+    serialize_display!(
+        serialize_bool => bool,
+        serialize_i8 => i8,
+        serialize_i16 => i16,
+        serialize_i32 => i32,
+        serialize_i64 => i64,
+        serialize_u8 => u8,
+        serialize_u16 => u16,
+        serialize_u32 => u32,
+        serialize_u64 => u64,
+        serialize_f32 => f32,
+        serialize_f64 => f64,
+        serialize_char => char
+    );
+
+    fn serialize_str(self, value: &str) -> Result<(), DeError> {
+        self.text(value)
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), DeError> {
+        Err(DeError::Unsupported("serialize_bytes"))
+    }
+
+    fn serialize_none(self) -> Result<(), DeError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), DeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), DeError> {
+        let name = self
+            .name
+            .ok_or(DeError::Unsupported("unit as root element"))?;
+        self.output.push('<');
+        self.output.push_str(name);
+        self.output.push_str("/>");
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> Result<(), DeError> {
+        if self.name.is_some() {
+            self.serialize_unit()
+        } else {
+            self.open(name).close()
+        }
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), DeError> {
+        self.text(variant)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), DeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), DeError> {
+        let mut fields = self.open(name);
+        fields.field(variant, value)?;
+        fields.close()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Items<'o>, DeError> {
+        Ok(Items {
+            output: self.output,
+            name: self.name,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, DeError> {
+        Err(DeError::Unsupported("serialize_tuple"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, DeError> {
+        Err(DeError::Unsupported("serialize_tuple_struct"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, DeError> {
+        Err(DeError::Unsupported("serialize_tuple_variant"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, DeError> {
+        Err(DeError::Unsupported("serialize_map"))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Fields<'o>, DeError> {
+        Ok(self.open(name))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, DeError> {
+        Err(DeError::Unsupported("serialize_struct_variant"))
+    }
+}
+
+impl<'o> ser::SerializeSeq for Items<'o> {
+    type Ok = ();
+    type Error = DeError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), DeError> {
+        value.serialize(Element {
+            output: self.output,
+            name: self.name,
+        })
+    }
+
+    fn end(self) -> Result<(), DeError> {
+        Ok(())
+    }
+}
+
+impl<'o> ser::SerializeStruct for Fields<'o> {
+    type Ok = ();
+    type Error = DeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), DeError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), DeError> {
+        self.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        CompleteMultipartUpload,
+        CompletedPart,
+    };
+
+    #[derive(serde::Serialize)]
+    struct Grantee {
+        #[serde(rename = "@type")]
+        kind: &'static str,
+        #[serde(rename = "@id")]
+        id: Option<&'static str>,
+        #[serde(rename = "URI")]
+        uri: &'static str,
+    }
+
+    #[test]
+    fn attributes() {
+        let grantee = Grantee {
+            kind: "Group",
+            id: None,
+            uri: "a&b",
+        };
+
+        assert_eq!(
+            to_string(&grantee).unwrap(),
+            concat!(
+                r#"<Grantee xmlns="http://s3.amazonaws.com/doc/2006-03-01/" type="Group">"#,
+                "<URI>a&amp;b</URI></Grantee>"
+            )
+        );
+    }
+
+    #[test]
+    fn nested_sequence() {
+        let upload = CompleteMultipartUpload {
+            parts: vec![
+                CompletedPart {
+                    part_number: 1,
+                    etag: "\"a\"".to_owned(),
+                },
+                CompletedPart {
+                    part_number: 2,
+                    etag: "\"b\"".to_owned(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            to_string(&upload).unwrap(),
+            concat!(
+                r#"<CompleteMultipartUpload xmlns="http://s3.amazonaws.com/doc/2006-03-01/">"#,
+                "<Part><PartNumber>1</PartNumber><ETag>&quot;a&quot;</ETag></Part>",
+                "<Part><PartNumber>2</PartNumber><ETag>&quot;b&quot;</ETag></Part>",
+                "</CompleteMultipartUpload>"
+            )
+        );
+    }
+}